@@ -0,0 +1,36 @@
+//! Shared fixtures for the benchmarks, also compiled by `tests/bench_support.rs` so the harness
+//! is checked by `cargo test`
+#![allow(dead_code)]
+
+/// Payload sizes in bytes each benchmark runs against
+pub const PAYLOAD_SIZES: [usize; 3] = [256, 4 * 1024, 64 * 1024];
+
+/// V3 and V4 local keys are 32 bytes
+pub const LOCAL_KEY: &str = "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f";
+
+/// V4 public keys, from the official test vectors
+pub const V4_PRIVATE_KEY: &str = "b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+pub const V4_PUBLIC_KEY: &str = "1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+
+/// V3 public keys, from the official test vectors
+pub const V3_PRIVATE_KEY: &str = "20347609607477aca8fbfbc5e6218455f3199669792ef8b466faa87bdc67798144c848dd03661eed5ac62461340cea96";
+pub const V3_PUBLIC_KEY: &str = "02fbcb7c69ee1c60579be7a334134878d9c5c5bf35d552dab63c0140397ed14cef637d7720925c44699ea30e72874c72fb";
+
+/// Builds a JSON claims payload of roughly `size` bytes, with the registered claims a real token
+/// would carry and the remainder padded out in a custom claim
+pub fn claims_payload(size: usize) -> String {
+  let claims = serde_json::json!({
+    "iss": "https://issuer.example.com",
+    "sub": "loyal subjects",
+    "aud": "customers",
+    "jti": "3f6a1c2e-4b8d-4e0f-9a7c-5d2b1e8f6a90",
+    "iat": "2022-01-01T00:00:00+00:00",
+    "nbf": "2022-01-01T00:00:00+00:00",
+    "exp": "2099-01-01T00:00:00+00:00",
+    "data": "",
+  });
+  let padding = size.saturating_sub(claims.to_string().len());
+  let mut claims = claims;
+  claims["data"] = serde_json::Value::from("a".repeat(padding));
+  claims.to_string()
+}
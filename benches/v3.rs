@@ -0,0 +1,71 @@
+//! Throughput of building and parsing V3 tokens across payload sizes
+//!
+//! Run with `cargo bench --bench v3 --no-default-features --features v3_local,v3_public`, since
+//! V3 public can't be enabled alongside the default V4 public. Criterion keeps the previous run in
+//! `target/criterion`, so benchmarking a branch after `main` reports the change.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rusty_paseto::core::*;
+
+mod support;
+use support::*;
+
+fn v3_local(c: &mut Criterion) {
+  let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::try_from(LOCAL_KEY).unwrap());
+  let nonce = Key::<32>::try_new_random().unwrap();
+  let nonce = PasetoNonce::<V3, Local>::from(&nonce);
+
+  let mut group = c.benchmark_group("v3_local");
+  for size in PAYLOAD_SIZES {
+    let payload = claims_payload(size);
+    let token = Paseto::<V3, Local>::builder()
+      .set_payload(Payload::from(payload.as_str()))
+      .try_encrypt(&key, &nonce)
+      .unwrap();
+
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_with_input(BenchmarkId::new("encrypt", size), &payload, |b, payload| {
+      b.iter(|| {
+        Paseto::<V3, Local>::builder()
+          .set_payload(Payload::from(payload.as_str()))
+          .try_encrypt(&key, &nonce)
+      })
+    });
+    group.bench_with_input(BenchmarkId::new("decrypt", size), &token, |b, token| {
+      b.iter(|| Paseto::<V3, Local>::try_decrypt(token, &key, None, None))
+    });
+  }
+  group.finish();
+}
+
+fn v3_public(c: &mut Criterion) {
+  let private_key = Key::<48>::try_from(V3_PRIVATE_KEY).unwrap();
+  let private_key = PasetoAsymmetricPrivateKey::<V3, Public>::from(&private_key);
+  let public_key = Key::<49>::try_from(V3_PUBLIC_KEY).unwrap();
+  let public_key = PasetoAsymmetricPublicKey::<V3, Public>::try_from(&public_key).unwrap();
+
+  let mut group = c.benchmark_group("v3_public");
+  for size in PAYLOAD_SIZES {
+    let payload = claims_payload(size);
+    let token = Paseto::<V3, Public>::builder()
+      .set_payload(Payload::from(payload.as_str()))
+      .try_sign(&private_key)
+      .unwrap();
+
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_with_input(BenchmarkId::new("sign", size), &payload, |b, payload| {
+      b.iter(|| {
+        Paseto::<V3, Public>::builder()
+          .set_payload(Payload::from(payload.as_str()))
+          .try_sign(&private_key)
+      })
+    });
+    group.bench_with_input(BenchmarkId::new("verify", size), &token, |b, token| {
+      b.iter(|| Paseto::<V3, Public>::try_verify(token, &public_key, None, None))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, v3_local, v3_public);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+//! Throughput of building and parsing V4 tokens across payload sizes
+//!
+//! Run with `cargo bench --bench v4 --features v4_local,v4_public`. Criterion keeps the
+//! previous run in `target/criterion`, so benchmarking a branch after `main` reports the change.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rusty_paseto::core::*;
+
+mod support;
+use support::*;
+
+fn v4_local(c: &mut Criterion) {
+  let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(LOCAL_KEY).unwrap());
+  let nonce = Key::<32>::try_new_random().unwrap();
+  let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+
+  let mut group = c.benchmark_group("v4_local");
+  for size in PAYLOAD_SIZES {
+    let payload = claims_payload(size);
+    let token = Paseto::<V4, Local>::builder()
+      .set_payload(Payload::from(payload.as_str()))
+      .try_encrypt(&key, &nonce)
+      .unwrap();
+
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_with_input(BenchmarkId::new("encrypt", size), &payload, |b, payload| {
+      b.iter(|| {
+        Paseto::<V4, Local>::builder()
+          .set_payload(Payload::from(payload.as_str()))
+          .try_encrypt(&key, &nonce)
+      })
+    });
+    group.bench_with_input(BenchmarkId::new("decrypt", size), &token, |b, token| {
+      b.iter(|| Paseto::<V4, Local>::try_decrypt(token, &key, None, None))
+    });
+  }
+  group.finish();
+}
+
+fn v4_public(c: &mut Criterion) {
+  let private_key = Key::<64>::try_from(V4_PRIVATE_KEY).unwrap();
+  let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+  let public_key = Key::<32>::try_from(V4_PUBLIC_KEY).unwrap();
+  let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+
+  let mut group = c.benchmark_group("v4_public");
+  for size in PAYLOAD_SIZES {
+    let payload = claims_payload(size);
+    let token = Paseto::<V4, Public>::builder()
+      .set_payload(Payload::from(payload.as_str()))
+      .try_sign(&private_key)
+      .unwrap();
+
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_with_input(BenchmarkId::new("sign", size), &payload, |b, payload| {
+      b.iter(|| {
+        Paseto::<V4, Public>::builder()
+          .set_payload(Payload::from(payload.as_str()))
+          .try_sign(&private_key)
+      })
+    });
+    group.bench_with_input(BenchmarkId::new("verify", size), &token, |b, token| {
+      b.iter(|| Paseto::<V4, Public>::try_verify(token, &public_key, None, None))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, v4_local, v4_public);
+criterion_main!(benches);
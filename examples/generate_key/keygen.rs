@@ -0,0 +1,32 @@
+//! Generates fresh (V4, Local) and (V4, Public) keys and encodes them as PASERK strings.
+//!
+//! PASERK strings carry their version and type in a prefix (`k4.local.`, `k4.secret.`,
+//! `k4.public.`), so they can be stored in configuration or environment variables and decoded back
+//! into keys without guessing how the bytes were encoded.
+use rusty_paseto::core::*;
+
+/// Freshly generated keys, encoded as PASERK strings
+pub struct GeneratedKeys {
+  /// The `k4.local.` symmetric key
+  pub local: String,
+  /// The `k4.secret.` half of the Ed25519 keypair, used to sign tokens
+  pub secret: String,
+  /// The `k4.public.` half of the Ed25519 keypair, used to verify tokens
+  pub public: String,
+}
+
+/// Generates a random symmetric key and a random Ed25519 keypair
+pub fn generate() -> Result<GeneratedKeys, PasetoError> {
+  let local = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+
+  let seed = Key::<32>::try_new_random()?;
+  let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
+  let secret = Key::<64>::from(signing_key.to_keypair_bytes());
+  let public = Key::<32>::from(signing_key.verifying_key().to_bytes());
+
+  Ok(GeneratedKeys {
+    local: local.to_paserk(),
+    secret: PasetoAsymmetricPrivateKey::<V4, Public>::from(&secret).to_paserk(),
+    public: PasetoAsymmetricPublicKey::<V4, Public>::from(&public).to_paserk(),
+  })
+}
@@ -0,0 +1,24 @@
+/// This example generates ready-to-use keys and prints them as PASERK strings, e.g. to paste into
+/// configuration when setting up a service.
+///
+/// A (V4, Local) key is printed as `k4.local.`, and a (V4, Public) keypair as `k4.secret.` for
+/// signing and `k4.public.` for verifying. Each decodes back into a key with
+/// `PasetoSymmetricKey::<V4, Local>::try_from`,
+/// `PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk` and
+/// `PasetoAsymmetricPublicKey::<V4, Public>::try_from` respectively.
+///
+/// cargo run --example generate_key
+///
+use rusty_paseto::core::PasetoError;
+
+mod keygen;
+
+fn main() -> Result<(), PasetoError> {
+  let keys = keygen::generate()?;
+
+  println!("PASETO_LOCAL_KEY={}", keys.local);
+  println!("PASETO_SECRET_KEY={}", keys.secret);
+  println!("PASETO_PUBLIC_KEY={}", keys.public);
+
+  Ok(())
+}
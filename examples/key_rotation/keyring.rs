@@ -0,0 +1,71 @@
+//! A minimal keyring for rotating (V4, Local) keys.
+//!
+//! The first key in the ring is the current key and is used to mint new tokens. The id of the key
+//! used to mint a token is stored in its footer so the matching key can be found when parsing,
+//! which lets tokens minted under a previous key keep parsing after a rotation.
+use base64::prelude::*;
+use rusty_paseto::prelude::*;
+use serde_json::{json, Value};
+
+pub struct Keyring {
+  keys: Vec<(String, PasetoSymmetricKey<V4, Local>)>,
+}
+
+impl Keyring {
+  /// Creates a keyring with a single current key
+  pub fn new(kid: &str, key: PasetoSymmetricKey<V4, Local>) -> Self {
+    Self {
+      keys: vec![(kid.to_string(), key)],
+    }
+  }
+
+  /// Rotates the keyring by prepending a new current key. Previous keys are kept for parsing.
+  pub fn rotate(&mut self, kid: &str, key: PasetoSymmetricKey<V4, Local>) -> &mut Self {
+    self.keys.insert(0, (kid.to_string(), key));
+    self
+  }
+
+  /// The id of the current key
+  pub fn current_kid(&self) -> &str {
+    &self.keys[0].0
+  }
+
+  /// Mints a token for the subject under the current key, storing the key id in the footer
+  pub fn mint(&self, subject: &str) -> Result<String, GeneralPasetoError> {
+    let (kid, key) = &self.keys[0];
+    let footer = json!({ "kid": kid }).to_string();
+
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from(subject))
+      .set_footer(Footer::from(footer.as_str()))
+      .build(key)?;
+    Ok(token)
+  }
+
+  /// Parses a token with the key named by the key id in its footer
+  pub fn parse(&self, token: &str) -> Result<Value, GeneralPasetoError> {
+    let footer = Self::footer(token).ok_or(GenericParserError::from(PasetoError::FooterInvalid))?;
+    let kid = serde_json::from_str::<Value>(&footer)
+      .map_err(GenericParserError::from)?
+      .get("kid")
+      .and_then(Value::as_str)
+      .map(str::to_string)
+      .ok_or(GenericParserError::from(PasetoError::FooterInvalid))?;
+    let (_, key) = self
+      .keys
+      .iter()
+      .find(|(id, _)| *id == kid)
+      .ok_or(GenericParserError::from(PasetoError::FooterInvalid))?;
+
+    let claims = PasetoParser::<V4, Local>::default()
+      .set_footer(Footer::from(footer.as_str()))
+      .parse(token, key)?;
+    Ok(claims)
+  }
+
+  /// The decoded (unverified) footer of a token, if it has one
+  fn footer(token: &str) -> Option<String> {
+    let footer = token.split('.').nth(3)?;
+    String::from_utf8(BASE64_URL_SAFE_NO_PAD.decode(footer).ok()?).ok()
+  }
+}
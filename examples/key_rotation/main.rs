@@ -0,0 +1,40 @@
+/// This example shows how you might rotate (V4, Local) keys without invalidating tokens minted
+/// under a previous key.
+///
+/// Tokens are minted under the current key of a [Keyring](keyring::Keyring) with the key id in
+/// the footer. Rotating prepends a new current key, so new tokens carry the new key id while
+/// tokens minted before the rotation still parse with the key they were minted under.
+///
+/// cargo run --example key_rotation
+///
+use rusty_paseto::prelude::*;
+
+mod keyring;
+use keyring::Keyring;
+
+fn main() -> Result<(), GeneralPasetoError> {
+  let mut keyring = Keyring::new(
+    "key-1",
+    PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub")),
+  );
+
+  // mint a token under the first key
+  let old_token = keyring.mint("loyal subjects")?;
+  println!("minted under {}: {}", keyring.current_kid(), old_token);
+
+  // rotate by prepending a new random key
+  let new_key = Key::<32>::try_new_random().map_err(GenericBuilderError::from)?;
+  keyring.rotate("key-2", PasetoSymmetricKey::<V4, Local>::from(new_key));
+
+  // new tokens are minted under the new key
+  let new_token = keyring.mint("loyal subjects")?;
+  println!("minted under {}: {}", keyring.current_kid(), new_token);
+
+  // both tokens still parse
+  let old_claims = keyring.parse(&old_token)?;
+  let new_claims = keyring.parse(&new_token)?;
+  println!("old token subject: {}", old_claims["sub"]);
+  println!("new token subject: {}", new_claims["sub"]);
+
+  Ok(())
+}
@@ -0,0 +1,24 @@
+//! Verifies (V4, Public) and decrypts (V4, Local) PASETO tokens from JavaScript using wasm-bindgen.
+//!
+//! See the [readme](./readme.md) for build instructions.
+use rusty_paseto::core::*;
+use wasm_bindgen::prelude::*;
+
+/// Verifies a (V4, Public) token with a hex encoded public key, returning the token payload
+#[wasm_bindgen]
+pub fn verify_v4_public(token: &str, public_key: &str, footer: Option<String>) -> Result<String, JsError> {
+  let public_key = Key::<32>::try_from(public_key)?;
+  let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+  let footer = footer.as_deref().map(Footer::from);
+
+  Ok(Paseto::<V4, Public>::try_verify(token, &public_key, footer, None)?)
+}
+
+/// Decrypts a (V4, Local) token with a hex encoded symmetric key, returning the token payload
+#[wasm_bindgen]
+pub fn decrypt_v4_local(token: &str, key: &str, footer: Option<String>) -> Result<String, JsError> {
+  let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(key)?);
+  let footer = footer.as_deref().map(Footer::from);
+
+  Ok(Paseto::<V4, Local>::try_decrypt(token, &key, footer, None)?)
+}
@@ -0,0 +1,116 @@
+use super::PasetoError;
+
+/// Argon2id parameters used to derive a wrapping key from a password, e.g. with
+/// [PasetoSymmetricKey::local_pw_wrap](crate::core::PasetoSymmetricKey::local_pw_wrap).
+///
+/// Weak parameters make brute forcing a wrapped key cheap, so they're checked with
+/// [validate](Argon2Params::validate) before a key is wrapped unless `allow_weak` is set.  The
+/// documented minimums follow the OWASP recommendation for Argon2id of at least 19 MiB of memory
+/// and 2 iterations.
+///
+/// # Usage
+/// ```
+/// # use rusty_paseto::core::*;
+/// let params = Argon2Params::recommended();
+/// assert!(params.validate().is_ok());
+///
+/// let weak = Argon2Params::new(1024, 1, 1);
+/// assert!(weak.validate().is_err());
+///
+/// //e.g. for fast tests, weak parameters can be explicitly allowed when wrapping
+/// let weak = Argon2Params { allow_weak: true, ..weak };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+  /// Memory cost in KiB
+  pub memory_kib: u32,
+  /// Number of iterations (time cost)
+  pub iterations: u32,
+  /// Degree of parallelism
+  pub parallelism: u32,
+  /// Wraps keys even if the parameters are below the documented minimums
+  pub allow_weak: bool,
+}
+
+impl Argon2Params {
+  /// The minimum accepted memory cost in KiB (19 MiB)
+  pub const MIN_MEMORY_KIB: u32 = 19 * 1024;
+  /// The minimum accepted number of iterations
+  pub const MIN_ITERATIONS: u32 = 2;
+  /// The minimum accepted degree of parallelism
+  pub const MIN_PARALLELISM: u32 = 1;
+
+  /// Creates a set of Argon2id parameters
+  pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+    Self {
+      memory_kib,
+      iterations,
+      parallelism,
+      allow_weak: false,
+    }
+  }
+
+  /// The recommended parameters for interactive use: 64 MiB of memory, 2 iterations and a
+  /// parallelism of 1 (libsodium's interactive limits)
+  pub fn recommended() -> Self {
+    Self::new(64 * 1024, 2, 1)
+  }
+
+  /// Rejects parameters below the documented minimums with [PasetoError::WeakKdfParams],
+  /// regardless of `allow_weak`
+  pub fn validate(&self) -> Result<(), PasetoError> {
+    if self.memory_kib < Self::MIN_MEMORY_KIB
+      || self.iterations < Self::MIN_ITERATIONS
+      || self.parallelism < Self::MIN_PARALLELISM
+    {
+      return Err(PasetoError::WeakKdfParams);
+    }
+    Ok(())
+  }
+}
+
+impl Default for Argon2Params {
+  fn default() -> Self {
+    Self::recommended()
+  }
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+  use super::*;
+
+  #[test]
+  fn test_recommended_params_are_valid() {
+    assert!(Argon2Params::recommended().validate().is_ok());
+    assert!(Argon2Params::default().validate().is_ok());
+    assert!(Argon2Params::new(
+      Argon2Params::MIN_MEMORY_KIB,
+      Argon2Params::MIN_ITERATIONS,
+      Argon2Params::MIN_PARALLELISM
+    )
+    .validate()
+    .is_ok());
+  }
+
+  #[test]
+  fn test_weak_params_are_rejected() {
+    let recommended = Argon2Params::recommended();
+    let weak_memory = Argon2Params {
+      memory_kib: Argon2Params::MIN_MEMORY_KIB - 1,
+      ..recommended
+    };
+    let weak_iterations = Argon2Params {
+      iterations: 1,
+      ..recommended
+    };
+    let no_parallelism = Argon2Params {
+      parallelism: 0,
+      ..recommended
+    };
+
+    for params in [weak_memory, weak_iterations, no_parallelism] {
+      assert!(matches!(params.validate(), Err(PasetoError::WeakKdfParams)));
+    }
+  }
+}
@@ -0,0 +1,60 @@
+use base64::prelude::*;
+use base64::DecodeError;
+
+/// Encodes and decodes the base64 segments of a PASETO token.
+///
+/// PASETO tokens use the url-safe base64 alphabet without padding, which is what
+/// [DefaultBase64Codec] provides. Implement this trait to plug in another implementation of
+/// the same encoding, such as a SIMD or constant-time codec, and name it as the last type
+/// parameter of [Paseto](crate::core::Paseto).
+///
+/// # Example usage
+/// ```
+/// # #[cfg(feature = "v4_local")]
+/// # {
+/// # use rusty_paseto::core::*;
+/// use base64::prelude::*;
+///
+/// #[derive(Default, Clone, Copy)]
+/// struct MyCodec;
+///
+/// impl Base64Codec for MyCodec {
+///   fn encode(input: &[u8]) -> String {
+///     BASE64_URL_SAFE_NO_PAD.encode(input)
+///   }
+///   fn decode(input: &str) -> Result<Vec<u8>, base64::DecodeError> {
+///     BASE64_URL_SAFE_NO_PAD.decode(input)
+///   }
+/// }
+///
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+/// # let nonce = Key::<32>::try_new_random()?;
+/// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+/// let token = Paseto::<V4, Local, MyCodec>::builder()
+///   .set_payload(Payload::from("a secret"))
+///   .try_encrypt(&key, &nonce)?;
+/// let payload = Paseto::<V4, Local, MyCodec>::try_decrypt(&token, &key, None, None)?;
+/// # assert_eq!(payload, "a secret");
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+pub trait Base64Codec {
+  /// Encodes bytes as url-safe base64 without padding
+  fn encode(input: &[u8]) -> String;
+  /// Decodes url-safe base64 without padding into bytes
+  fn decode(input: &str) -> Result<Vec<u8>, DecodeError>;
+}
+
+/// The url-safe, unpadded base64 codec from the base64 crate used by default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultBase64Codec;
+
+impl Base64Codec for DefaultBase64Codec {
+  fn encode(input: &[u8]) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(input)
+  }
+
+  fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+    BASE64_URL_SAFE_NO_PAD.decode(input)
+  }
+}
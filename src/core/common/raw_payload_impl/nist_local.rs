@@ -1,13 +1,12 @@
 #![cfg(any(feature = "v1_local", feature = "v3_local"))]
-use base64::prelude::*;
 use crate::core::common::RawPayload;
-use crate::core::{Local, PasetoError, PasetoNonce, V1orV3};
+use crate::core::{Base64Codec, Local, PasetoError, PasetoNonce, V1orV3};
 
 impl<Version> RawPayload<Version, Local>
     where
         Version: V1orV3,
 {
-    pub(crate) fn from(
+    pub(crate) fn from<Codec: Base64Codec>(
         nonce: &PasetoNonce<Version, Local>,
         ciphertext: &impl AsRef<Vec<u8>>,
         tag: &impl AsRef<[u8]>,
@@ -24,6 +23,6 @@ impl<Version> RawPayload<Version, Local>
             .copy_from_slice(ciphertext.as_ref());
         raw_token[concat_len - tag_len..].copy_from_slice(tag.as_ref());
 
-        Ok(BASE64_URL_SAFE_NO_PAD.encode(&raw_token))
+        Ok(Codec::encode(&raw_token))
     }
 }
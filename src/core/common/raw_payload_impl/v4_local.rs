@@ -1,10 +1,9 @@
 #![cfg(feature = "v4_local")]
-use base64::prelude::*;
 use crate::core::common::RawPayload;
-use crate::core::{Local, PasetoError, PasetoNonce, V4};
+use crate::core::{Base64Codec, Local, PasetoError, PasetoNonce, V4};
 
 impl RawPayload<V4, Local> {
-    pub(crate) fn try_from(
+    pub(crate) fn try_from<Codec: Base64Codec>(
         nonce: &PasetoNonce<V4, Local>,
         ciphertext: &impl AsRef<Vec<u8>>,
         tag: &impl AsRef<[u8]>,
@@ -21,6 +20,6 @@ impl RawPayload<V4, Local> {
             .copy_from_slice(ciphertext.as_ref());
         raw_token[concat_len - tag_len..].copy_from_slice(tag.as_ref());
 
-        Ok(BASE64_URL_SAFE_NO_PAD.encode(&raw_token))
+        Ok(Codec::encode(&raw_token))
     }
 }
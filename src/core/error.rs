@@ -1,12 +1,35 @@
 use std::array::TryFromSliceError;
 use thiserror::Error;
 
+/// Coarse categories of the errors raised throughout the crate, returned by the `kind()` method on
+/// each error type.
+///
+/// Matching on a kind rather than on individual error variants keeps code compiling as new, more
+/// specific variants are added. New kinds may still be added, so a match needs a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+  ///The token string is malformed, or is not of the expected version or purpose
+  MalformedToken,
+  ///The token failed decryption or signature verification, or its footer was rejected
+  Authentication,
+  ///The token is authentic but its claims failed validation
+  InvalidClaims,
+  ///A key is malformed, of the wrong kind or was rejected
+  Key,
+  ///A builder or parser was misused, e.g. by setting a reserved claim
+  Configuration,
+  ///Any other failure, such as an I/O error
+  Other,
+}
+
 /// Potential errors from attempting to build a token claim
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum PasetoError {
   ///A general, unspecified (for security reasons) cipher error
   #[error("A cipher error occurred")]
-  PasetoCipherError(Box<PasetoError>),
+  PasetoCipherError(#[source] Box<PasetoError>),
   ///A general, unspecified (for security reasons) cipher error
   #[error("An unspecified cryption error occured")]
   Cryption,
@@ -66,12 +89,42 @@ pub enum PasetoError {
   ///Occurs when an untrusted token string is unable to be parsed into its constituent parts
   #[error("This string has an incorrect number of parts and cannot be parsed into a token")]
   IncorrectSize,
+  ///Occurs when an untrusted token string is missing one of its required parts
+  #[error("The token is malformed: {reason}")]
+  MalformedToken {
+    ///Describes which part of the token is missing or empty
+    reason: &'static str,
+  },
   ///Occurs when an incorrect header is provided on an untrusted token string
   #[error("The token header is invalid")]
   WrongHeader,
+  ///Occurs when an untrusted token string is a PASETO token of another version, e.g. a `v3` token
+  ///presented to a `v4` parser
+  #[error("The token version does not match the expected version '{expected}'")]
+  VersionMismatch {
+    ///The version that was expected
+    expected: &'static str,
+  },
+  ///Occurs when an untrusted token string has the expected version but the wrong purpose, e.g. a
+  ///`local` token presented for verification as a `public` token
+  #[error("The token purpose does not match the expected purpose '{expected}'")]
+  PurposeMismatch {
+    ///The purpose that was expected
+    expected: &'static str,
+  },
   ///Occurs when an incorrect footer was passed in an attempt to parse an untrusted token string
   #[error("The provided footer is invalid")]
   FooterInvalid,
+  ///Occurs when an untrusted token string carries a footer outside the parser's footer allowlist
+  #[error("The token footer is not in the allowed set")]
+  FooterNotAllowed,
+  ///Occurs when a parser with opaque errors fails to decrypt or verify a token, in place of the more
+  ///specific error
+  #[error("The token could not be decrypted or verified")]
+  VerificationFailed,
+  ///Occurs when a parser requiring a footer is given an untrusted token string without one
+  #[error("The token has no footer but one is required")]
+  MissingFooter,
   ///Occurs when a base64 encoded payload cannot be decoded
   #[error("A base64 decode error occurred")]
   PayloadBase64Decode {
@@ -103,4 +156,131 @@ pub enum PasetoError {
     #[from]
     source: std::string::FromUtf8Error,
   },
+  ///Occurs when a PASERK string isn't a key of the expected version and type
+  #[error("The PASERK is not a valid {expected} key")]
+  InvalidPaserk {
+    ///The PASERK prefix that was expected
+    expected: &'static str,
+  },
+  ///Occurs when a key can't be loaded from an environment variable
+  #[error("Could not load a key from the environment variable '{variable}': {reason}")]
+  KeyLoad {
+    ///The name of the environment variable
+    variable: String,
+    ///Why the variable's value was rejected, without the value itself
+    reason: String,
+  },
+  ///Occurs when a public key is loaded from a slice of the wrong length
+  #[error("The public key must be {expected} bytes but was {actual} bytes")]
+  InvalidPublicKeyLength {
+    ///The length a public key of the version must have
+    expected: usize,
+    ///The length of the slice provided
+    actual: usize,
+  },
+  ///Occurs when a [NonceTracker](crate::core::NonceTracker) sees the same nonce twice
+  #[cfg(feature = "nonce_tracking")]
+  #[error("A nonce was used more than once")]
+  NonceReuse,
+  ///Occurs when key derivation parameters are below the documented minimums
+  #[cfg(feature = "pbkw")]
+  #[error("The key derivation parameters are too weak")]
+  WeakKdfParams,
+  ///Occurs when a token cannot be written to its destination
+  #[error("The token could not be written")]
+  Io {
+    ///Surfaced from std::io
+    #[from]
+    source: std::io::Error,
+  },
+}
+
+impl PasetoError {
+  /// The coarse [ErrorKind] of this error
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      PasetoError::PasetoCipherError(source) => source.kind(),
+      PasetoError::IncorrectSize
+      | PasetoError::MalformedToken { .. }
+      | PasetoError::WrongHeader
+      | PasetoError::VersionMismatch { .. }
+      | PasetoError::PurposeMismatch { .. }
+      | PasetoError::PayloadBase64Decode { .. }
+      | PasetoError::Utf8Error { .. }
+      | PasetoError::MissingFooter
+      | PasetoError::FromUtf8Error { .. } => ErrorKind::MalformedToken,
+      PasetoError::Cryption
+      | PasetoError::Cipher { .. }
+      | PasetoError::InvalidSignature
+      | PasetoError::FooterInvalid
+      | PasetoError::FooterNotAllowed
+      | PasetoError::VerificationFailed
+      | PasetoError::ChaChaCipherError => ErrorKind::Authentication,
+      #[cfg(feature = "ed25519-dalek")]
+      PasetoError::RsaCipher { .. } => ErrorKind::Authentication,
+      #[cfg(feature = "p384")]
+      PasetoError::ECSDAError { .. } => ErrorKind::Authentication,
+      #[cfg(feature = "blake2")]
+      PasetoError::InvalidLength { .. } => ErrorKind::Key,
+      PasetoError::InvalidKey
+      | PasetoError::KeyRejected { .. }
+      | PasetoError::TryFromSlice { .. }
+      | PasetoError::InvalidPaserk { .. }
+      | PasetoError::KeyLoad { .. }
+      | PasetoError::InvalidPublicKeyLength { .. } => ErrorKind::Key,
+      #[cfg(feature = "pbkw")]
+      PasetoError::WeakKdfParams => ErrorKind::Key,
+      #[cfg(feature = "nonce_tracking")]
+      PasetoError::NonceReuse => ErrorKind::Configuration,
+      PasetoError::Signature | PasetoError::Infallibale { .. } | PasetoError::Io { .. } => ErrorKind::Other,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl PasetoError {
+  /// The HTTP status code a web service should respond with when a token fails with this error
+  ///
+  /// Malformed input maps to `400`, failed decryption or verification to `401` and problems with
+  /// keys or the local environment to `500`.
+  pub fn http_status(&self) -> u16 {
+    match self {
+      PasetoError::IncorrectSize
+      | PasetoError::MalformedToken { .. }
+      | PasetoError::WrongHeader
+      | PasetoError::VersionMismatch { .. }
+      | PasetoError::PurposeMismatch { .. }
+      | PasetoError::PayloadBase64Decode { .. }
+      | PasetoError::Utf8Error { .. }
+      | PasetoError::MissingFooter
+      | PasetoError::FromUtf8Error { .. } => 400,
+      PasetoError::PasetoCipherError(_)
+      | PasetoError::Cryption
+      | PasetoError::Cipher { .. }
+      | PasetoError::InvalidSignature
+      | PasetoError::FooterInvalid
+      | PasetoError::FooterNotAllowed
+      | PasetoError::VerificationFailed
+      | PasetoError::ChaChaCipherError => 401,
+      #[cfg(feature = "ed25519-dalek")]
+      PasetoError::RsaCipher { .. } => 401,
+      #[cfg(feature = "p384")]
+      PasetoError::ECSDAError { .. } => 401,
+      #[cfg(feature = "blake2")]
+      PasetoError::InvalidLength { .. } => 500,
+      #[cfg(feature = "nonce_tracking")]
+      PasetoError::NonceReuse => 500,
+      PasetoError::InvalidKey
+      | PasetoError::Signature
+      | PasetoError::KeyRejected { .. }
+      | PasetoError::TryFromSlice { .. }
+      | PasetoError::Infallibale { .. }
+      | PasetoError::InvalidPaserk { .. }
+      | PasetoError::KeyLoad { .. }
+      | PasetoError::InvalidPublicKeyLength { .. }
+      | PasetoError::Io { .. } => 500,
+      #[cfg(feature = "pbkw")]
+      PasetoError::WeakKdfParams => 500,
+    }
+  }
 }
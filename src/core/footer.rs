@@ -26,6 +26,23 @@ use std::ops::Deref;
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Footer<'a>(&'a str);
 
+impl<'a> Footer<'a> {
+  /// The length of the footer in bytes
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns true if the footer has no content
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The raw bytes of the footer
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.0.as_bytes()
+  }
+}
+
 impl<'a> Base64Encodable<str> for Footer<'a> {}
 
 impl<'a> Deref for Footer<'a> {
@@ -76,4 +93,17 @@ mod unit_tests {
     assert_eq!(footer.as_ref(), "wubbulubbadubdub");
     assert!(!footer.as_ref().is_empty());
   }
+
+  #[test]
+  fn test_footer_len_and_emptiness() {
+    let footer = Footer::from("");
+    assert!(footer.is_empty());
+    assert_eq!(footer.len(), 0);
+    assert_eq!(footer.as_bytes(), b"");
+
+    let footer = Footer::from("wubbulubbadubdub");
+    assert!(!footer.is_empty());
+    assert_eq!(footer.len(), 16);
+    assert_eq!(footer.as_bytes(), b"wubbulubbadubdub");
+  }
 }
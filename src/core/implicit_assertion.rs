@@ -26,6 +26,51 @@ use std::ops::Deref;
 #[derive(Default, Debug, Copy, Clone)]
 pub struct ImplicitAssertion<'a>(&'a str);
 
+impl<'a> ImplicitAssertion<'a> {
+  /// The length of the implicit assertion in bytes
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns true if the implicit assertion has no content
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The raw bytes of the implicit assertion
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.0.as_bytes()
+  }
+}
+
+#[cfg(feature = "uuid")]
+impl<'a> ImplicitAssertion<'a> {
+  /// Binds a token to a UUID, e.g. the id of the user or request, using its canonical lowercase
+  /// hyphenated form.
+  ///
+  /// An implicit assertion borrows its content, so the UUID is formatted into `buffer`, which
+  /// [uuid::Uuid::encode_buffer] provides.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buffer` is shorter than [uuid::fmt::Hyphenated::LENGTH].
+  ///
+  /// # Usage
+  /// ```
+  /// # #[cfg(feature = "uuid")]
+  /// # {
+  /// # use rusty_paseto::core::*;
+  /// let user_id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+  /// let mut buffer = uuid::Uuid::encode_buffer();
+  /// let assertion = ImplicitAssertion::from_uuid(&user_id, &mut buffer);
+  /// assert_eq!(assertion.as_ref(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+  /// # }
+  /// ```
+  pub fn from_uuid(uuid: &uuid::Uuid, buffer: &'a mut [u8]) -> Self {
+    Self(uuid.hyphenated().encode_lower(buffer))
+  }
+}
+
 impl<'a> Deref for ImplicitAssertion<'a> {
   type Target = [u8];
 
@@ -55,3 +100,51 @@ impl<'a> PartialEq for ImplicitAssertion<'a> {
   }
 }
 impl<'a> Eq for ImplicitAssertion<'a> {}
+
+#[cfg(test)]
+mod unit_tests {
+
+  use super::*;
+
+  #[test]
+  fn test_implicit_assertion_len_and_emptiness() {
+    let assertion = ImplicitAssertion::from("");
+    assert!(assertion.is_empty());
+    assert_eq!(assertion.len(), 0);
+
+    let assertion = ImplicitAssertion::from("wubbulubbadubdub");
+    assert!(!assertion.is_empty());
+    assert_eq!(assertion.len(), 16);
+    assert_eq!(assertion.as_bytes(), b"wubbulubbadubdub");
+  }
+
+  #[cfg(all(feature = "uuid", feature = "v4_local"))]
+  #[test]
+  fn test_uuid_implicit_assertion() {
+    use crate::core::*;
+
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub");
+    let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    let user_id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+    let mut buffer = uuid::Uuid::encode_buffer();
+    let assertion = ImplicitAssertion::from_uuid(&user_id, &mut buffer);
+    assert_eq!(assertion.as_ref(), user_id.to_string());
+
+    let token = Paseto::<V4, Local>::builder()
+      .set_payload(Payload::from("bound to a user"))
+      .set_implicit_assertion(assertion)
+      .try_encrypt(&key, &nonce)
+      .unwrap();
+
+    let mut buffer = uuid::Uuid::encode_buffer();
+    let same = ImplicitAssertion::from_uuid(&user_id, &mut buffer);
+    let payload = Paseto::<V4, Local>::try_decrypt(&token, &key, None, same).unwrap();
+    assert_eq!(payload, "bound to a user");
+
+    let other_id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c9);
+    let mut buffer = uuid::Uuid::encode_buffer();
+    let other = ImplicitAssertion::from_uuid(&other_id, &mut buffer);
+    assert!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, other).is_err());
+  }
+}
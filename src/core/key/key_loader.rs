@@ -0,0 +1,111 @@
+use super::{Key, PasetoSymmetricKey};
+use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError, VersionTrait};
+use zeroize::Zeroizing;
+
+/// Loads keys from the environment, e.g. at the startup of a twelve-factor app
+///
+/// Symmetric keys may be stored as a `k<version>.local.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/local.md),
+/// as 64 hex characters or as 43 characters of unpadded url-safe base64. The encoding is detected
+/// from the value, and surrounding whitespace such as a trailing newline is ignored.
+///
+/// # Example usage
+/// ```
+/// # #[cfg(feature = "v4_local")]
+/// # {
+/// # use rusty_paseto::core::*;
+/// # std::env::set_var("PASETO_KEY", "k4.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8");
+/// let key = KeyLoader::symmetric_from_env::<V4, Local>("PASETO_KEY")?;
+/// # assert_eq!(key.to_paserk(), "k4.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8");
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct KeyLoader;
+
+impl KeyLoader {
+  /// Reads a symmetric key from the environment variable `name`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::KeyLoad] if the variable isn't set or doesn't hold a 32 byte key in one
+  /// of the supported encodings, including a PASERK of another version or type. The error names the
+  /// variable but never includes its value.
+  pub fn symmetric_from_env<Version, Purpose>(name: &str) -> Result<PasetoSymmetricKey<Version, Purpose>, PasetoError>
+  where
+    Version: VersionTrait,
+    PasetoSymmetricKey<Version, Purpose>: From<Key<32>>,
+  {
+    let load_error = |reason: String| PasetoError::KeyLoad {
+      variable: name.to_string(),
+      reason,
+    };
+    let value = Zeroizing::new(std::env::var(name).map_err(|error| load_error(error.to_string()))?);
+    let value = value.trim();
+
+    let bytes = if value.starts_with('k') && value.contains('.') {
+      let prefix = format!("k{}.local.", Version::name().trim_start_matches('v'));
+      let encoded = value
+        .strip_prefix(prefix.as_str())
+        .ok_or_else(|| load_error(format!("the PASERK is not a {} key", prefix)))?;
+      DefaultBase64Codec::decode(encoded).map_err(|_| load_error("the PASERK is not valid base64".to_string()))?
+    } else if value.len() % 2 == 0 && value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+      hex::decode(value).map_err(|_| load_error("the value is not valid hex".to_string()))?
+    } else {
+      DefaultBase64Codec::decode(value)
+        .map_err(|_| load_error("the value is not a PASERK, hex or url-safe base64 key".to_string()))?
+    };
+    let bytes = Zeroizing::new(bytes);
+
+    let key = <[u8; 32]>::try_from(bytes.as_slice())
+      .map_err(|_| load_error(format!("the key must be 32 bytes but was {} bytes", bytes.len())))?;
+    Ok(PasetoSymmetricKey::from(Key::<32>::from(key)))
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+  use crate::core::{Local, V4};
+
+  const KEY: [u8; 32] = *b"wubbalubbadubdubwubbalubbadubdub";
+
+  fn load(name: &str, value: &str) -> Result<PasetoSymmetricKey<V4, Local>, PasetoError> {
+    //every test uses its own variable as tests run in parallel
+    std::env::set_var(name, value);
+    KeyLoader::symmetric_from_env::<V4, Local>(name)
+  }
+
+  #[test]
+  fn test_symmetric_from_env_encodings() {
+    let paserk = PasetoSymmetricKey::<V4, Local>::from(Key::from(KEY)).to_paserk();
+    let key = load("RUSTY_PASETO_TEST_KEY_PASERK", &paserk).unwrap();
+    assert_eq!(key.as_ref(), KEY);
+
+    let key = load("RUSTY_PASETO_TEST_KEY_HEX", &hex::encode(KEY)).unwrap();
+    assert_eq!(key.as_ref(), KEY);
+
+    let key = load("RUSTY_PASETO_TEST_KEY_BASE64", &DefaultBase64Codec::encode(&KEY)).unwrap();
+    assert_eq!(key.as_ref(), KEY);
+
+    //a trailing newline, e.g. from a mounted secret, is ignored
+    let key = load("RUSTY_PASETO_TEST_KEY_NEWLINE", &format!("{}\n", hex::encode(KEY))).unwrap();
+    assert_eq!(key.as_ref(), KEY);
+  }
+
+  #[test]
+  fn test_symmetric_from_env_malformed() {
+    let error = load("RUSTY_PASETO_TEST_KEY_MALFORMED", "not a key!").unwrap_err();
+    assert!(matches!(error, PasetoError::KeyLoad { ref variable, .. } if variable == "RUSTY_PASETO_TEST_KEY_MALFORMED"));
+    assert!(!error.to_string().contains("not a key!"));
+
+    let error = load("RUSTY_PASETO_TEST_KEY_SHORT", &hex::encode(&KEY[..16])).unwrap_err();
+    assert!(error.to_string().contains("must be 32 bytes but was 16 bytes"));
+
+    let paserk = PasetoSymmetricKey::<V4, Local>::from(Key::from(KEY)).to_paserk().replace("k4.", "k3.");
+    let error = load("RUSTY_PASETO_TEST_KEY_WRONG_VERSION", &paserk).unwrap_err();
+    assert!(error.to_string().contains("not a k4.local. key"));
+
+    let error = KeyLoader::symmetric_from_env::<V4, Local>("RUSTY_PASETO_TEST_KEY_UNSET").unwrap_err();
+    assert!(matches!(error, PasetoError::KeyLoad { .. }));
+  }
+}
@@ -6,9 +6,15 @@ use std::ops::Deref;
 use zeroize::Zeroize;
 
 /// A wrapper for a slice of bytes that constitute a key of a specific size
+///
+/// With the `insecure_key_ordering` feature, keys also implement `PartialEq`, `Eq`, `PartialOrd`,
+/// `Ord` and `Hash` so they can be used as map keys, e.g. when caching derived subkeys. These
+/// comparisons are **not** constant-time and must never be used to check a key against a secret;
+/// they are for indexing only.
 #[derive(Zeroize)]
 #[zeroize(drop)]
 #[derive(Clone)]
+#[cfg_attr(feature = "insecure_key_ordering", derive(PartialEq, Eq, PartialOrd, Ord, Hash))]
 pub struct Key<const KEYSIZE: usize>([u8; KEYSIZE]);
 
 impl<const KEYSIZE: usize> Default for Key<KEYSIZE> {
@@ -72,6 +78,38 @@ impl<const KEYSIZE: usize> Key<KEYSIZE> {
 
 impl<const KEYSIZE: usize> Debug for Key<KEYSIZE> {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "!!! KEY IS PRIVATE !!!")
+    write!(f, "Key<{}>(REDACTED)", KEYSIZE)
+  }
+}
+
+/// Formats a wrapper type's name along with its version and purpose, e.g. `PasetoSymmetricKey<V4, Local>`
+pub(super) fn type_label<Version, Purpose>(wrapper: &str) -> String {
+  let short = |name: &'static str| name.rsplit("::").next().unwrap_or(name);
+  format!(
+    "{}<{}, {}>",
+    wrapper,
+    short(std::any::type_name::<Version>()),
+    short(std::any::type_name::<Purpose>())
+  )
+}
+
+#[cfg(all(test, feature = "insecure_key_ordering"))]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn test_key_as_map_key() {
+    let first = Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub");
+    let second = Key::<32>::from([7u8; 32]);
+
+    let mut subkeys = HashMap::new();
+    subkeys.insert(first.clone(), "first subkey");
+    subkeys.insert(second.clone(), "second subkey");
+
+    assert_eq!(subkeys.get(&Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub")), Some(&"first subkey"));
+    assert_eq!(subkeys.get(&second), Some(&"second subkey"));
+    assert!(!subkeys.contains_key(&Key::<32>::default()));
+    assert!(second < first);
   }
 }
@@ -1,3 +1,4 @@
+mod key_loader;
 mod keys;
 mod paseto_asymmetric_private_key;
 mod paseto_asymmetric_public_key;
@@ -5,6 +6,7 @@ mod paseto_nonce;
 mod paseto_symmetric_key;
 mod paseto_nonce_impl;
 
+pub use key_loader::KeyLoader;
 pub use keys::Key;
 pub use paseto_asymmetric_private_key::PasetoAsymmetricPrivateKey;
 pub use paseto_asymmetric_public_key::PasetoAsymmetricPublicKey;
@@ -31,6 +31,16 @@ impl<'a, Version, Purpose> AsRef<[u8]> for PasetoAsymmetricPrivateKey<'a, Versio
   }
 }
 
+impl<'a, Version, Purpose> std::fmt::Debug for PasetoAsymmetricPrivateKey<'a, Version, Purpose> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}(REDACTED)",
+      super::keys::type_label::<Version, Purpose>("PasetoAsymmetricPrivateKey")
+    )
+  }
+}
+
 #[cfg(feature = "v1_public")]
 impl<'a> From<&'a [u8]> for PasetoAsymmetricPrivateKey<'a, V1, Public> {
   fn from(key: &'a [u8]) -> Self {
@@ -67,3 +77,96 @@ impl<'a> From<&'a Key<48>> for PasetoAsymmetricPrivateKey<'a, V3, Public> {
 }
 
 
+
+#[cfg(feature = "v4_public")]
+impl PasetoAsymmetricPrivateKey<'_, V4, Public> {
+  const SECRET_HEADER: &'static str = "k4.secret.";
+
+  /// Encodes this key as a `k4.secret.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/secret.md).
+  ///
+  /// The PASERK carries the full 64 byte Ed25519 keypair (seed followed by the public key), which is
+  /// the same layout this key is created from.
+  pub fn to_paserk(&self) -> String {
+    format!("{}{}", Self::SECRET_HEADER, DefaultBase64Codec::encode(self.key))
+  }
+
+  /// Signs an arbitrary message with this key's Ed25519 keypair, returning the raw 64 byte signature.
+  ///
+  /// **This is not PASETO.** The signature covers `msg` alone, with none of the header, footer or
+  /// implicit assertion binding a token signature has. Prefix messages with a context string of your
+  /// own, e.g. `b"myapp-webhook-v1:"`, so signatures for one purpose can't be replayed for another,
+  /// and prefer a separate key where possible.
+  ///
+  /// Because a token signature is a raw signature over a pre-authentication encoding starting with
+  /// the `v4.public.` header, messages shaped like one are refused rather than letting this key mint
+  /// tokens.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::Signature] if `msg` is shaped like a `v4.public.` token's signing input,
+  /// or a key error if this key isn't a valid 64 byte Ed25519 keypair.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+  /// let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+  /// let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+  /// let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+  ///
+  /// let signature = private_key.sign_detached(b"myapp-webhook-v1:order 42 shipped")?;
+  /// assert!(public_key.verify_detached(b"myapp-webhook-v1:order 42 shipped", &signature));
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn sign_detached(&self, msg: &[u8]) -> Result<[u8; 64], PasetoError> {
+    use ed25519_dalek::Signer;
+
+    if is_v4_public_signing_input(msg) {
+      return Err(PasetoError::Signature);
+    }
+    let signing_key = ed25519_dalek::SigningKey::from_keypair_bytes(<&[u8; 64]>::try_from(self.key)?)?;
+    Ok(signing_key.sign(msg).to_bytes())
+  }
+
+  /// Decodes a `k4.secret.` PASERK into the [Key] a private key borrows from.
+  ///
+  /// Private keys borrow their bytes, so the decoded [Key] is returned for the caller to own.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::InvalidPaserk] if the string isn't a `k4.secret.` PASERK or its public
+  /// half doesn't belong to its seed.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let secret = "k4.secret.tMv7Q99M4hByfZU-SnEzB_oZu32fhQQUONnhG5QqN3Qeudu7vAR8A_1wYE4AcfCYfhayi3VyJcEfAEFdDiCxog";
+  /// let key = PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk(secret)?;
+  /// let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&key);
+  /// assert_eq!(private_key.to_paserk(), secret);
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn try_key_from_paserk(paserk: &str) -> Result<Key<64>, PasetoError> {
+    let invalid = || PasetoError::InvalidPaserk {
+      expected: Self::SECRET_HEADER,
+    };
+    let encoded = paserk.strip_prefix(Self::SECRET_HEADER).ok_or_else(invalid)?;
+    let key = DefaultBase64Codec::decode(encoded).map_err(|_| invalid())?;
+    let key = <[u8; 64]>::try_from(key.as_slice()).map_err(|_| invalid())?;
+    //rejects a keypair whose public half wasn't derived from its seed
+    ed25519_dalek::SigningKey::from_keypair_bytes(&key).map_err(|_| invalid())?;
+    Ok(Key::<64>::from(key))
+  }
+}
+
+//whether a message starts like the pre-authentication encoding a v4.public token signs, i.e. a
+//little endian count of four pieces followed by the length prefixed `v4.public.` header
+#[cfg(feature = "v4_public")]
+fn is_v4_public_signing_input(msg: &[u8]) -> bool {
+  const HEADER: &[u8] = b"v4.public.";
+  let mut prefix = Vec::with_capacity(16 + HEADER.len());
+  prefix.extend_from_slice(&4u64.to_le_bytes());
+  prefix.extend_from_slice(&(HEADER.len() as u64).to_le_bytes());
+  prefix.extend_from_slice(HEADER);
+  msg.starts_with(&prefix)
+}
@@ -1,5 +1,6 @@
 use super::Key;
 use crate::core::*;
+use std::borrow::Cow;
 use std::convert::{AsRef, From};
 use std::marker::PhantomData;
 /// A wrapper for the public half of an asymmetric key pair
@@ -8,12 +9,26 @@ use std::marker::PhantomData;
 pub struct PasetoAsymmetricPublicKey<'a, Version, Purpose> {
   version: PhantomData<Version>,
   purpose: PhantomData<Purpose>,
-  key: &'a [u8],
+  key: Cow<'a, [u8]>,
 }
 
 impl<'a, Version, Purpose> AsRef<[u8]> for PasetoAsymmetricPublicKey<'a, Version, Purpose> {
   fn as_ref(&self) -> &[u8] {
-    self.key
+    &self.key
+  }
+}
+
+/// Public keys aren't secret, but printing the whole key is noisy, so only the first
+/// eight bytes are shown as a fingerprint
+impl<'a, Version, Purpose> std::fmt::Debug for PasetoAsymmetricPublicKey<'a, Version, Purpose> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let fingerprint = &self.key[..self.key.len().min(8)];
+    write!(
+      f,
+      "{}({}..)",
+      super::keys::type_label::<Version, Purpose>("PasetoAsymmetricPublicKey"),
+      hex::encode(fingerprint)
+    )
   }
 }
 
@@ -23,11 +38,73 @@ impl<'a> From<&'a [u8]> for PasetoAsymmetricPublicKey<'a, V1, Public> {
     Self {
       version: PhantomData,
       purpose: PhantomData,
-      key,
+      key: Cow::Borrowed(key),
     }
   }
 }
 
+#[cfg(feature = "v1_public")]
+impl PasetoAsymmetricPublicKey<'_, V1, Public> {
+  /// Creates a V1 public key from the big-endian modulus and public exponent of an RSA key,
+  /// assembling the DER encoded `RSAPublicKey` which V1 verification expects.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::InvalidKey] if either component is empty or entirely zero.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// # let modulus = [0xc9u8; 256];
+  /// let public_key = PasetoAsymmetricPublicKey::<V1, Public>::from_rsa_components(&modulus, &[0x01, 0x00, 0x01])?;
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn from_rsa_components(n: &[u8], e: &[u8]) -> Result<Self, PasetoError> {
+    let n = der_integer(n)?;
+    let e = der_integer(e)?;
+
+    //RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    let mut key = vec![0x30];
+    der_length(n.len() + e.len(), &mut key);
+    key.extend_from_slice(&n);
+    key.extend_from_slice(&e);
+
+    Ok(Self {
+      version: PhantomData,
+      purpose: PhantomData,
+      key: Cow::Owned(key),
+    })
+  }
+}
+
+#[cfg(feature = "v1_public")]
+fn der_integer(value: &[u8]) -> Result<Vec<u8>, PasetoError> {
+  //DER integers are minimal, so strip leading zeros and re-add one if the sign bit is set
+  let start = value.iter().position(|b| *b != 0).ok_or(PasetoError::InvalidKey)?;
+  let value = &value[start..];
+  let padded = value[0] & 0x80 != 0;
+
+  let mut integer = vec![0x02];
+  der_length(value.len() + usize::from(padded), &mut integer);
+  if padded {
+    integer.push(0);
+  }
+  integer.extend_from_slice(value);
+  Ok(integer)
+}
+
+#[cfg(feature = "v1_public")]
+fn der_length(len: usize, out: &mut Vec<u8>) {
+  if len < 0x80 {
+    out.push(len as u8);
+  } else {
+    let bytes = len.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len() - 1);
+    out.push(0x80 | (bytes.len() - start) as u8);
+    out.extend_from_slice(&bytes[start..]);
+  }
+}
+
 #[cfg(feature = "v3_public")]
 impl<'a> TryFrom<&'a Key<49>> for PasetoAsymmetricPublicKey<'a, V3, Public> {
   type Error = PasetoError;
@@ -39,7 +116,7 @@ impl<'a> TryFrom<&'a Key<49>> for PasetoAsymmetricPublicKey<'a, V3, Public> {
     Ok(Self {
       version: PhantomData,
       purpose: PhantomData,
-      key: key.as_ref(),
+      key: Cow::Borrowed(key.as_ref()),
     })
   }
 }
@@ -52,7 +129,166 @@ where
     Self {
       version: PhantomData,
       purpose: PhantomData,
-      key: key.as_ref(),
+      key: Cow::Borrowed(key.as_ref()),
+    }
+  }
+}
+
+#[cfg(feature = "v4_public")]
+impl<'a> TryFrom<&'a [u8]> for PasetoAsymmetricPublicKey<'a, V4, Public> {
+  type Error = PasetoError;
+
+  /// Constructs a key from a slice loaded at runtime, e.g. from configuration, checking it is the
+  /// 32 bytes of an Ed25519 public key.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let bytes = hex::decode("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+  /// let public_key = PasetoAsymmetricPublicKey::<V4, Public>::try_from(bytes.as_slice())?;
+  /// assert!(PasetoAsymmetricPublicKey::<V4, Public>::try_from(&bytes[1..]).is_err());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  fn try_from(key: &'a [u8]) -> Result<Self, Self::Error> {
+    if key.len() != 32 {
+      return Err(PasetoError::InvalidPublicKeyLength {
+        expected: 32,
+        actual: key.len(),
+      });
+    }
+    Ok(Self {
+      version: PhantomData,
+      purpose: PhantomData,
+      key: Cow::Borrowed(key),
+    })
+  }
+}
+
+#[cfg(feature = "v4_public")]
+impl PasetoAsymmetricPublicKey<'_, V4, Public> {
+  /// Verifies a raw Ed25519 signature over an arbitrary message, as created by
+  /// [sign_detached](PasetoAsymmetricPrivateKey::sign_detached).
+  ///
+  /// **This is not PASETO** and offers none of a token's header, footer or implicit assertion
+  /// binding, so check any context prefix the message should carry. Returns `false` for a malformed
+  /// key or signature as well as for a signature which doesn't verify.
+  pub fn verify_detached(&self, msg: &[u8], signature: &[u8]) -> bool {
+    let verify = || -> Result<(), PasetoError> {
+      let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(<&[u8; 32]>::try_from(self.key.as_ref())?)?;
+      let signature = ed25519_dalek::Signature::from_slice(signature)?;
+      Ok(verifying_key.verify_strict(msg, &signature)?)
+    };
+    verify().is_ok()
+  }
+
+  /// Encodes this key as a `k4.public.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/public.md),
+  /// the inverse of its `TryFrom<&str>` implementation.
+  pub fn to_paserk(&self) -> String {
+    format!("k4.public.{}", DefaultBase64Codec::encode(&self.key))
+  }
+}
+
+#[cfg(feature = "v4_public")]
+impl TryFrom<&str> for PasetoAsymmetricPublicKey<'static, V4, Public> {
+  type Error = PasetoError;
+
+  /// Constructs a key from a `k4.public.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/public.md)
+  /// string, rejecting any other PASERK type or version.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let key = PasetoAsymmetricPublicKey::<V4, Public>::try_from("k4.public.Hrnbu7wEfAP9cGBOAHHwmH4Wsot1ciXBHwBBXQ4gsaI")?;
+  /// assert_eq!(key.to_paserk(), "k4.public.Hrnbu7wEfAP9cGBOAHHwmH4Wsot1ciXBHwBBXQ4gsaI");
+  /// assert!(PasetoAsymmetricPublicKey::<V4, Public>::try_from("k2.public.Hrnbu7wEfAP9cGBOAHHwmH4Wsot1ciXBHwBBXQ4gsaI").is_err());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  fn try_from(paserk: &str) -> Result<Self, Self::Error> {
+    const PREFIX: &str = "k4.public.";
+    let encoded = paserk
+      .strip_prefix(PREFIX)
+      .ok_or(PasetoError::InvalidPaserk { expected: PREFIX })?;
+    let key = DefaultBase64Codec::decode(encoded).map_err(|_| PasetoError::InvalidPaserk { expected: PREFIX })?;
+    if key.len() != 32 {
+      return Err(PasetoError::InvalidPaserk { expected: PREFIX });
     }
+    Ok(Self {
+      version: PhantomData,
+      purpose: PhantomData,
+      key: Cow::Owned(key),
+    })
+  }
+}
+
+#[cfg(all(test, feature = "v4_public"))]
+mod unit_tests {
+  use super::*;
+
+  #[test]
+  fn test_try_from_slice() {
+    let bytes = [7u8; 32];
+    let key = PasetoAsymmetricPublicKey::<V4, Public>::try_from(&bytes[..]).unwrap();
+    assert_eq!(key.as_ref(), &bytes);
+
+    let error = PasetoAsymmetricPublicKey::<V4, Public>::try_from(&bytes[..31]).unwrap_err();
+    assert!(matches!(
+      error,
+      PasetoError::InvalidPublicKeyLength {
+        expected: 32,
+        actual: 31
+      }
+    ));
+    assert_eq!(error.to_string(), "The public key must be 32 bytes but was 31 bytes");
+  }
+
+  fn keypair() -> (Key<64>, Key<32>) {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    (
+      Key::<64>::from(signing_key.to_keypair_bytes()),
+      Key::<32>::from(signing_key.verifying_key().to_bytes()),
+    )
+  }
+
+  #[test]
+  fn test_detached_signature_round_trip() {
+    let (private_key, public_key) = keypair();
+    let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+    let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+
+    let msg = b"myapp-webhook-v1:order 42 shipped";
+    let signature = private_key.sign_detached(msg).unwrap();
+    assert!(public_key.verify_detached(msg, &signature));
+
+    //the signature is plain Ed25519
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&<[u8; 32]>::try_from(public_key.as_ref()).unwrap()).unwrap();
+    assert!(verifying_key
+      .verify_strict(msg, &ed25519_dalek::Signature::from_bytes(&signature))
+      .is_ok());
+  }
+
+  #[test]
+  fn test_detached_signature_detects_tampering() {
+    let (private_key, public_key) = keypair();
+    let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+    let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+    let signature = private_key.sign_detached(b"myapp-webhook-v1:order 42 shipped").unwrap();
+
+    assert!(!public_key.verify_detached(b"myapp-webhook-v1:order 43 shipped", &signature));
+    let mut tampered = signature;
+    tampered[0] ^= 1;
+    assert!(!public_key.verify_detached(b"myapp-webhook-v1:order 42 shipped", &tampered));
+    assert!(!public_key.verify_detached(b"myapp-webhook-v1:order 42 shipped", &signature[..63]));
+
+    let other = Key::<32>::from(ed25519_dalek::SigningKey::from_bytes(&[8; 32]).verifying_key().to_bytes());
+    let other = PasetoAsymmetricPublicKey::<V4, Public>::from(&other);
+    assert!(!other.verify_detached(b"myapp-webhook-v1:order 42 shipped", &signature));
+  }
+
+  #[test]
+  fn test_detached_signature_refuses_token_signing_input() {
+    let (private_key, _) = keypair();
+    let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+    let signing_input = Paseto::<V4, Public>::signing_input(b"{\"sub\":\"forged\"}", b"", b"");
+    assert!(matches!(private_key.sign_detached(&signing_input), Err(PasetoError::Signature)));
   }
 }
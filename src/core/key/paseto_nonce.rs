@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::convert::AsRef;
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -6,7 +7,8 @@ use std::ops::Deref;
 ///
 /// Key sizes for nonces are either 32 or 24 bytes in size
 ///
-/// Nonces can be specified directly for testing or randomly in production
+/// Nonces can be specified directly for testing or randomly in production. A fixed nonce, e.g.
+/// from a test vector, can also be built straight from a byte array of the version's nonce size.
 /// # Example usage
 /// ```
 /// # #[cfg(feature = "v4_local")]
@@ -35,19 +37,25 @@ use std::ops::Deref;
 pub struct PasetoNonce<'a, Version, Purpose> {
   pub(crate) version: PhantomData<Version>,
   pub(crate) purpose: PhantomData<Purpose>,
-  pub(crate) key: &'a [u8],
+  pub(crate) key: Cow<'a, [u8]>,
 }
 
 impl<'a, Version, Purpose> Deref for PasetoNonce<'a, Version, Purpose> {
   type Target = [u8];
   fn deref(&self) -> &Self::Target {
-    self.key
+    &self.key
   }
 }
 
 impl<'a, Version, Purpose> AsRef<[u8]> for PasetoNonce<'a, Version, Purpose> {
   fn as_ref(&self) -> &[u8] {
-    self.key
+    &self.key
+  }
+}
+
+impl<'a, Version, Purpose> std::fmt::Debug for PasetoNonce<'a, Version, Purpose> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}(REDACTED)", super::keys::type_label::<Version, Purpose>("PasetoNonce"))
   }
 }
 
@@ -1,4 +1,5 @@
 #![cfg(feature = "v2_local")]
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use crate::core::{Key, Local, PasetoNonce, V2};
 
@@ -7,7 +8,7 @@ impl<'a> From<&'a Key<24>> for PasetoNonce<'a, V2, Local> {
         Self {
             version: PhantomData,
             purpose: PhantomData,
-            key: key.as_ref(),
+            key: Cow::Borrowed(key.as_ref()),
         }
     }
 }
@@ -17,7 +18,27 @@ impl<'a> From<&'a Key<32>> for PasetoNonce<'a, V2, Local> {
         Self {
             version: PhantomData,
             purpose: PhantomData,
-            key: key.as_ref(),
+            key: Cow::Borrowed(key.as_ref()),
+        }
+    }
+}
+
+impl From<[u8; 24]> for PasetoNonce<'_, V2, Local> {
+    fn from(key: [u8; 24]) -> Self {
+        Self {
+            version: PhantomData,
+            purpose: PhantomData,
+            key: Cow::Owned(key.to_vec()),
+        }
+    }
+}
+
+impl From<[u8; 32]> for PasetoNonce<'_, V2, Local> {
+    fn from(key: [u8; 32]) -> Self {
+        Self {
+            version: PhantomData,
+            purpose: PhantomData,
+            key: Cow::Owned(key.to_vec()),
         }
     }
 }
@@ -38,4 +59,10 @@ mod builders {
         assert_eq!(paseto_key.as_ref().len(), key.as_ref().len());
         Ok(())
     }
+
+    #[test]
+    fn v2_local_nonce_from_array_test() {
+        let nonce = PasetoNonce::<V2, Local>::from([7u8; 24]);
+        assert_eq!(nonce.as_ref(), &[7u8; 24]);
+    }
 }
@@ -1,4 +1,5 @@
 #![cfg(feature = "v2_public")]
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use crate::core::{PasetoNonce, Public, V2};
 
@@ -11,7 +12,7 @@ impl<'a, T> From<&'a T> for PasetoNonce<'a, V2, Public>
         Self {
             version: PhantomData,
             purpose: PhantomData,
-            key: key.into(),
+            key: Cow::Borrowed(key.into()),
         }
     }
 }
@@ -1,4 +1,5 @@
 #![cfg(feature = "v3_local")]
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use crate::core::{Key, Local, PasetoNonce, V3};
 
@@ -7,7 +8,17 @@ impl<'a> From<&'a Key<32>> for PasetoNonce<'a, V3, Local> {
         Self {
             version: PhantomData,
             purpose: PhantomData,
-            key: key.as_ref(),
+            key: Cow::Borrowed(key.as_ref()),
+        }
+    }
+}
+
+impl From<[u8; 32]> for PasetoNonce<'_, V3, Local> {
+    fn from(key: [u8; 32]) -> Self {
+        Self {
+            version: PhantomData,
+            purpose: PhantomData,
+            key: Cow::Owned(key.to_vec()),
         }
     }
 }
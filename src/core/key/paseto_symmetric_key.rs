@@ -22,8 +22,476 @@ impl<Version> From<Key<32>> for PasetoSymmetricKey<Version, Local> {
   }
 }
 
+impl<Version> PasetoSymmetricKey<Version, Local> {
+  /// Constructs a key from any 32 bytes, e.g. a `[u8; 32]` or `Vec<u8>` handed over by an external
+  /// key store, without building a [Key] first.
+  ///
+  /// This isn't a `TryFrom` implementation as it would overlap with `From<Key<32>>`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::InvalidKey](crate::core::PasetoError::InvalidKey) if `bytes` isn't
+  /// exactly 32 bytes long.
+  ///
+  /// # Example usage
+  /// ```
+  /// # #[cfg(feature = "v4_local")]
+  /// # {
+  /// # use rusty_paseto::core::*;
+  /// let key = PasetoSymmetricKey::<V4, Local>::try_from_bytes(*b"wubbalubbadubdubwubbalubbadubdub")?;
+  /// assert_eq!(key.as_ref(), b"wubbalubbadubdubwubbalubbadubdub");
+  /// assert!(PasetoSymmetricKey::<V4, Local>::try_from_bytes(b"too short").is_err());
+  /// # }
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn try_from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, crate::core::PasetoError> {
+    let key = <[u8; 32]>::try_from(bytes.as_ref()).map_err(|_| crate::core::PasetoError::InvalidKey)?;
+    Ok(Self::from(Key::<32>::from(key)))
+  }
+}
+
+impl<Version, Purpose> std::fmt::Debug for PasetoSymmetricKey<Version, Purpose> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}(REDACTED)", super::keys::type_label::<Version, Purpose>("PasetoSymmetricKey"))
+  }
+}
+
+impl<Version, Purpose> PasetoSymmetricKey<Version, Purpose> {
+  /// Compares two keys in constant time, e.g. to confirm a rotation target differs from the
+  /// current key without leaking how many leading bytes they share.
+  pub fn ct_eq(&self, other: &Self) -> bool {
+    use subtle::ConstantTimeEq;
+    self.key.as_ref().ct_eq(other.key.as_ref()).into()
+  }
+}
+
+#[cfg(feature = "v4_local")]
+impl PasetoSymmetricKey<crate::core::V4, Local> {
+  /// Deterministically derives a 32 byte key from a larger seed by hashing it with BLAKE2b,
+  /// the same hash V4 uses internally to split encryption and authentication keys.
+  ///
+  /// This is a convenience for systems which store a seed rather than a key. It is not a
+  /// PASERK operation and keys derived this way aren't interoperable with other libraries.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let seed = [7u8; 64];
+  /// let key = PasetoSymmetricKey::<V4, Local>::from_seed(&seed);
+  /// assert_eq!(key.as_ref(), PasetoSymmetricKey::<V4, Local>::from_seed(&seed).as_ref());
+  /// ```
+  pub fn from_seed(seed: &[u8]) -> Self {
+    use blake2::digest::consts::U32;
+    use blake2::{Blake2b, Digest};
+
+    let mut context = Blake2b::<U32>::new();
+    context.update(b"rusty_paseto-v4.local-key-from-seed");
+    context.update(seed);
+    Self::from(Key::<32>::from(<[u8; 32]>::from(context.finalize())))
+  }
+}
+
+#[cfg(feature = "v4_local")]
+impl TryFrom<&str> for PasetoSymmetricKey<crate::core::V4, Local> {
+  type Error = crate::core::PasetoError;
+
+  /// Constructs a key from a `k4.local.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/local.md)
+  /// string, rejecting any other PASERK type or version.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let key = PasetoSymmetricKey::<V4, Local>::try_from("k4.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8")?;
+  /// assert!(PasetoSymmetricKey::<V4, Local>::try_from("k3.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8").is_err());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  fn try_from(paserk: &str) -> Result<Self, Self::Error> {
+    use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError};
+
+    const PREFIX: &str = "k4.local.";
+    let encoded = paserk
+      .strip_prefix(PREFIX)
+      .ok_or(PasetoError::InvalidPaserk { expected: PREFIX })?;
+    let key = DefaultBase64Codec::decode(encoded).map_err(|_| PasetoError::InvalidPaserk { expected: PREFIX })?;
+    let key = <[u8; 32]>::try_from(key.as_slice()).map_err(|_| PasetoError::InvalidPaserk { expected: PREFIX })?;
+    Ok(Self::from(Key::<32>::from(key)))
+  }
+}
+
+#[cfg(feature = "v4_local")]
+impl PasetoSymmetricKey<crate::core::V4, Local> {
+  const PIE_HEADER: &'static str = "k4.local-wrap.pie.";
+
+  /// Encodes this key as a `k4.local.` [PASERK](https://github.com/paseto-standard/paserk/blob/master/types/local.md),
+  /// the inverse of its `TryFrom<&str>` implementation.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+  /// let paserk = key.to_paserk();
+  /// assert!(paserk.starts_with("k4.local."));
+  /// assert_eq!(PasetoSymmetricKey::<V4, Local>::try_from(paserk.as_str())?.as_ref(), key.as_ref());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn to_paserk(&self) -> String {
+    use crate::core::{Base64Codec, DefaultBase64Codec};
+    format!("k4.local.{}", DefaultBase64Codec::encode(self.key.as_ref()))
+  }
+
+  /// Wraps this key with `wrapping_key` as a `k4.local-wrap.pie.`
+  /// [PASERK](https://github.com/paseto-standard/paserk/blob/master/operations/Wrap/pie.md), e.g. to
+  /// carry an encrypted data key in a token's footer.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let master = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+  /// let data_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+  /// let wrapped = data_key.try_wrap_pie(&master)?;
+  /// let unwrapped = PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(&wrapped, &master)?;
+  /// assert_eq!(unwrapped.as_ref(), data_key.as_ref());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn try_wrap_pie(&self, wrapping_key: &Self) -> Result<String, crate::core::PasetoError> {
+    Ok(self.wrap_pie(wrapping_key, &Key::<32>::try_new_random()?))
+  }
+
+  //wraps the key with a given nonce, which is only fixed by known answer tests
+  fn wrap_pie(&self, wrapping_key: &Self, nonce: &Key<32>) -> String {
+    use crate::core::{Base64Codec, DefaultBase64Codec};
+    use chacha20::cipher::StreamCipher;
+
+    let mut wrapped = self.key.to_vec();
+    Self::pie_cipher(wrapping_key, nonce).apply_keystream(&mut wrapped);
+    let tag = Self::pie_tag(wrapping_key, nonce, &wrapped);
+
+    let mut paserk = Vec::with_capacity(32 + 32 + wrapped.len());
+    paserk.extend_from_slice(&tag);
+    paserk.extend_from_slice(nonce.as_ref());
+    paserk.extend_from_slice(&wrapped);
+    format!("{}{}", Self::PIE_HEADER, DefaultBase64Codec::encode(&paserk))
+  }
+
+  /// Unwraps a key from a `k4.local-wrap.pie.` PASERK created with the same `wrapping_key`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::InvalidPaserk](crate::core::PasetoError::InvalidPaserk) if the PASERK is
+  /// malformed and [PasetoError::InvalidSignature](crate::core::PasetoError::InvalidSignature) if it
+  /// wasn't wrapped with `wrapping_key` or has been tampered with.
+  pub fn try_unwrap_pie(paserk: &str, wrapping_key: &Self) -> Result<Self, crate::core::PasetoError> {
+    use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError};
+    use chacha20::cipher::StreamCipher;
+    use subtle::ConstantTimeEq;
+
+    let invalid = || PasetoError::InvalidPaserk {
+      expected: Self::PIE_HEADER,
+    };
+    let encoded = paserk.strip_prefix(Self::PIE_HEADER).ok_or_else(invalid)?;
+    let decoded = DefaultBase64Codec::decode(encoded).map_err(|_| invalid())?;
+    if decoded.len() != 32 + 32 + 32 {
+      return Err(invalid());
+    }
+    let (tag, rest) = decoded.split_at(32);
+    let (nonce, wrapped) = rest.split_at(32);
+    let nonce = Key::<32>::from(nonce);
+
+    //authenticate before decrypting
+    if !bool::from(tag.ct_eq(&Self::pie_tag(wrapping_key, &nonce, wrapped))) {
+      return Err(PasetoError::InvalidSignature);
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(wrapped);
+    Self::pie_cipher(wrapping_key, &nonce).apply_keystream(&mut key);
+    Ok(Self::from(Key::<32>::from(key)))
+  }
+
+  //Ek and n2 are derived from BLAKE2b-448(0x80 || n) keyed with the wrapping key
+  fn pie_cipher(wrapping_key: &Self, nonce: &Key<32>) -> chacha20::XChaCha20 {
+    use blake2::digest::consts::U56;
+    use blake2::digest::{FixedOutput, KeyInit, Update};
+    use chacha20::cipher::KeyIvInit;
+
+    let mut context = blake2::Blake2bMac::<U56>::new_from_slice(wrapping_key.as_ref()).unwrap();
+    context.update(&[0x80]);
+    context.update(nonce.as_ref());
+    let x = context.finalize_fixed();
+    chacha20::XChaCha20::new(chacha20::Key::from_slice(&x[..32]), chacha20::XNonce::from_slice(&x[32..]))
+  }
+
+  //t = BLAKE2b-256(h || n || c) keyed with Ak = BLAKE2b-256(0x81 || n) keyed with the wrapping key
+  fn pie_tag(wrapping_key: &Self, nonce: &Key<32>, wrapped: &[u8]) -> [u8; 32] {
+    use blake2::digest::consts::U32;
+    use blake2::digest::{FixedOutput, KeyInit, Update};
+
+    let mut context = blake2::Blake2bMac::<U32>::new_from_slice(wrapping_key.as_ref()).unwrap();
+    context.update(&[0x81]);
+    context.update(nonce.as_ref());
+    let authentication_key = context.finalize_fixed();
+
+    let mut context = blake2::Blake2bMac::<U32>::new_from_slice(&authentication_key).unwrap();
+    context.update(Self::PIE_HEADER.as_bytes());
+    context.update(nonce.as_ref());
+    context.update(wrapped);
+    context.finalize_fixed().into()
+  }
+}
+
+#[cfg(feature = "pbkw")]
+impl PasetoSymmetricKey<crate::core::V4, Local> {
+  const PW_HEADER: &'static str = "k4.local-pw.";
+
+  /// Wraps this key with a password as a `k4.local-pw.`
+  /// [PASERK](https://github.com/paseto-standard/paserk/blob/master/operations/PBKW.md), deriving the
+  /// wrapping key from the password with Argon2id.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::WeakKdfParams](crate::core::PasetoError::WeakKdfParams) if `params` are
+  /// below the minimums checked by [Argon2Params::validate](crate::core::Argon2Params::validate) and
+  /// `allow_weak` isn't set, or if Argon2 can't be run with them at all.
+  ///
+  /// # Example usage
+  /// ```
+  /// # use rusty_paseto::core::*;
+  /// let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+  /// let params = Argon2Params::new(Argon2Params::MIN_MEMORY_KIB, 2, 1);
+  /// let wrapped = key.local_pw_wrap(b"correct horse battery staple", &params)?;
+  /// let unwrapped = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(&wrapped, b"correct horse battery staple")?;
+  /// assert!(unwrapped.ct_eq(&key));
+  ///
+  /// assert!(key.local_pw_wrap(b"correct horse battery staple", &Argon2Params::new(1024, 1, 1)).is_err());
+  /// # Ok::<(),anyhow::Error>(())
+  /// ```
+  pub fn local_pw_wrap(
+    &self,
+    password: &[u8],
+    params: &crate::core::Argon2Params,
+  ) -> Result<String, crate::core::PasetoError> {
+    use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError};
+    use chacha20::cipher::StreamCipher;
+
+    if !params.allow_weak {
+      params.validate()?;
+    }
+    let salt = Key::<16>::try_new_random()?;
+    let nonce = Key::<24>::try_new_random()?;
+    let pre_key = Self::pw_pre_key(password, salt.as_ref(), params).ok_or(PasetoError::WeakKdfParams)?;
+
+    let mut wrapped = self.key.to_vec();
+    Self::pw_cipher(&pre_key, nonce.as_ref()).apply_keystream(&mut wrapped);
+
+    let mut paserk = Vec::with_capacity(16 + 16 + 24 + 32 + 32);
+    paserk.extend_from_slice(salt.as_ref());
+    paserk.extend_from_slice(&(u64::from(params.memory_kib) * 1024).to_be_bytes());
+    paserk.extend_from_slice(&params.iterations.to_be_bytes());
+    paserk.extend_from_slice(&params.parallelism.to_be_bytes());
+    paserk.extend_from_slice(nonce.as_ref());
+    paserk.extend_from_slice(&wrapped);
+    let tag = Self::pw_tag(&pre_key, &paserk);
+    paserk.extend_from_slice(&tag);
+    Ok(format!("{}{}", Self::PW_HEADER, DefaultBase64Codec::encode(&paserk)))
+  }
+
+  /// Unwraps a key from a `k4.local-pw.` PASERK created with the same password.
+  ///
+  /// The Argon2 parameters are read from the PASERK and aren't checked against any minimum, so a
+  /// PASERK from an untrusted source can make unwrapping arbitrarily expensive.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::InvalidPaserk](crate::core::PasetoError::InvalidPaserk) if the PASERK is
+  /// malformed and [PasetoError::InvalidSignature](crate::core::PasetoError::InvalidSignature) if the
+  /// password is wrong or the PASERK has been tampered with.
+  pub fn local_pw_unwrap(paserk: &str, password: &[u8]) -> Result<Self, crate::core::PasetoError> {
+    use crate::core::{Argon2Params, Base64Codec, DefaultBase64Codec, PasetoError};
+    use chacha20::cipher::StreamCipher;
+    use subtle::ConstantTimeEq;
+
+    let invalid = || PasetoError::InvalidPaserk {
+      expected: Self::PW_HEADER,
+    };
+    let encoded = paserk.strip_prefix(Self::PW_HEADER).ok_or_else(invalid)?;
+    let decoded = DefaultBase64Codec::decode(encoded).map_err(|_| invalid())?;
+    if decoded.len() != 16 + 16 + 24 + 32 + 32 {
+      return Err(invalid());
+    }
+    let (authenticated, tag) = decoded.split_at(decoded.len() - 32);
+    let (salt, rest) = authenticated.split_at(16);
+    let (memory, rest) = rest.split_at(8);
+    let (iterations, rest) = rest.split_at(4);
+    let (parallelism, rest) = rest.split_at(4);
+    let (nonce, wrapped) = rest.split_at(24);
+
+    //the memory limit is encoded in bytes but Argon2 takes it in KiB
+    let memory = u64::from_be_bytes(memory.try_into().map_err(|_| invalid())?);
+    let params = Argon2Params {
+      allow_weak: true,
+      ..Argon2Params::new(
+        u32::try_from(memory / 1024).map_err(|_| invalid())?,
+        u32::from_be_bytes(iterations.try_into().map_err(|_| invalid())?),
+        u32::from_be_bytes(parallelism.try_into().map_err(|_| invalid())?),
+      )
+    };
+    let pre_key = Self::pw_pre_key(password, salt, &params).ok_or_else(invalid)?;
+
+    //authenticate before decrypting
+    if !bool::from(tag.ct_eq(&Self::pw_tag(&pre_key, authenticated))) {
+      return Err(PasetoError::InvalidSignature);
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(wrapped);
+    Self::pw_cipher(&pre_key, nonce).apply_keystream(&mut key);
+    Ok(Self::from(Key::<32>::from(key)))
+  }
+
+  //k = Argon2id(password, salt) with the given costs, or None if Argon2 rejects them
+  fn pw_pre_key(
+    password: &[u8],
+    salt: &[u8],
+    params: &crate::core::Argon2Params,
+  ) -> Option<zeroize::Zeroizing<[u8; 32]>> {
+    let params = argon2::Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32)).ok()?;
+    let mut pre_key = zeroize::Zeroizing::new([0u8; 32]);
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+      .hash_password_into(password, salt, pre_key.as_mut())
+      .ok()?;
+    Some(pre_key)
+  }
+
+  //Ek = BLAKE2b-256(0xFF || k)
+  fn pw_cipher(pre_key: &[u8; 32], nonce: &[u8]) -> chacha20::XChaCha20 {
+    use blake2::digest::consts::U32;
+    use blake2::{Blake2b, Digest};
+    use chacha20::cipher::KeyIvInit;
+
+    let encryption_key = Blake2b::<U32>::new().chain_update([0xff]).chain_update(pre_key).finalize();
+    chacha20::XChaCha20::new(&encryption_key, chacha20::XNonce::from_slice(nonce))
+  }
+
+  //t = BLAKE2b-256(h || s || mem || ops || para || n || edk) keyed with Ak = BLAKE2b-256(0xFE || k)
+  fn pw_tag(pre_key: &[u8; 32], authenticated: &[u8]) -> [u8; 32] {
+    use blake2::digest::consts::U32;
+    use blake2::digest::{FixedOutput, KeyInit, Update};
+    use blake2::{Blake2b, Digest};
+
+    let authentication_key = Blake2b::<U32>::new().chain_update([0xfe]).chain_update(pre_key).finalize();
+    let mut context = blake2::Blake2bMac::<U32>::new_from_slice(&authentication_key).unwrap();
+    Update::update(&mut context, Self::PW_HEADER.as_bytes());
+    Update::update(&mut context, authenticated);
+    context.finalize_fixed().into()
+  }
+}
+
 impl<Version, Purpose> AsRef<[u8]> for PasetoSymmetricKey<Version, Purpose> {
   fn as_ref(&self) -> &[u8] {
     self.key.as_ref()
   }
 }
+
+#[cfg(all(test, feature = "v4"))]
+mod unit_tests {
+  use super::*;
+  use crate::core::V4;
+
+  #[test]
+  fn test_ct_eq() {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let same = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let mut last_byte_differs = *b"wubbalubbadubdubwubbalubbadubdub";
+    last_byte_differs[31] ^= 1;
+    let other = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(last_byte_differs));
+
+    assert!(key.ct_eq(&key));
+    assert!(key.ct_eq(&same));
+    assert!(!key.ct_eq(&other));
+    assert!(!other.ct_eq(&key));
+  }
+
+  #[test]
+  fn test_try_from_bytes() {
+    let expected = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+    let bytes: Vec<u8> = b"wubbalubbadubdubwubbalubbadubdub".to_vec();
+    let from_vec = PasetoSymmetricKey::<V4, Local>::try_from_bytes(bytes).unwrap();
+    assert!(from_vec.ct_eq(&expected));
+    let from_array = PasetoSymmetricKey::<V4, Local>::try_from_bytes(*b"wubbalubbadubdubwubbalubbadubdub").unwrap();
+    assert!(from_array.ct_eq(&expected));
+
+    let too_short = &b"wubbalubbadubdubwubbalubbadubdub"[..31];
+    assert!(matches!(
+      PasetoSymmetricKey::<V4, Local>::try_from_bytes(too_short),
+      Err(crate::core::PasetoError::InvalidKey)
+    ));
+    assert!(PasetoSymmetricKey::<V4, Local>::try_from_bytes([0u8; 33]).is_err());
+  }
+
+  #[cfg(feature = "v4_local")]
+  #[test]
+  fn test_pie_known_answer() {
+    use crate::core::PasetoError;
+
+    //computed independently from the PASERK pie specification, as BLAKE2b and XChaCha20 over
+    //the wrapping key 0x70..0x8f, the key 0x00..0x1f and the nonce 0x40..0x5f
+    let paserk = "k4.local-wrap.pie.Zrzbcp4YVDRzatXilqWJizh-B-BhU30GYSlkNuhxRYxAQUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVpbXF1eX3dfALdzRD-_IzYjMQtc34F8F8HW9hfzCwoy4rUruGOS";
+    let wrapping_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(core::array::from_fn(|i| 0x70 + i as u8)));
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(core::array::from_fn(|i| i as u8)));
+    let nonce = Key::<32>::from(core::array::from_fn(|i| 0x40 + i as u8));
+
+    assert_eq!(key.wrap_pie(&wrapping_key, &nonce), paserk);
+    let unwrapped = PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(paserk, &wrapping_key).unwrap();
+    assert!(unwrapped.ct_eq(&key));
+
+    let wrong_wrapping_key = PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(paserk, &key);
+    assert!(matches!(wrong_wrapping_key, Err(PasetoError::InvalidSignature)));
+  }
+
+  #[cfg(feature = "pbkw")]
+  #[test]
+  fn test_local_pw_unwrap_test_vectors() {
+    use crate::core::PasetoError;
+
+    //k4.local-pw-1 from the PASERK test vectors, whose password is the hex string itself
+    const PASSWORD: &[u8] = b"636f727265637420686f727365206261747465727920737461706c65";
+    let paserk = "k4.local-pw.9VvzoqE_i23NOqsP9xoijQAAAAAEAAAAAAAAAgAAAAG_uxDZC-NsYyOW8OUOqISJqgHN8xIfAXiPfmFTfB4GPidUzm4aKzMGJmZtRPeyZCV11MxEJS3VMIRHXxYsfUQsmWLALpFwqUhxZdk_ymFcK2Nk0-N7CVp-";
+    let key = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(paserk, PASSWORD).unwrap();
+    assert_eq!(
+      hex::encode(key.as_ref()),
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f"
+    );
+
+    //the wrong password of k4.local-pw-fail-1 and a tampered tag in the style of k4.local-pw-fail-2
+    let wrong_password = b"636f727265637420686f727365206261747465727920737461706c66";
+    let wrong_password = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(paserk, wrong_password);
+    assert!(matches!(wrong_password, Err(PasetoError::InvalidSignature)));
+    let tampered = paserk.replace("p-", "p_");
+    let tampered = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(&tampered, PASSWORD);
+    assert!(matches!(tampered, Err(PasetoError::InvalidSignature)));
+
+    //k4.local-pw-fail-3
+    let wrong_version = "k3.local-pw.BZtl8KfhFR8CCZp6hB0V2yMWttTMpK_U8HiKxnuvMI0AACcQIm4KcJGvG1kfptqCbQxzQUOp72AzgtmhCLVP1mn3orDRJIpoDzRj82dc1cMnANbUsEdcYVG8xzSuCt99zfCjQnQ2rIKbKRM66gafzcSWmD9iMoY3W6KUaN56t0P-ODV2";
+    let wrong_version = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(wrong_version, PASSWORD);
+    assert!(matches!(wrong_version, Err(PasetoError::InvalidPaserk { .. })));
+  }
+
+  #[cfg(feature = "pbkw")]
+  #[test]
+  fn test_local_pw_wrap_rejects_weak_params() {
+    use crate::core::{Argon2Params, PasetoError};
+
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let weak = Argon2Params::new(1024, 1, 1);
+    assert!(matches!(key.local_pw_wrap(b"password", &weak), Err(PasetoError::WeakKdfParams)));
+
+    let weak = Argon2Params { allow_weak: true, ..weak };
+    let wrapped = key.local_pw_wrap(b"password", &weak).unwrap();
+    assert!(wrapped.starts_with("k4.local-pw."));
+    let unwrapped = PasetoSymmetricKey::<V4, Local>::local_pw_unwrap(&wrapped, b"password").unwrap();
+    assert!(unwrapped.ct_eq(&key));
+
+    //parameters Argon2 can't run with at all are rejected even when weak ones are allowed
+    let invalid = Argon2Params { allow_weak: true, ..Argon2Params::new(1024, 1, 0) };
+    assert!(matches!(key.local_pw_wrap(b"password", &invalid), Err(PasetoError::WeakKdfParams)));
+  }
+}
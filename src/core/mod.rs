@@ -44,27 +44,41 @@
 //! # Ok::<(),anyhow::Error>(())
 //! ```
 
+#[cfg(feature = "pbkw")]
+mod argon2_params;
+mod base64_codec;
 mod error;
 mod footer;
 mod header;
 mod implicit_assertion;
 mod key;
+#[cfg(feature = "nonce_tracking")]
+mod nonce_tracker;
 mod paseto;
+mod paseto_token;
 mod payload;
 mod purpose;
 mod traits;
 mod version;
+mod wrapped_token;
 mod common;
 mod paseto_impl;
 
-pub use error::PasetoError;
+#[cfg(feature = "pbkw")]
+pub use argon2_params::Argon2Params;
+pub use base64_codec::{Base64Codec, DefaultBase64Codec};
+pub use error::{ErrorKind, PasetoError};
 pub use footer::Footer;
 pub(crate) use header::Header;
 pub use implicit_assertion::ImplicitAssertion;
-pub use key::{Key, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoNonce, PasetoSymmetricKey};
+pub use key::{Key, KeyLoader, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoNonce, PasetoSymmetricKey};
+#[cfg(feature = "nonce_tracking")]
+pub use nonce_tracker::NonceTracker;
 pub use paseto::Paseto;
+pub use paseto_token::PasetoToken;
 pub use payload::Payload;
 pub use purpose::{Local, Public};
 pub(crate) use traits::{Base64Encodable, V1orV3, V2orV4};
 pub use traits::{ImplicitAssertionCapable, PurposeTrait, VersionTrait};
 pub use version::*;
+pub use wrapped_token::WrappedToken;
@@ -0,0 +1,52 @@
+use super::{PasetoError, PasetoNonce};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Records every nonce it is shown and rejects any nonce it has seen before.
+///
+/// Random nonces make a collision vanishingly unlikely, but audits of batch encryption sometimes
+/// need it ruled out. Only available with the `nonce_tracking` feature, so builds without it pay
+/// nothing. Each recorded nonce is kept in memory for the lifetime of the tracker.
+///
+/// # Usage
+/// ```
+/// # use rusty_paseto::core::*;
+/// let tracker = NonceTracker::new();
+/// let nonce = Key::<32>::try_new_random()?;
+/// let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+///
+/// tracker.record(&nonce)?;
+/// assert!(matches!(tracker.record(&nonce), Err(PasetoError::NonceReuse)));
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct NonceTracker {
+  seen: Mutex<HashSet<Vec<u8>>>,
+}
+
+impl NonceTracker {
+  /// Creates a tracker which hasn't seen any nonces
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Records a nonce, returning [PasetoError::NonceReuse] if it was recorded before
+  pub fn record<Version, Purpose>(&self, nonce: &PasetoNonce<Version, Purpose>) -> Result<(), PasetoError> {
+    //a poisoned lock still holds every nonce recorded before the panic
+    let mut seen = self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match seen.insert(nonce.as_ref().to_vec()) {
+      true => Ok(()),
+      false => Err(PasetoError::NonceReuse),
+    }
+  }
+
+  /// The number of distinct nonces recorded
+  pub fn len(&self) -> usize {
+    self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).len()
+  }
+
+  /// Whether no nonces have been recorded
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
@@ -1,7 +1,14 @@
 use std::{
+    marker::PhantomData,
     str,
 };
-use crate::core::{Base64Encodable, Footer, Header, ImplicitAssertion, ImplicitAssertionCapable, PasetoError, Payload, PurposeTrait, VersionTrait};
+#[cfg(any(feature = "v3", feature = "v4"))]
+use std::borrow::Cow;
+#[cfg(feature = "v4_local")]
+use std::io::Write;
+#[cfg(any(feature = "v3", feature = "v4"))]
+use crate::core::common::PreAuthenticationEncoding;
+use crate::core::{Base64Codec, Base64Encodable, DefaultBase64Codec, Footer, Header, ImplicitAssertion, ImplicitAssertionCapable, PasetoError, Payload, PurposeTrait, VersionTrait};
 
 
 /// Used to build and encrypt / decrypt core PASETO tokens
@@ -10,6 +17,9 @@ use crate::core::{Base64Encodable, Footer, Header, ImplicitAssertion, ImplicitAs
 /// Given a [Payload], optional [Footer] and optional [ImplicitAssertion] ([V3] or [V4] only)
 /// returns an encrypted token when [Local] is specified as the purpose or a signed token when
 /// [Public] is specified
+///
+/// The base64 segments of the token are encoded and decoded with [DefaultBase64Codec] unless
+/// another [Base64Codec] is named as the last type parameter
 /// # Example usage
 /// ```
 /// # #[cfg(feature = "v4_local")]
@@ -42,7 +52,7 @@ use crate::core::{Base64Encodable, Footer, Header, ImplicitAssertion, ImplicitAs
 /// # Ok::<(),anyhow::Error>(())
 /// ```
 #[derive(Default, Copy, Clone)]
-pub struct Paseto<'a, Version, Purpose>
+pub struct Paseto<'a, Version, Purpose, Codec = DefaultBase64Codec>
     where
         Version: VersionTrait,
         Purpose: PurposeTrait,
@@ -51,9 +61,11 @@ pub struct Paseto<'a, Version, Purpose>
     pub(crate) payload: Payload<'a>,
     pub(crate) footer: Option<Footer<'a>>,
     pub(crate) implicit_assertion: Option<ImplicitAssertion<'a>>,
+    pub(crate) additional_data: Option<&'a [u8]>,
+    pub(crate) codec: PhantomData<Codec>,
 }
 
-impl<'a, Version: VersionTrait, Purpose: PurposeTrait> Paseto<'a, Version, Purpose> {
+impl<'a, Version: VersionTrait, Purpose: PurposeTrait, Codec: Base64Codec> Paseto<'a, Version, Purpose, Codec> {
     /// Returns a builder for creating a PASETO token
     ///
     /// # Example usage
@@ -87,8 +99,15 @@ impl<'a, Version: VersionTrait, Purpose: PurposeTrait> Paseto<'a, Version, Purpo
     /// }
     /// # Ok::<(),anyhow::Error>(())
     /// ```
-    pub fn builder() -> Paseto<'a, Version, Purpose> {
-        Self { ..Default::default() }
+    pub fn builder() -> Paseto<'a, Version, Purpose, Codec> {
+        Self {
+            header: Header::default(),
+            payload: Payload::default(),
+            footer: None,
+            implicit_assertion: None,
+            additional_data: None,
+            codec: PhantomData,
+        }
     }
 
     /// Sets the payload for the token
@@ -133,56 +152,170 @@ impl<'a, Version: VersionTrait, Purpose: PurposeTrait> Paseto<'a, Version, Purpo
         self
     }
 
+    /// Checks that an untrusted token string is structurally valid for this version and purpose
+    /// without a key and without any cryptography.
+    ///
+    /// The header must match, the payload and any footer must be valid base64 and the payload must
+    /// be long enough to hold a nonce and tag ([Local]) or a signature ([Public]). A structurally valid
+    /// token may still fail to decrypt or verify.
+    ///
+    /// # Example usage
+    /// ```
+    /// # #[cfg(feature = "v4_local")]
+    /// # {
+    /// # use rusty_paseto::core::*;
+    /// let token = "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XJ5hOb_4v9RmDkneN0S92dx0OW4pgy7omxgf3S8c3LlQg";
+    /// assert!(Paseto::<V4, Local>::validate_structure(token).is_ok());
+    /// assert!(Paseto::<V4, Local>::validate_structure("v4.local.").is_err());
+    /// # }
+    /// ```
+    pub fn validate_structure(token: &str) -> Result<(), PasetoError> {
+        Self::verify_header(token, &Version::default(), &Purpose::default())?;
+        let potential_parts = Self::split_token(token)?;
+        Self::decode_payload(potential_parts[2])?;
+        if let Some(footer) = potential_parts.get(3) {
+            Codec::decode(footer)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the decoded length in bytes of an untrusted token's payload segment, without a key.
+    ///
+    /// The length includes the nonce and tag ([Local]) or the signature ([Public]), so oversized
+    /// tokens can be rejected before spending any time on decryption or verification.
+    ///
+    /// # Example usage
+    /// ```
+    /// # #[cfg(feature = "v4_local")]
+    /// # {
+    /// # use rusty_paseto::core::*;
+    /// let token = "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XJ5hOb_4v9RmDkneN0S92dx0OW4pgy7omxgf3S8c3LlQg";
+    /// // a 32 byte nonce, 69 bytes of ciphertext and a 32 byte tag
+    /// assert_eq!(Paseto::<V4, Local>::peek_payload_len(token)?, 133);
+    /// # }
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn peek_payload_len(token: &str) -> Result<usize, PasetoError> {
+        Self::verify_header(token, &Version::default(), &Purpose::default())?;
+        let potential_parts = Self::split_token(token)?;
+        Ok(Self::decode_payload(potential_parts[2])?.len())
+    }
+
     /* BEGIN PRIVATE FUNCTIONS */
     pub(crate) fn format_token(&self, encrypted_payload: &str) -> String {
-        let footer = self.footer.map(|f| f.encode());
+        let footer = self.footer.map(|f| f.encode::<Codec>());
         match footer {
             Some(f) => format!("{}{}.{}", self.header, encrypted_payload, f),
             None => format!("{}{}", self.header, encrypted_payload),
         }
     }
 
+    #[cfg(feature = "v4_local")]
+    pub(crate) fn write_token<W: Write>(&self, w: &mut W, encrypted_payload: &str) -> Result<(), PasetoError> {
+        write!(w, "{}", self.header)?;
+        w.write_all(encrypted_payload.as_bytes())?;
+        if let Some(footer) = self.footer {
+            w.write_all(b".")?;
+            w.write_all(footer.encode::<Codec>().as_bytes())?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn parse_raw_token(
         raw_token: &'a str,
         footer: (impl Into<Option<Footer<'a>>> + Copy),
         v: &Version,
         p: &Purpose,
     ) -> Result<Vec<u8>, PasetoError> {
+        //check the header first so a token of the wrong version or purpose is never processed any further
+        Self::verify_header(raw_token, v, p)?;
+        let potential_parts = Self::split_token(raw_token)?;
+        if potential_parts.len() == 4 {
+            //verify expected footer
+            let footer = footer.into().unwrap_or_default();
+            let found_footer = Footer::from(potential_parts[3]);
+            if !footer.constant_time_equals::<Codec, _>(found_footer) {
+                return Err(PasetoError::FooterInvalid);
+            }
+        }
+
+        Self::decode_payload(potential_parts[2])
+    }
+
+    fn split_token(raw_token: &str) -> Result<Vec<&str>, PasetoError> {
+        if raw_token.is_empty() {
+            return Err(PasetoError::MalformedToken { reason: "the token is empty" });
+        }
         //split the raw token into parts
         let potential_parts = raw_token.split('.').collect::<Vec<_>>();
         //inspect the parts
         match potential_parts.len() {
-            length if !(3..=4).contains(&length) => {
-                return Err(PasetoError::IncorrectSize);
-            }
-            4 => {
-                //verify expected footer
-                let footer = footer.into().unwrap_or_default();
-                let found_footer = Footer::from(potential_parts[3]);
-                if !footer.constant_time_equals(found_footer) {
-                    return Err(PasetoError::FooterInvalid);
-                }
-            }
-            _ => {}
+            1 | 2 => Err(PasetoError::MalformedToken {
+                reason: "the token has a header but no payload",
+            }),
+            length if length > 4 && potential_parts[length - 1].is_empty() => Err(PasetoError::MalformedToken {
+                reason: "the token ends with a trailing '.' after its footer",
+            }),
+            length if length > 4 => Err(PasetoError::IncorrectSize),
+            _ => Ok(potential_parts),
         }
+    }
 
-        //grab the header
-        let potential_header = format!("{}.{}.", potential_parts[0], potential_parts[1]);
-        //we should be able to verify the header using the passed in Version and Purpose
-        let expected_header = format!("{}.{}.", v, p);
+    fn verify_header(raw_token: &str, v: &Version, p: &Purpose) -> Result<(), PasetoError> {
+        //the expected header is checked as a prefix of the raw token, so a token of another
+        //version or purpose is rejected before it's split or decoded
+        if raw_token.starts_with(Header::<Version, Purpose>::default().as_ref()) {
+            return Ok(());
+        }
+        let mut segments = raw_token.splitn(3, '.');
+        let (version, purpose) = match (segments.next(), segments.next(), segments.next()) {
+            (Some(version), Some(purpose), Some(_)) => (version, purpose),
+            //too few segments to hold a header and payload, which is reported when splitting
+            _ => return Ok(()),
+        };
 
-        //verify the header
-        if potential_header.ne(&expected_header) {
+        if version != v.as_ref() {
+            let is_version = version.len() > 1
+                && version.starts_with('v')
+                && version[1..].bytes().all(|b| b.is_ascii_digit());
+            if is_version {
+                return Err(PasetoError::VersionMismatch { expected: Version::name() });
+            }
             return Err(PasetoError::WrongHeader);
-        };
+        }
+
+        //a token of the right version but the wrong purpose is a downgrade attempt, e.g. a local
+        //token presented to a public parser, so it's reported explicitly
+        if purpose != p.as_ref() {
+            return Err(PasetoError::PurposeMismatch { expected: Purpose::name() });
+        }
+        Err(PasetoError::WrongHeader)
+    }
+
+    pub(crate) fn decode_payload(potential_payload: &str) -> Result<Vec<u8>, PasetoError> {
+        if potential_payload.is_empty() {
+            return Err(PasetoError::MalformedToken { reason: "the token payload is empty" });
+        }
 
-        let encrypted_payload = Payload::from(potential_parts[2]);
-        Ok(encrypted_payload.decode()?)
+        let encrypted_payload = Payload::from(potential_payload);
+        let decoded_payload = encrypted_payload.decode::<Codec>()?;
+
+        //make sure the payload is long enough to hold a nonce and tag or a signature
+        let minimum_len = match Purpose::name() {
+            "local" => Version::LOCAL_NONCE_LEN + Version::LOCAL_TAG_LEN,
+            _ => Version::PUBLIC_SIG_LEN,
+        };
+        if decoded_payload.len() < minimum_len {
+            return Err(PasetoError::MalformedToken {
+                reason: "the token payload is too short",
+            });
+        }
+        Ok(decoded_payload)
     }
     /* END PRIVATE FUNCTIONS */
 }
 
-impl<'a, Version, Purpose> Paseto<'a, Version, Purpose>
+impl<'a, Version, Purpose, Codec> Paseto<'a, Version, Purpose, Codec>
     where
         Purpose: PurposeTrait,
         Version: ImplicitAssertionCapable,
@@ -224,6 +357,57 @@ impl<'a, Version, Purpose> Paseto<'a, Version, Purpose>
         self.implicit_assertion = Some(implicit_assertion);
         self
     }
+
+    /// Binds additional context bytes, such as a TLS channel binding, into the token without placing
+    /// them in the footer or the [ImplicitAssertion]
+    ///
+    /// The bytes are folded into the implicit assertion position of the pre-authentication encoding
+    /// as `PAE(implicit_assertion, additional_data)`, so the same bytes must be supplied when
+    /// decrypting or verifying. Tokens using this aren't interoperable with other PASETO libraries.
+    ///
+    /// *NOTE:* Only for [V3] or [V4] tokens, [V1] and [V2] have no implicit assertion to fold into
+    ///
+    /// # Example usage
+    /// ```
+    /// # #[cfg(feature = "v4_local")]
+    /// # {
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// let channel_binding = [0xc4u8; 32];
+    /// let token = Paseto::<V4, Local>::builder()
+    ///   .set_payload(Payload::from("claims"))
+    ///   .set_additional_data(&channel_binding)
+    ///   .try_encrypt(&key, &nonce)?;
+    ///
+    /// let payload = Paseto::<V4, Local>::try_decrypt_with_additional_data(&token, &key, None, None, &channel_binding)?;
+    /// # assert_eq!(payload, "claims");
+    /// # }
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn set_additional_data(&mut self, additional_data: &'a [u8]) -> &mut Self {
+        self.additional_data = Some(additional_data);
+        self
+    }
+
+    /// The bytes placed in the implicit assertion position of the pre-authentication encoding
+    #[cfg(any(feature = "v3", feature = "v4"))]
+    pub(crate) fn implicit_assertion_bytes(&self) -> Cow<'a, [u8]> {
+        fold_additional_data(self.implicit_assertion.unwrap_or_default().as_bytes(), self.additional_data)
+    }
+}
+
+/// Folds any additional data into the implicit assertion, leaving the implicit assertion untouched
+/// when there is none so tokens without additional data still follow the spec
+#[cfg(any(feature = "v3", feature = "v4"))]
+pub(crate) fn fold_additional_data<'b>(implicit_assertion: &'b [u8], additional_data: Option<&[u8]>) -> Cow<'b, [u8]> {
+    match additional_data {
+        Some(additional_data) => {
+            Cow::Owned(PreAuthenticationEncoding::parse(&[implicit_assertion, additional_data]).to_vec())
+        }
+        None => Cow::Borrowed(implicit_assertion),
+    }
 }
 
 
@@ -1,12 +1,11 @@
 #![cfg(feature = "v1_local")]
-use std::str;
 use hmac::{Hmac, Mac};
-use crate::core::{Footer, Header, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V1};
+use crate::core::{Base64Codec, Footer, Header, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V1};
 use crate::core::common::{AuthenticationKey, AuthenticationKeySeparator, CipherText, EncryptionKey, EncryptionKeySeparator, PreAuthenticationEncoding, RawPayload, Tag};
-use ring::constant_time::verify_slices_are_equal as ConstantTimeEquals;
+use subtle::ConstantTimeEq;
 use sha2::Sha384;
 
-impl<'a> Paseto<'a, V1, Local> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V1, Local, Codec> {
     /// Attempts to decrypt a PASETO token
     /// ```
     /// # use serde_json::json;
@@ -28,6 +27,32 @@ impl<'a> Paseto<'a, V1, Local> {
         key: &PasetoSymmetricKey<V1, Local>,
         footer: (impl Into<Option<Footer<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
+        let plaintext = Self::try_decrypt_raw(token, key, footer)?;
+
+        //take ownership of the decrypted buffer rather than copying it
+        Ok(String::from_utf8(plaintext).map_err(|err| err.utf8_error())?)
+    }
+
+    /// Attempts to decrypt a PASETO token, returning the plaintext bytes exactly as they were
+    /// encrypted.
+    ///
+    /// No assumptions are made about the contents of the payload so it needn't be valid UTF-8
+    /// or JSON, which is useful when migrating or debugging tokens from other producers.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V1, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+    /// # let nonce = PasetoNonce::<V1, Local>::from(&nonce);
+    /// # let token = Paseto::<V1, Local>::builder().set_payload(Payload::from("not json")).try_encrypt(&key, &nonce)?;
+    /// let plaintext = Paseto::<V1, Local>::try_decrypt_raw(&token, &key, None)?;
+    /// # assert_eq!(plaintext, b"not json");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_raw(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V1, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+    ) -> Result<Vec<u8>, PasetoError> {
         let decoded_payload = Self::parse_raw_token(token, footer, &V1::default(), &Local::default())?;
         let nonce = Key::from(&decoded_payload[..32]);
         let nonce = PasetoNonce::<V1, Local>::from(&nonce);
@@ -50,16 +75,16 @@ impl<'a> Paseto<'a, V1, Local> {
         //generate tags
         let tag = &decoded_payload[(nonce.len() + ciphertext.len())..];
         let tag2 = &Tag::<V1, Local>::from(authentication_key, &pae);
-        //compare tags
-        ConstantTimeEquals(tag, tag2)?;
+        //compare tags in constant time
+        if !bool::from(tag.ct_eq(tag2)) {
+            return Err(PasetoError::InvalidSignature);
+        }
 
         //decrypt payload
         let ciphertext = CipherText::<V1, Local>::from(ciphertext, &encryption_key);
 
-        let decoded_str = str::from_utf8(&ciphertext)?;
-
         //return decrypted payload
-        Ok(decoded_str.to_owned())
+        Ok(ciphertext.ciphertext)
     }
 
     /// Attempts to encrypt a PASETO token
@@ -111,7 +136,7 @@ impl<'a> Paseto<'a, V1, Local> {
         let tag = Tag::<V1, Local>::from(authentication_key, &pae);
 
         //      //generate appended and base64 encoded payload
-        let raw_payload = RawPayload::<V1, Local>::from(&nonce, &ciphertext, &tag)?;
+        let raw_payload = RawPayload::<V1, Local>::from::<Codec>(&nonce, &ciphertext, &tag)?;
 
         //format as paseto with header and optional footer
         Ok(self.format_token(&raw_payload))
@@ -1,10 +1,10 @@
 #![cfg(feature = "v1_public")]
 use ring::rand::SystemRandom;
 use ring::signature::{RSA_PSS_SHA384, RsaKeyPair};
-use crate::core::{Footer, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V1};
+use crate::core::{Base64Codec, Footer, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V1};
 use crate::core::common::{CipherText, PreAuthenticationEncoding, RawPayload};
 
-impl<'a> Paseto<'a, V1, Public> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V1, Public, Codec> {
     /// Verifies a signed V1 Public Paseto
     pub fn try_verify(
         signature: &'a str,
@@ -37,7 +37,7 @@ impl<'a> Paseto<'a, V1, Public> {
             .sign(&RSA_PSS_SHA384, &random, &pae, &mut signature)
             .map_err(|_| PasetoError::InvalidSignature)?;
 
-        let raw_payload = RawPayload::<V1, Public>::from(&self.payload, &signature);
+        let raw_payload = RawPayload::<V1, Public>::from::<Codec>(&self.payload, &signature);
 
         Ok(self.format_token(&raw_payload))
     }
@@ -2,10 +2,9 @@
 use blake2::Blake2bMac;
 use blake2::digest::{FixedOutput, Mac};
 use chacha20poly1305::XNonce;
-use crate::core::{Footer, Header, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V2};
+use crate::core::{Base64Codec, Footer, Header, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V2};
 use crate::core::common::{CipherText, PreAuthenticationEncoding, RawPayload};
-use std::str;
-impl<'a> Paseto<'a, V2, Local> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V2, Local, Codec> {
     /// Attempts to decrypt a PASETO token
     /// ```
     /// # use serde_json::json;
@@ -27,6 +26,32 @@ impl<'a> Paseto<'a, V2, Local> {
         key: &PasetoSymmetricKey<V2, Local>,
         footer: (impl Into<Option<Footer<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
+        let plaintext = Self::try_decrypt_raw(token, key, footer)?;
+
+        //take ownership of the decrypted buffer rather than copying it
+        Ok(String::from_utf8(plaintext).map_err(|err| err.utf8_error())?)
+    }
+
+    /// Attempts to decrypt a PASETO token, returning the plaintext bytes exactly as they were
+    /// encrypted.
+    ///
+    /// No assumptions are made about the contents of the payload so it needn't be valid UTF-8
+    /// or JSON, which is useful when migrating or debugging tokens from other producers.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+    /// # let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+    /// # let token = Paseto::<V2, Local>::builder().set_payload(Payload::from("not json")).try_encrypt(&key, &nonce)?;
+    /// let plaintext = Paseto::<V2, Local>::try_decrypt_raw(&token, &key, None)?;
+    /// # assert_eq!(plaintext, b"not json");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_raw(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V2, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+    ) -> Result<Vec<u8>, PasetoError> {
         //get footer
 
         let decoded_payload = Self::parse_raw_token(token, footer, &V2::default(), &Local::default())?;
@@ -45,11 +70,8 @@ impl<'a> Paseto<'a, V2, Local> {
         //encrypt payload
         let ciphertext = CipherText::<V2, Local>::try_decrypt_from(key, nonce, ciphertext, pae)?;
 
-        //generate appended and base64 encoded payload
-        let decoded_str = str::from_utf8(&ciphertext)?;
-
         //return decrypted payload
-        Ok(decoded_str.to_owned())
+        Ok(ciphertext.ciphertext)
     }
 
     /// Attempts to encrypt a PASETO token
@@ -92,7 +114,7 @@ impl<'a> Paseto<'a, V2, Local> {
         let ciphertext = CipherText::<V2, Local>::try_from(key, nonce, &self.payload, &pae)?;
 
         //generate appended and base64 encoded payload
-        let raw_payload = RawPayload::<V2, Local>::from(&context, &ciphertext);
+        let raw_payload = RawPayload::<V2, Local>::from::<Codec>(&context, &ciphertext);
 
         //format as paseto with header and optional footer
         Ok(self.format_token(&raw_payload))
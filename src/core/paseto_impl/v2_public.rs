@@ -1,9 +1,9 @@
 #![cfg(feature = "v2_public")]
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
-use crate::core::{Footer, Header, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V2};
+use crate::core::{Base64Codec, Footer, Header, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V2};
 use crate::core::common::{PreAuthenticationEncoding, RawPayload};
 
-impl<'a> Paseto<'a, V2, Public> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V2, Public, Codec> {
     /// Attempts to verify a signed V2 Public Paseto
     /// Fails with a PasetoError if the token is malformed or the token cannot be verified with the
     /// passed public key
@@ -47,7 +47,7 @@ impl<'a> Paseto<'a, V2, Public> {
 
         // let signature = keypair.sign(&pae);
         let signature = signing_key.sign(&pae);
-        let raw_payload = RawPayload::<V2, Public>::from(&self.payload, &signature.to_bytes());
+        let raw_payload = RawPayload::<V2, Public>::from::<Codec>(&self.payload, &signature.to_bytes());
 
         Ok(self.format_token(&raw_payload))
     }
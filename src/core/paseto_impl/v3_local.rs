@@ -1,13 +1,12 @@
 #![cfg(feature = "v3_local")]
 
-use std::str;
+use subtle::ConstantTimeEq;
 
-use ring::constant_time::verify_slices_are_equal as ConstantTimeEquals;
-
-use crate::core::{Footer, Header, ImplicitAssertion, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V3};
+use crate::core::{Base64Codec, Footer, Header, ImplicitAssertion, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V3};
+use crate::core::paseto::fold_additional_data;
 use crate::core::common::{AuthenticationKey, AuthenticationKeySeparator, CipherText, EncryptionKey, EncryptionKeySeparator, PreAuthenticationEncoding, RawPayload, Tag};
 
-impl<'a> Paseto<'a, V3, Local> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V3, Local, Codec> {
     /// Attempts to decrypt a PASETO token
     /// ```
     /// # use serde_json::json;
@@ -30,9 +29,72 @@ impl<'a> Paseto<'a, V3, Local> {
         footer: (impl Into<Option<Footer<'a>>> + Copy),
         implicit_assertion: (impl Into<Option<ImplicitAssertion<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
+        let plaintext = Self::try_decrypt_raw(token, key, footer, implicit_assertion)?;
+
+        //take ownership of the decrypted buffer rather than copying it
+        Ok(String::from_utf8(plaintext).map_err(|err| err.utf8_error())?)
+    }
+
+    /// Attempts to decrypt a PASETO token, returning the plaintext bytes exactly as they were
+    /// encrypted.
+    ///
+    /// No assumptions are made about the contents of the payload so it needn't be valid UTF-8
+    /// or JSON, which is useful when migrating or debugging tokens from other producers.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+    /// # let nonce = PasetoNonce::<V3, Local>::from(&nonce);
+    /// # let token = Paseto::<V3, Local>::builder().set_payload(Payload::from("not json")).try_encrypt(&key, &nonce)?;
+    /// let plaintext = Paseto::<V3, Local>::try_decrypt_raw(&token, &key, None, None)?;
+    /// # assert_eq!(plaintext, b"not json");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_raw(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V3, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>> + Copy,
+    ) -> Result<Vec<u8>, PasetoError> {
         //get footer
 
         let decoded_payload = Self::parse_raw_token(token, footer, &V3::default(), &Local::default())?;
+        Self::decrypt_payload(
+            &decoded_payload,
+            key,
+            &footer.into().unwrap_or_default(),
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
+
+    /// Attempts to decrypt a PASETO token which was encrypted with additional context bytes bound
+    /// through [Paseto::set_additional_data]
+    ///
+    /// Decryption fails unless the same additional data is supplied.
+    pub fn try_decrypt_with_additional_data(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V3, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+        additional_data: &[u8],
+    ) -> Result<String, PasetoError> {
+        let decoded_payload = Self::parse_raw_token(token, footer, &V3::default(), &Local::default())?;
+        let implicit_assertion = implicit_assertion.into().unwrap_or_default();
+        let plaintext = Self::decrypt_payload(
+            &decoded_payload,
+            key,
+            &footer.into().unwrap_or_default(),
+            &fold_additional_data(implicit_assertion.as_bytes(), Some(additional_data)),
+        )?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+
+    fn decrypt_payload(
+        decoded_payload: &[u8],
+        key: &PasetoSymmetricKey<V3, Local>,
+        footer: &[u8],
+        implicit_assertion: &[u8],
+    ) -> Result<Vec<u8>, PasetoError> {
         let nonce = Key::from(&decoded_payload[..32]);
         let nonce = PasetoNonce::<V3, Local>::from(&nonce);
 
@@ -47,23 +109,23 @@ impl<'a> Paseto<'a, V3, Local> {
             &Header::<V3, Local>::default(),
             nonce.as_ref(),
             ciphertext,
-            &footer.into().unwrap_or_default(),
-            &implicit_assertion.into().unwrap_or_default(),
+            footer,
+            implicit_assertion,
         ]);
 
         //generate tags
         let tag = &decoded_payload[(nonce.len() + ciphertext.len())..];
         let tag2 = &Tag::<V3, Local>::from(authentication_key, &pae);
-        //compare tags
-        ConstantTimeEquals(tag, tag2)?;
+        //compare tags in constant time
+        if !bool::from(tag.ct_eq(tag2)) {
+            return Err(PasetoError::InvalidSignature);
+        }
 
         //decrypt payload
         let ciphertext = CipherText::<V3, Local>::from(ciphertext, &encryption_key);
 
-        let decoded_str = str::from_utf8(&ciphertext)?;
-
         //return decrypted payload
-        Ok(decoded_str.to_owned())
+        Ok(ciphertext.ciphertext)
     }
 
     /// Attempts to encrypt a PASETO token
@@ -89,7 +151,7 @@ impl<'a> Paseto<'a, V3, Local> {
     ) -> Result<String, PasetoError> {
         //setup
         let footer = self.footer.unwrap_or_default();
-        let implicit_assertion = self.implicit_assertion.unwrap_or_default();
+        let implicit_assertion = self.implicit_assertion_bytes();
 
         //split key
         let authentication_key =
@@ -107,7 +169,7 @@ impl<'a> Paseto<'a, V3, Local> {
         let tag = Tag::<V3, Local>::from(authentication_key, &pae);
 
         //      //generate appended and base64 encoded payload
-        let raw_payload = RawPayload::<V3, Local>::from(nonce, &ciphertext, &tag)?;
+        let raw_payload = RawPayload::<V3, Local>::from::<Codec>(nonce, &ciphertext, &tag)?;
 
         //format as paseto with header and optional footer
         Ok(self.format_token(&raw_payload))
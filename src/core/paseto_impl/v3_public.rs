@@ -1,7 +1,8 @@
 #![cfg(feature = "v3_public")]
 
-use crate::core::{Footer, Header, ImplicitAssertion, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V3};
+use crate::core::{Base64Codec, Footer, Header, ImplicitAssertion, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V3};
 use crate::core::common::{PreAuthenticationEncoding, RawPayload};
+use crate::core::paseto::fold_additional_data;
 use p384::ecdsa::{
     signature::DigestSigner, signature::DigestVerifier, Signature, SigningKey, VerifyingKey,
 };
@@ -9,7 +10,7 @@ use p384::elliptic_curve::sec1::ToEncodedPoint;
 use p384::PublicKey;
 use sha2::Digest;
 
-impl<'a> Paseto<'a, V3, Public> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V3, Public, Codec> {
     /// Verifies a signed V3 Public Paseto
     pub fn try_verify(
         signature: &'a str,
@@ -18,7 +19,41 @@ impl<'a> Paseto<'a, V3, Public> {
         implicit_assertion: (impl Into<Option<ImplicitAssertion<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
         let decoded_payload = Self::parse_raw_token(signature, footer, &V3::default(), &Public::default())?;
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            &footer.into().unwrap_or_default(),
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
+
+    /// Verifies a signed V3 Public Paseto which was signed with additional context bytes bound
+    /// through [Paseto::set_additional_data]
+    ///
+    /// Verification fails unless the same additional data is supplied.
+    pub fn try_verify_with_additional_data(
+        signature: &'a str,
+        public_key: &PasetoAsymmetricPublicKey<V3, Public>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+        additional_data: &[u8],
+    ) -> Result<String, PasetoError> {
+        let decoded_payload = Self::parse_raw_token(signature, footer, &V3::default(), &Public::default())?;
+        let implicit_assertion = implicit_assertion.into().unwrap_or_default();
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            &footer.into().unwrap_or_default(),
+            &fold_additional_data(implicit_assertion.as_bytes(), Some(additional_data)),
+        )
+    }
 
+    fn verify_payload(
+        decoded_payload: &[u8],
+        public_key: &PasetoAsymmetricPublicKey<V3, Public>,
+        footer: &[u8],
+        implicit_assertion: &[u8],
+    ) -> Result<String, PasetoError> {
         //compress the key
         let compressed_public_key = PublicKey::from_sec1_bytes(public_key.as_ref())
             .map_err(|_| PasetoError::InvalidKey)?
@@ -34,8 +69,8 @@ impl<'a> Paseto<'a, V3, Public> {
             compressed_public_key.as_ref(),
             &Header::<V3, Public>::default(),
             msg,
-            &footer.into().unwrap_or_default(),
-            &implicit_assertion.into().unwrap_or_default(),
+            footer,
+            implicit_assertion,
         ]);
         let mut msg_digest = sha2::Sha384::default();
         msg_digest.update(&*m2);
@@ -51,7 +86,7 @@ impl<'a> Paseto<'a, V3, Public> {
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V3, Public>) -> Result<String, PasetoError> {
         let footer = self.footer.unwrap_or_default();
 
-        let implicit_assertion = self.implicit_assertion.unwrap_or_default();
+        let implicit_assertion = self.implicit_assertion_bytes();
         let signing_key = SigningKey::from_bytes(key.as_ref().into()).map_err(|_| PasetoError::InvalidKey)?;
         let public_key = VerifyingKey::from(&signing_key).to_encoded_point(true);
 
@@ -66,7 +101,7 @@ impl<'a> Paseto<'a, V3, Public> {
         msg_digest.update(&*m2);
         let signature: Signature = signing_key
             .try_sign_digest(msg_digest)?;
-        let raw_payload = RawPayload::<V3, Public>::from(&self.payload, &signature.to_bytes());
+        let raw_payload = RawPayload::<V3, Public>::from::<Codec>(&self.payload, &signature.to_bytes());
         Ok(self.format_token(&raw_payload))
     }
 }
@@ -1,13 +1,14 @@
 #![cfg(feature = "v4_local")]
 
-use std::str;
+use std::io::Write;
 
-use ring::constant_time::verify_slices_are_equal as ConstantTimeEquals;
+use subtle::ConstantTimeEq;
 
-use crate::core::{Footer, Header, ImplicitAssertion, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, V4};
+use crate::core::{Base64Codec, Footer, Header, ImplicitAssertion, Key, Local, Paseto, PasetoError, PasetoNonce, PasetoSymmetricKey, Payload, V4};
+use crate::core::paseto::fold_additional_data;
 use crate::core::common::{AuthenticationKey, AuthenticationKeySeparator, CipherText, EncryptionKey, EncryptionKeySeparator, PreAuthenticationEncoding, RawPayload, Tag};
 
-impl<'a> Paseto<'a, V4, Local> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V4, Local, Codec> {
     /// Attempts to decrypt a PASETO token
     /// ```
     /// # use serde_json::json;
@@ -30,9 +31,149 @@ impl<'a> Paseto<'a, V4, Local> {
         footer: (impl Into<Option<Footer<'a>>> + Copy),
         implicit_assertion: (impl Into<Option<ImplicitAssertion<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
-        //get footer
+        let plaintext = Self::try_decrypt_raw(token, key, footer, implicit_assertion)?;
 
+        //take ownership of the decrypted buffer rather than copying it
+        Ok(String::from_utf8(plaintext).map_err(|err| err.utf8_error())?)
+    }
+
+    /// Attempts to decrypt a PASETO token, returning the plaintext bytes exactly as they were
+    /// encrypted.
+    ///
+    /// No assumptions are made about the contents of the payload so it needn't be valid UTF-8
+    /// or JSON, which is useful when migrating or debugging tokens from other producers.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("not json")).try_encrypt(&key, &nonce)?;
+    /// let plaintext = Paseto::<V4, Local>::try_decrypt_raw(&token, &key, None, None)?;
+    /// # assert_eq!(plaintext, b"not json");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_raw(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>> + Copy,
+    ) -> Result<Vec<u8>, PasetoError> {
+        let decoded_payload = Self::parse_raw_token(token, footer, &V4::default(), &Local::default())?;
+        Self::decrypt_payload(
+            &decoded_payload,
+            key,
+            &footer.into().unwrap_or_default(),
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
+
+    /// Attempts to decrypt a PASETO token whose footer is transmitted separately from the token.
+    ///
+    /// The token string must not contain a footer segment. The detached footer is still
+    /// authenticated, so decryption fails if it differs from the footer the token was encrypted with.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("claims")).set_footer(Footer::from("kid-1")).try_encrypt(&key, &nonce)?;
+    /// // the footer segment travels out-of-band
+    /// let (token, _) = token.rsplit_once('.').unwrap();
+    /// let payload = Paseto::<V4, Local>::try_decrypt_detached_footer(token, &key, b"kid-1", None)?;
+    /// # assert_eq!(payload, "claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_detached_footer(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: &[u8],
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+    ) -> Result<String, PasetoError> {
+        //an attached footer is rejected since none is expected in the token string
+        let decoded_payload = Self::parse_raw_token(token, None::<Footer>, &V4::default(), &Local::default())?;
+        let plaintext =
+            Self::decrypt_payload(&decoded_payload, key, footer, &implicit_assertion.into().unwrap_or_default())?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+
+    /// Attempts to decrypt a PASETO token, writing the plaintext to `w` rather than returning it
+    ///
+    /// The authentication tag is fully verified before any plaintext is written, so nothing reaches
+    /// `w` for a tampered token. This means the whole token is buffered in memory: peak usage is
+    /// roughly twice the decoded token length, for the ciphertext and the plaintext.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("claims")).try_encrypt(&key, &nonce)?;
+    /// let mut buffer = Vec::new();
+    /// Paseto::<V4, Local>::try_decrypt_to(&token, &key, None, None, &mut buffer)?;
+    /// # assert_eq!(buffer, b"claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_to<W: Write>(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>> + Copy,
+        w: &mut W,
+    ) -> Result<(), PasetoError> {
+        let plaintext = Self::try_decrypt_raw(token, key, footer, implicit_assertion)?;
+        w.write_all(&plaintext)?;
+        Ok(())
+    }
+
+    /// Attempts to decrypt a PASETO token which was encrypted with additional context bytes bound
+    /// through [Paseto::set_additional_data]
+    ///
+    /// Decryption fails unless the same additional data is supplied.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("claims")).set_additional_data(b"channel").try_encrypt(&key, &nonce)?;
+    /// let payload = Paseto::<V4, Local>::try_decrypt_with_additional_data(&token, &key, None, None, b"channel")?;
+    /// # assert_eq!(payload, "claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_decrypt_with_additional_data(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+        additional_data: &[u8],
+    ) -> Result<String, PasetoError> {
         let decoded_payload = Self::parse_raw_token(token, footer, &V4::default(), &Local::default())?;
+        let implicit_assertion = implicit_assertion.into().unwrap_or_default();
+        let plaintext = Self::decrypt_payload(
+            &decoded_payload,
+            key,
+            &footer.into().unwrap_or_default(),
+            &fold_additional_data(implicit_assertion.as_bytes(), Some(additional_data)),
+        )?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+
+    /// Returns the exact message that is authenticated for a (V4, Local) token: the
+    /// pre-authentication encoding of the header, nonce, ciphertext, footer and implicit assertion
+    /// that the BLAKE2b tag is computed over.
+    ///
+    /// This is the associated data of the encryption scheme, exposed so external tooling can
+    /// reproduce tags independently.
+    pub fn authentication_input(nonce: &[u8], ciphertext: &[u8], footer: &[u8], implicit_assertion: &[u8]) -> Vec<u8> {
+        PreAuthenticationEncoding::parse(&[&Header::<V4, Local>::default(), nonce, ciphertext, footer, implicit_assertion])
+            .as_ref()
+            .clone()
+    }
+
+    fn decrypt_payload(
+        decoded_payload: &[u8],
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: &[u8],
+        implicit_assertion: &[u8],
+    ) -> Result<Vec<u8>, PasetoError> {
         let nonce = Key::from(&decoded_payload[..32]);
         let nonce = PasetoNonce::<V4, Local>::from(&nonce);
 
@@ -47,23 +188,50 @@ impl<'a> Paseto<'a, V4, Local> {
             &Header::<V4, Local>::default(),
             nonce.as_ref(),
             ciphertext,
-            &footer.into().unwrap_or_default(),
-            &implicit_assertion.into().unwrap_or_default(),
+            footer,
+            implicit_assertion,
         ]);
 
         //generate tags
         let tag = &decoded_payload[(nonce.len() + ciphertext.len())..];
         let tag2 = &Tag::<V4, Local>::from(authentication_key, &pae);
-        //compare tags
-        ConstantTimeEquals(tag, tag2)?;
+        //compare tags in constant time
+        if !bool::from(tag.ct_eq(tag2)) {
+            return Err(PasetoError::InvalidSignature);
+        }
 
         //decrypt payload
         let ciphertext = CipherText::<V4, Local>::from(ciphertext, &encryption_key);
 
-        let decoded_str = str::from_utf8(&ciphertext)?;
-
         //return decrypted payload
-        Ok(decoded_str.to_owned())
+        Ok(ciphertext.ciphertext)
+    }
+
+    /// Attempts to decrypt a PASETO token and compares the decrypted payload with an expected
+    /// value in constant time.
+    ///
+    /// Intended for tokens whose payload is an opaque secret rather than a set of claims.
+    /// Returns `Ok(false)` when the token decrypts but the payload doesn't match.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("an opaque secret")).try_encrypt(&key, &nonce)?;
+    /// let matches = Paseto::<V4, Local>::decrypt_and_match(&token, &key, b"an opaque secret", None, None)?;
+    /// # assert!(matches);
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn decrypt_and_match(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        expected_payload: &[u8],
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>> + Copy,
+    ) -> Result<bool, PasetoError> {
+        //compare the raw bytes so opaque payloads needn't be valid UTF-8
+        let payload = Self::try_decrypt_raw(token, key, footer, implicit_assertion)?;
+        Ok(payload.ct_eq(expected_payload).into())
     }
 
     /// Attempts to encrypt a PASETO token
@@ -88,10 +256,78 @@ impl<'a> Paseto<'a, V4, Local> {
         &mut self,
         key: &PasetoSymmetricKey<V4, Local>,
         nonce: &PasetoNonce<V4, Local>,
+    ) -> Result<String, PasetoError> {
+        let raw_payload = self.encrypt_raw_payload(key, nonce)?;
+
+        //format as paseto with header and optional footer
+        Ok(self.format_token(&raw_payload))
+    }
+
+    /// Encrypts a map of claims as the JSON payload of a PASETO token under a random nonce, for
+    /// simple cases which don't need the claim validation of the generic layer
+    /// ```
+    /// # use serde_json::json;
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// let claims = json!({"sub": "loyal subjects", "seats": 4});
+    /// let token = Paseto::<V4, Local>::encrypt_claims(claims.as_object().unwrap(), &key, None, None)?;
+    /// # let json = Paseto::<V4, Local>::try_decrypt(&token, &key, None, None)?;
+    /// # assert_eq!(serde_json::from_str::<serde_json::Value>(&json)?, claims);
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn encrypt_claims(
+        claims: &serde_json::Map<String, serde_json::Value>,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: impl Into<Option<Footer<'a>>>,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+    ) -> Result<String, PasetoError> {
+        let payload = serde_json::Value::Object(claims.clone()).to_string();
+        let nonce = Key::<32>::try_new_random()?;
+
+        let mut paseto = Paseto::<V4, Local, Codec>::builder();
+        paseto.set_payload(Payload::from(payload.as_str()));
+        if let Some(footer) = footer.into() {
+            paseto.set_footer(footer);
+        }
+        if let Some(implicit_assertion) = implicit_assertion.into() {
+            paseto.set_implicit_assertion(implicit_assertion);
+        }
+        paseto.try_encrypt(key, &PasetoNonce::<V4, Local>::from(&nonce))
+    }
+
+    /// Attempts to encrypt a PASETO token, writing it directly to `w` rather than returning a `String`
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// let mut buffer = Vec::new();
+    /// Paseto::<V4, Local>::builder()
+    ///     .set_payload(Payload::from("claims"))
+    ///     .try_encrypt_to(&key, &nonce, &mut buffer)?;
+    /// # let token = String::from_utf8(buffer)?;
+    /// # assert_eq!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None)?, "claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_encrypt_to<W: Write>(
+        &mut self,
+        key: &PasetoSymmetricKey<V4, Local>,
+        nonce: &PasetoNonce<V4, Local>,
+        w: &mut W,
+    ) -> Result<(), PasetoError> {
+        let raw_payload = self.encrypt_raw_payload(key, nonce)?;
+        self.write_token(w, &raw_payload)
+    }
+
+    fn encrypt_raw_payload(
+        &self,
+        key: &PasetoSymmetricKey<V4, Local>,
+        nonce: &PasetoNonce<V4, Local>,
     ) -> Result<String, PasetoError> {
         //setup
         let footer = self.footer.unwrap_or_default();
-        let implicit_assertion = self.implicit_assertion.unwrap_or_default();
+        let implicit_assertion = self.implicit_assertion_bytes();
 
         //split key
         let authentication_key =
@@ -109,9 +345,87 @@ impl<'a> Paseto<'a, V4, Local> {
         let tag = Tag::<V4, Local>::from(authentication_key, &pae);
 
         //generate appended and base64 encoded payload
-        let raw_payload = RawPayload::<V4, Local>::try_from(nonce, &ciphertext, &tag)?;
+        let raw_payload = RawPayload::<V4, Local>::try_from::<Codec>(nonce, &ciphertext, &tag)?;
+        Ok(raw_payload)
+    }
 
-        //format as paseto with header and optional footer
-        Ok(self.format_token(&raw_payload))
+    /// Decrypts a PASETO token and encrypts the same payload again with a new footer, for example
+    /// to change the `kid` in a footer without re-minting the claims.
+    ///
+    /// Footers are authenticated rather than encrypted, so changing one still requires a full
+    /// decrypt and encrypt with a fresh nonce. The implicit assertion is carried over unchanged.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?);
+    /// # let nonce = Key::<32>::try_new_random()?;
+    /// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    /// # let token = Paseto::<V4, Local>::builder().set_payload(Payload::from("claims")).set_footer(Footer::from("kid-1")).try_encrypt(&key, &nonce)?;
+    /// let new_nonce = Key::<32>::try_new_random()?;
+    /// let token = Paseto::<V4, Local>::reencrypt_with_footer(
+    ///     &token,
+    ///     &key,
+    ///     Footer::from("kid-1"),
+    ///     Footer::from("kid-2"),
+    ///     None,
+    ///     &PasetoNonce::<V4, Local>::from(&new_nonce),
+    /// )?;
+    /// # assert_eq!(Paseto::<V4, Local>::try_decrypt(&token, &key, Footer::from("kid-2"), None)?, "claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn reencrypt_with_footer(
+        token: &'a str,
+        key: &PasetoSymmetricKey<V4, Local>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        new_footer: impl Into<Option<Footer<'a>>>,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>> + Copy,
+        nonce: &PasetoNonce<V4, Local>,
+    ) -> Result<String, PasetoError> {
+        let payload = Self::try_decrypt(token, key, footer, implicit_assertion)?;
+
+        let mut builder = Self::builder();
+        builder.set_payload(Payload::from(payload.as_str()));
+        if let Some(new_footer) = new_footer.into() {
+            builder.set_footer(new_footer);
+        }
+        if let Some(implicit_assertion) = implicit_assertion.into() {
+            builder.set_implicit_assertion(implicit_assertion);
+        }
+        builder.try_encrypt(key, nonce)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    //builds a token around an arbitrary byte payload, which the public API only accepts as a str
+    fn encrypt_bytes(payload: &[u8], key: &PasetoSymmetricKey<V4, Local>) -> String {
+        let nonce = Key::<32>::from(*b"nonce for a non-UTF-8 payload!!!");
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        let authentication_key =
+            AuthenticationKey::<V4, Local>::from(&(AuthenticationKeySeparator::default() + &nonce), key);
+        let encryption_key = EncryptionKey::<V4, Local>::from(&(EncryptionKeySeparator::default() + &nonce), key);
+        let ciphertext = CipherText::<V4, Local>::from(payload, &encryption_key);
+        let header = Header::<V4, Local>::default();
+        let pae = PreAuthenticationEncoding::parse(&[&header, nonce.as_ref(), &ciphertext, &[], &[]]);
+        let tag = Tag::<V4, Local>::from(authentication_key, &pae);
+        let raw_payload =
+            RawPayload::<V4, Local>::try_from::<crate::core::DefaultBase64Codec>(&nonce, &ciphertext, &tag).unwrap();
+        format!("{}{}", header, raw_payload)
+    }
+
+    #[test]
+    fn test_decrypt_and_match_non_utf8_payload() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        //not valid UTF-8, so it can only be read back as raw bytes
+        let secret = [0xff, 0xfe, 0x00, 0x80, 0xc3, 0x28];
+        let token = encrypt_bytes(&secret, &key);
+
+        assert!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None).is_err());
+        assert_eq!(Paseto::<V4, Local>::try_decrypt_raw(&token, &key, None, None).unwrap(), secret);
+
+        assert!(Paseto::<V4, Local>::decrypt_and_match(&token, &key, &secret, None, None).unwrap());
+        assert!(!Paseto::<V4, Local>::decrypt_and_match(&token, &key, &secret[..5], None, None).unwrap());
+        assert!(!Paseto::<V4, Local>::decrypt_and_match(&token, &key, b"secret", None, None).unwrap());
     }
 }
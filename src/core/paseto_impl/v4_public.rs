@@ -1,9 +1,10 @@
 #![cfg(feature = "v4_public")]
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
-use crate::core::{Footer, Header, ImplicitAssertion, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V4};
+use crate::core::{Base64Codec, Footer, Header, ImplicitAssertion, Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, PasetoError, Public, V4};
 use crate::core::common::{PreAuthenticationEncoding, RawPayload};
+use crate::core::paseto::fold_additional_data;
 
-impl<'a> Paseto<'a, V4, Public> {
+impl<'a, Codec: Base64Codec> Paseto<'a, V4, Public, Codec> {
     pub fn try_verify(
         signature: &'a str,
         public_key: &PasetoAsymmetricPublicKey<V4, Public>,
@@ -11,29 +12,121 @@ impl<'a> Paseto<'a, V4, Public> {
         implicit_assertion: (impl Into<Option<ImplicitAssertion<'a>>> + Copy),
     ) -> Result<String, PasetoError> {
         let decoded_payload = Self::parse_raw_token(signature, footer, &V4::default(), &Public::default())?;
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            &footer.into().unwrap_or_default(),
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
+
+    /// Attempts to verify a signed PASETO token whose footer is transmitted separately from the token.
+    ///
+    /// The token string must not contain a footer segment. The detached footer is still covered by
+    /// the signature, so verification fails if it differs from the footer the token was signed with.
+    pub fn try_verify_detached_footer(
+        signature: &'a str,
+        public_key: &PasetoAsymmetricPublicKey<V4, Public>,
+        footer: &[u8],
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+    ) -> Result<String, PasetoError> {
+        //an attached footer is rejected since none is expected in the token string
+        let decoded_payload = Self::parse_raw_token(signature, None::<Footer>, &V4::default(), &Public::default())?;
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            footer,
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
 
+    /// Attempts to verify a signed PASETO token which was signed with additional context bytes bound
+    /// through [Paseto::set_additional_data]
+    ///
+    /// Verification fails unless the same additional data is supplied.
+    pub fn try_verify_with_additional_data(
+        signature: &'a str,
+        public_key: &PasetoAsymmetricPublicKey<V4, Public>,
+        footer: impl Into<Option<Footer<'a>>> + Copy,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+        additional_data: &[u8],
+    ) -> Result<String, PasetoError> {
+        let decoded_payload = Self::parse_raw_token(signature, footer, &V4::default(), &Public::default())?;
+        let implicit_assertion = implicit_assertion.into().unwrap_or_default();
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            &footer.into().unwrap_or_default(),
+            &fold_additional_data(implicit_assertion.as_bytes(), Some(additional_data)),
+        )
+    }
+
+    /// Attempts to verify a signed PASETO token which has already been split into its base64
+    /// payload and optional footer segments, e.g. by a proxy which tokenizes tokens itself.
+    ///
+    /// The `v4.public.` header isn't passed since it's implied by the type.
+    /// ```
+    /// # use rusty_paseto::core::*;
+    /// # let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+    /// # let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&private_key);
+    /// # let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+    /// # let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+    /// # let token = Paseto::<V4, Public>::builder().set_payload(Payload::from("claims")).set_footer(Footer::from("kid-1")).try_sign(&private_key)?;
+    /// let mut segments = token.split('.').skip(2);
+    /// let (payload, footer) = (segments.next().unwrap(), segments.next());
+    /// let claims = Paseto::<V4, Public>::try_verify_parts(payload, footer, &public_key, None)?;
+    /// # assert_eq!(claims, "claims");
+    /// # Ok::<(),anyhow::Error>(())
+    /// ```
+    pub fn try_verify_parts(
+        payload_b64: &str,
+        footer_b64: Option<&str>,
+        public_key: &PasetoAsymmetricPublicKey<V4, Public>,
+        implicit_assertion: impl Into<Option<ImplicitAssertion<'a>>>,
+    ) -> Result<String, PasetoError> {
+        let decoded_payload = Self::decode_payload(payload_b64)?;
+        let footer = footer_b64.map(Codec::decode).transpose()?.unwrap_or_default();
+        Self::verify_payload(
+            &decoded_payload,
+            public_key,
+            &footer,
+            &implicit_assertion.into().unwrap_or_default(),
+        )
+    }
+
+    /// Returns the exact message that is signed for a (V4, Public) token: the pre-authentication
+    /// encoding of the header, payload, footer and implicit assertion.
+    ///
+    /// This is the message checked during verification, exposed so external tooling can
+    /// reproduce signatures independently.
+    pub fn signing_input(payload: &[u8], footer: &[u8], implicit_assertion: &[u8]) -> Vec<u8> {
+        PreAuthenticationEncoding::parse(&[&Header::<V4, Public>::default(), payload, footer, implicit_assertion])
+            .as_ref()
+            .clone()
+    }
+
+    fn verify_payload(
+        decoded_payload: &[u8],
+        public_key: &PasetoAsymmetricPublicKey<V4, Public>,
+        footer: &[u8],
+        implicit_assertion: &[u8],
+    ) -> Result<String, PasetoError> {
         let verifying_key: VerifyingKey = VerifyingKey::from_bytes(<&[u8; 32]>::try_from(public_key.as_ref())?)?;
 
         let msg = decoded_payload[..(decoded_payload.len() - ed25519_dalek::SIGNATURE_LENGTH)].as_ref();
         let sig = decoded_payload[msg.len()..msg.len() + ed25519_dalek::SIGNATURE_LENGTH].as_ref();
 
         let signature = Signature::try_from(sig)?;
-        let pae = PreAuthenticationEncoding::parse(&[
-            &Header::<V4, Public>::default(),
-            msg,
-            &footer.into().unwrap_or_default(),
-            &implicit_assertion.into().unwrap_or_default(),
-        ]);
+        let pae = PreAuthenticationEncoding::parse(&[&Header::<V4, Public>::default(), msg, footer, implicit_assertion]);
 
         verifying_key.verify(&pae, &signature)?;
-        // public_key.verify(&pae, &signature)?;
 
         Ok(String::from_utf8(Vec::from(msg))?)
     }
 
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V4, Public>) -> Result<String, PasetoError> {
         let footer = self.footer.unwrap_or_default();
-        let assertion = self.implicit_assertion.unwrap_or_default();
+        let assertion = self.implicit_assertion_bytes();
         // let secret_key : SecretKey = SecretKey::try_from(key.as_ref())?;
         let signing_key = SigningKey::from_keypair_bytes(<&[u8; 64]>::try_from(key.as_ref())?)?;
 
@@ -44,7 +137,7 @@ impl<'a> Paseto<'a, V4, Public> {
 
         let signature = signing_key.sign(&pae);
 
-        let raw_payload = RawPayload::<V4, Public>::from(&self.payload, &signature.to_bytes());
+        let raw_payload = RawPayload::<V4, Public>::from::<Codec>(&self.payload, &signature.to_bytes());
 
         Ok(self.format_token(&raw_payload))
     }
@@ -0,0 +1,138 @@
+use super::*;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A token string which carries its PASETO version and purpose in its type, so handing it to a
+/// parser of another version or purpose is a compile error rather than a runtime one.
+///
+/// Tokens built with the prelude's `build_token` are typed from the start, while token strings
+/// received from elsewhere are typed with `TryFrom`, which checks the header. A typed token is
+/// parsed with the prelude's `parse_token`.
+///
+/// # Usage
+/// ```
+/// # #[cfg(all(feature = "prelude", feature = "v4_local"))]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+/// let token: PasetoToken<V4, Local> = PasetoBuilder::<V4, Local>::default()
+///   .set_claim(SubjectClaim::from("loyal subjects"))
+///   .build_token(&key)?;
+///
+/// let json = PasetoParser::<V4, Local>::default().parse_token(&token, &key)?;
+/// assert_eq!(json["sub"], "loyal subjects");
+///
+/// //a token received as a string is checked against the expected header
+/// let received = PasetoToken::<V4, Local>::try_from(token.to_string())?;
+/// assert_eq!(received, token);
+/// assert!(PasetoToken::<V4, Public>::try_from(token.to_string()).is_err());
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+pub struct PasetoToken<Version, Purpose> {
+  version: PhantomData<Version>,
+  purpose: PhantomData<Purpose>,
+  token: String,
+}
+
+impl<Version, Purpose> PasetoToken<Version, Purpose> {
+  //wraps a token the crate itself just built for this version and purpose
+  pub(crate) fn from_built(token: String) -> Self {
+    Self {
+      version: PhantomData,
+      purpose: PhantomData,
+      token,
+    }
+  }
+
+  /// The token string
+  pub fn as_str(&self) -> &str {
+    &self.token
+  }
+
+  /// Consumes the typed token, returning the token string
+  pub fn into_string(self) -> String {
+    self.token
+  }
+}
+
+impl<Version: VersionTrait, Purpose: PurposeTrait> TryFrom<String> for PasetoToken<Version, Purpose> {
+  type Error = PasetoError;
+
+  /// Types a token string, failing with [PasetoError::WrongHeader] unless it starts with the
+  /// header of this version and purpose. Nothing else about the token is checked until it's parsed.
+  fn try_from(token: String) -> Result<Self, Self::Error> {
+    if !token.starts_with(Header::<Version, Purpose>::default().as_ref()) {
+      return Err(PasetoError::WrongHeader);
+    }
+    Ok(Self::from_built(token))
+  }
+}
+
+impl<Version: VersionTrait, Purpose: PurposeTrait> TryFrom<&str> for PasetoToken<Version, Purpose> {
+  type Error = PasetoError;
+
+  fn try_from(token: &str) -> Result<Self, Self::Error> {
+    Self::try_from(token.to_string())
+  }
+}
+
+impl<Version, Purpose> Clone for PasetoToken<Version, Purpose> {
+  fn clone(&self) -> Self {
+    Self::from_built(self.token.clone())
+  }
+}
+
+impl<Version, Purpose> PartialEq for PasetoToken<Version, Purpose> {
+  fn eq(&self, other: &Self) -> bool {
+    self.token == other.token
+  }
+}
+
+impl<Version, Purpose> Eq for PasetoToken<Version, Purpose> {}
+
+impl<Version, Purpose> PartialEq<str> for PasetoToken<Version, Purpose> {
+  fn eq(&self, other: &str) -> bool {
+    self.token == other
+  }
+}
+
+impl<Version, Purpose> PartialEq<&str> for PasetoToken<Version, Purpose> {
+  fn eq(&self, other: &&str) -> bool {
+    self.token == *other
+  }
+}
+
+impl<Version, Purpose> AsRef<str> for PasetoToken<Version, Purpose> {
+  fn as_ref(&self) -> &str {
+    &self.token
+  }
+}
+
+impl<Version, Purpose> fmt::Display for PasetoToken<Version, Purpose> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.token)
+  }
+}
+
+impl<Version, Purpose> fmt::Debug for PasetoToken<Version, Purpose> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("PasetoToken").field(&self.token).finish()
+  }
+}
+
+#[cfg(all(test, feature = "v4"))]
+mod unit_tests {
+  use super::*;
+
+  #[test]
+  fn test_try_from_checks_header() {
+    let token = PasetoToken::<V4, Local>::try_from("v4.local.payload").unwrap();
+    assert_eq!(token, "v4.local.payload");
+    assert_eq!(token.clone().into_string(), "v4.local.payload");
+
+    for token in ["v4.public.payload", "v3.local.payload", "v4.local", ""] {
+      assert!(matches!(PasetoToken::<V4, Local>::try_from(token), Err(PasetoError::WrongHeader)));
+    }
+  }
+}
@@ -5,7 +5,11 @@ use std::fmt::Display;
 /// Symmetric encryption
 #[derive(Debug, Clone, Copy)]
 pub struct Local(&'static str);
-impl PurposeTrait for Local {}
+impl PurposeTrait for Local {
+  fn name() -> &'static str {
+    "local"
+  }
+}
 impl Default for Local {
   fn default() -> Self {
     Self("local")
@@ -6,7 +6,11 @@ use std::fmt::Display;
 #[derive(Debug, Clone, Copy)]
 pub struct Public(&'static str);
 
-impl PurposeTrait for Public {}
+impl PurposeTrait for Public {
+  fn name() -> &'static str {
+    "public"
+  }
+}
 impl AsRef<str> for Public {
   fn as_ref(&self) -> &str {
     self.0
@@ -1,13 +1,45 @@
+use super::Base64Codec;
 use base64::DecodeError;
-use base64::prelude::*;
-use ring::constant_time::verify_slices_are_equal as ConstantTimeEquals;
+use subtle::ConstantTimeEq;
 use std::fmt::Display;
 
 //marker traits
 /// Used by marker traits to determine at compile time which PASETO version the user is attempting to use
-pub trait VersionTrait: Display + Default + AsRef<str> {}
+pub trait VersionTrait: Display + Default + AsRef<str> {
+  //the lengths default to 0 for versions defined outside of this crate, which skips the minimum
+  //payload length check made while decoding
+
+  /// The length in bytes of the symmetric key used by local tokens
+  const LOCAL_KEY_LEN: usize = 0;
+  /// The length in bytes of the nonce prepended to the payload of local tokens
+  const LOCAL_NONCE_LEN: usize = 0;
+  /// The length in bytes of the authentication tag appended to the payload of local tokens
+  const LOCAL_TAG_LEN: usize = 0;
+  /// The length in bytes of the signature appended to the payload of public tokens
+  const PUBLIC_SIG_LEN: usize = 0;
+
+  /// The name of the version as it appears in a token header, e.g. `"v4"`
+  ///
+  /// Defaults to the name of the implementing type for versions defined outside of this crate.
+  fn name() -> &'static str {
+    type_name_tail::<Self>()
+  }
+}
 /// Used by marker traits to determine at compile time which PASETO purpose the user is attempting to use
-pub trait PurposeTrait: Display + Default + AsRef<str> {}
+pub trait PurposeTrait: Display + Default + AsRef<str> {
+  /// The name of the purpose as it appears in a token header, e.g. `"local"`
+  ///
+  /// Defaults to the name of the implementing type for purposes defined outside of this crate.
+  fn name() -> &'static str {
+    type_name_tail::<Self>()
+  }
+}
+
+//the unqualified name of a type, e.g. `V4` rather than `rusty_paseto::core::V4`
+fn type_name_tail<T: ?Sized>() -> &'static str {
+  let name = std::any::type_name::<T>();
+  name.rsplit("::").next().unwrap_or(name)
+}
 pub trait V1orV3: VersionTrait {}
 /// A marker trait used to determine if the PASETO token version is capable of using an implicit
 /// assertion. Currently this applies only to V3/V4 PASETO tokens
@@ -16,17 +48,61 @@ pub trait V2orV4: VersionTrait {}
 
 /// Enable a type to encode/decode to/from base64 and compare itself to another implementer using
 /// constant time comparision
-pub(crate) trait Base64Encodable<T: ?Sized + AsRef<[u8]>>: Display + AsRef<T> {
-  fn encode(&self) -> String {
-    BASE64_URL_SAFE_NO_PAD.encode(self.as_ref())
+pub(crate) trait Base64Encodable<T: ?Sized + AsRef<[u8]> + AsRef<str>>: Display + AsRef<T> {
+  fn encode<Codec: Base64Codec>(&self) -> String {
+    Codec::encode(AsRef::<[u8]>::as_ref(self.as_ref()))
   }
-  fn decode(&self) -> Result<Vec<u8>, DecodeError> {
-    BASE64_URL_SAFE_NO_PAD.decode(self.as_ref())
+  fn decode<Codec: Base64Codec>(&self) -> Result<Vec<u8>, DecodeError> {
+    Codec::decode(AsRef::<str>::as_ref(self.as_ref()))
   }
-  fn constant_time_equals<B>(&self, other: B) -> bool
+  fn constant_time_equals<Codec: Base64Codec, B>(&self, other: B) -> bool
   where
     B: AsRef<str>,
   {
-    ConstantTimeEquals(self.encode().as_ref(), other.as_ref().as_bytes()).is_ok()
+    self.encode::<Codec>().as_bytes().ct_eq(other.as_ref().as_bytes()).into()
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+  use crate::core::{Local, V4};
+
+  #[test]
+  fn test_version_and_purpose_names() {
+    assert_eq!(V4::name(), "v4");
+    assert_eq!(Local::name(), "local");
+    assert_eq!(V4::name(), V4::default().as_ref());
+    assert_eq!(Local::name(), Local::default().as_ref());
+  }
+
+  #[test]
+  fn test_default_names() {
+    #[derive(Default)]
+    struct Custom;
+    impl Display for Custom {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "custom")
+      }
+    }
+    impl AsRef<str> for Custom {
+      fn as_ref(&self) -> &str {
+        "custom"
+      }
+    }
+    impl PurposeTrait for Custom {}
+    impl VersionTrait for Custom {}
+
+    assert_eq!(<Custom as PurposeTrait>::name(), "Custom");
+    assert_eq!(<Custom as VersionTrait>::name(), "Custom");
+    assert_eq!(Custom::LOCAL_NONCE_LEN + Custom::LOCAL_TAG_LEN + Custom::PUBLIC_SIG_LEN, 0);
+  }
+
+  #[test]
+  fn test_version_lengths() {
+    assert_eq!(V4::LOCAL_KEY_LEN, 32);
+    assert_eq!(V4::LOCAL_NONCE_LEN, 32);
+    assert_eq!(V4::LOCAL_TAG_LEN, 32);
+    assert_eq!(V4::PUBLIC_SIG_LEN, 64);
   }
 }
@@ -36,7 +36,18 @@ impl AsRef<str> for V1 {
   }
 }
 impl V1orV3 for V1 {}
-impl VersionTrait for V1 {}
+impl VersionTrait for V1 {
+  const LOCAL_KEY_LEN: usize = 32;
+  const LOCAL_NONCE_LEN: usize = 32;
+  // HMAC-SHA384
+  const LOCAL_TAG_LEN: usize = 48;
+  // RSASSA-PSS with a 2048 bit modulus
+  const PUBLIC_SIG_LEN: usize = 256;
+
+  fn name() -> &'static str {
+    "v1"
+  }
+}
 impl Default for V1 {
   fn default() -> Self {
     Self("v1")
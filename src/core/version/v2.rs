@@ -26,7 +26,18 @@ use std::fmt::Display;
 ///     * See `verify()` for signature verification
 #[derive(Debug, Clone, Copy)]
 pub struct V2(&'static str);
-impl VersionTrait for V2 {}
+impl VersionTrait for V2 {
+  const LOCAL_KEY_LEN: usize = 32;
+  const LOCAL_NONCE_LEN: usize = 24;
+  // Poly1305
+  const LOCAL_TAG_LEN: usize = 16;
+  // Ed25519
+  const PUBLIC_SIG_LEN: usize = 64;
+
+  fn name() -> &'static str {
+    "v2"
+  }
+}
 impl AsRef<str> for V2 {
   fn as_ref(&self) -> &str {
     self.0
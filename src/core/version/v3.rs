@@ -28,7 +28,18 @@ use std::fmt::Display;
 ///       `v3.public` tokens provide Exclusive Ownership.
 #[derive(Debug, Clone, Copy)]
 pub struct V3(&'static str);
-impl VersionTrait for V3 {}
+impl VersionTrait for V3 {
+  const LOCAL_KEY_LEN: usize = 32;
+  const LOCAL_NONCE_LEN: usize = 32;
+  // HMAC-SHA384
+  const LOCAL_TAG_LEN: usize = 48;
+  // ECDSA over P-384
+  const PUBLIC_SIG_LEN: usize = 96;
+
+  fn name() -> &'static str {
+    "v3"
+  }
+}
 impl AsRef<str> for V3 {
   fn as_ref(&self) -> &str {
     self.0
@@ -24,7 +24,18 @@ use std::fmt::Display;
 ///     * Verifying: `sodium_crypto_sign_verify_detached()`
 #[derive(Debug, Clone, Copy)]
 pub struct V4(&'static str);
-impl VersionTrait for V4 {}
+impl VersionTrait for V4 {
+  const LOCAL_KEY_LEN: usize = 32;
+  const LOCAL_NONCE_LEN: usize = 32;
+  // BLAKE2b-MAC
+  const LOCAL_TAG_LEN: usize = 32;
+  // Ed25519
+  const PUBLIC_SIG_LEN: usize = 64;
+
+  fn name() -> &'static str {
+    "v4"
+  }
+}
 impl ImplicitAssertionCapable for V4 {}
 impl V2orV4 for V4 {}
 impl AsRef<str> for V4 {
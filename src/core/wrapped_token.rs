@@ -0,0 +1,151 @@
+use super::*;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A token read back from text which was split across several lines, e.g. to fit it into chunked
+/// QR codes or fixed width output.
+///
+/// [encode_wrapped](WrappedToken::encode_wrapped) inserts a `\n` every `line_len` characters and
+/// parsing a [WrappedToken] strips them again, leaving the original, spec compatible token. Tokens
+/// only contain URL-safe characters, so the line breaks are the only whitespace a wrapped token
+/// can hold.
+///
+/// # Usage
+/// ```
+/// # #[cfg(feature = "v4_local")]
+/// # {
+/// # use rusty_paseto::core::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+/// # let nonce = Key::<32>::try_new_random()?;
+/// # let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+/// let token = Paseto::<V4, Local>::builder()
+///   .set_payload(Payload::from("a message for a QR code"))
+///   .try_encrypt(&key, &nonce)?;
+///
+/// let wrapped = WrappedToken::encode_wrapped(&token, 64);
+/// assert!(wrapped.lines().all(|line| line.len() <= 64));
+///
+/// let unwrapped = wrapped.parse::<WrappedToken>()?;
+/// assert_eq!(unwrapped.as_str(), token);
+/// let payload = Paseto::<V4, Local>::try_decrypt(&unwrapped, &key, None, None)?;
+/// assert_eq!(payload, "a message for a QR code");
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedToken(String);
+
+impl WrappedToken {
+  /// Inserts a `\n` after every `line_len` characters of `token`. A `line_len` of zero leaves the
+  /// token on a single line.
+  pub fn encode_wrapped(token: &str, line_len: usize) -> String {
+    if line_len == 0 {
+      return token.to_string();
+    }
+    let mut wrapped = String::with_capacity(token.len() + token.len() / line_len);
+    for (i, c) in token.chars().enumerate() {
+      if i > 0 && i % line_len == 0 {
+        wrapped.push('\n');
+      }
+      wrapped.push(c);
+    }
+    wrapped
+  }
+
+  /// The unwrapped token
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  /// Consumes the [WrappedToken], returning the unwrapped token
+  pub fn into_inner(self) -> String {
+    self.0
+  }
+}
+
+impl FromStr for WrappedToken {
+  type Err = PasetoError;
+
+  /// Strips the line breaks, including `\r\n` line endings, from a wrapped token.
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoError::MalformedToken] if the token holds any other whitespace, which can't
+  /// have come from wrapping it.
+  fn from_str(wrapped: &str) -> Result<Self, Self::Err> {
+    let token = wrapped
+      .trim_end()
+      .chars()
+      .filter(|c| *c != '\n' && *c != '\r')
+      .collect::<String>();
+    if token.chars().any(char::is_whitespace) {
+      return Err(PasetoError::MalformedToken {
+        reason: "the wrapped token contains whitespace other than line breaks",
+      });
+    }
+    Ok(Self(token))
+  }
+}
+
+impl Deref for WrappedToken {
+  type Target = str;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl AsRef<str> for WrappedToken {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl fmt::Display for WrappedToken {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+
+  #[test]
+  fn test_wrapped_token_round_trip() {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub");
+    let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+    let token = Paseto::<V4, Local>::builder()
+      .set_payload(Payload::from("a message for a QR code"))
+      .set_footer(Footer::from("some footer"))
+      .try_encrypt(&key, &nonce)
+      .unwrap();
+
+    let wrapped = WrappedToken::encode_wrapped(&token, 64);
+    let lines = wrapped.lines().collect::<Vec<_>>();
+    assert!(lines.len() > 1);
+    assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 64));
+    assert!(lines[lines.len() - 1].len() <= 64);
+
+    let unwrapped = wrapped.parse::<WrappedToken>().unwrap();
+    assert_eq!(unwrapped.as_str(), token);
+    let payload = Paseto::<V4, Local>::try_decrypt(&unwrapped, &key, Footer::from("some footer"), None).unwrap();
+    assert_eq!(payload, "a message for a QR code");
+
+    //line endings from other platforms and a trailing newline are stripped too
+    let crlf = format!("{}\r\n", wrapped.replace('\n', "\r\n"));
+    assert_eq!(crlf.parse::<WrappedToken>().unwrap(), unwrapped);
+  }
+
+  #[test]
+  fn test_wrapped_token_edge_cases() {
+    assert_eq!(WrappedToken::encode_wrapped("v4.local.abc", 0), "v4.local.abc");
+    assert_eq!(WrappedToken::encode_wrapped("v4.local.abc", 3), "v4.\nloc\nal.\nabc");
+    assert!(matches!(
+      "v4.local.a bc".parse::<WrappedToken>(),
+      Err(PasetoError::MalformedToken { .. })
+    ));
+  }
+}
@@ -1,7 +1,9 @@
+use crate::core::ErrorKind;
 use crate::generic::PasetoClaimError;
 use thiserror::Error;
 /// Errors raised by the generic builder when adding claims or encrypting or signing PASETO tokens.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum GenericBuilderError {
   /// A generic claim error
   #[error(transparent)]
@@ -15,6 +17,12 @@ pub enum GenericBuilderError {
   ///An error indicating a duplicate top level claim in the token
   #[error("The claim '{0}' appears more than once in the top level payload json")]
   DuplicateTopLevelPayloadClaim(String),
+  ///An error indicating the token expiration is further out than an expiration policy allows
+  #[error("The token expiration exceeds the maximum time-to-live of {0}")]
+  ExceedsMaxTtl(time::Duration),
+  ///An error indicating a builder requiring an expiration was built without an `exp` claim
+  #[error("The token has no expiration claim but one is required")]
+  MissingExpiration,
   ///A generic cipher error
   #[error("A paseto cipher error occurred")]
   CipherError {
@@ -28,3 +36,18 @@ pub enum GenericBuilderError {
     source: serde_json::Error,
   },
 }
+
+impl GenericBuilderError {
+  /// The coarse [ErrorKind] of this error
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      GenericBuilderError::ClaimError { source } => source.kind(),
+      GenericBuilderError::CipherError { source } => source.kind(),
+      GenericBuilderError::BadEmailAddress(_)
+      | GenericBuilderError::DuplicateTopLevelPayloadClaim(_)
+      | GenericBuilderError::ExceedsMaxTtl(_)
+      | GenericBuilderError::MissingExpiration
+      | GenericBuilderError::PayloadJsonError { .. } => ErrorKind::Configuration,
+    }
+  }
+}
@@ -65,8 +65,14 @@ pub struct GenericBuilder<'a, 'b, Version, Purpose> {
     version: PhantomData<Version>,
     purpose: PhantomData<Purpose>,
     claims: HashMap<String, Box<dyn erased_serde::Serialize + 'b>>,
+    claim_order: Vec<String>,
+    strict_claim_keys: bool,
+    empty_claim_key_set: bool,
+    require_expiration: bool,
     footer: Option<Footer<'a>>,
     implicit_assertion: Option<ImplicitAssertion<'a>>,
+    #[cfg(feature = "nonce_tracking")]
+    nonce_tracker: Option<&'a NonceTracker>,
 }
 
 impl<'a, 'b, Version, Purpose> GenericBuilder<'a, 'b, Version, Purpose> {
@@ -75,8 +81,14 @@ impl<'a, 'b, Version, Purpose> GenericBuilder<'a, 'b, Version, Purpose> {
             version: PhantomData::<Version>,
             purpose: PhantomData::<Purpose>,
             claims: HashMap::with_capacity(10),
+            claim_order: Vec::new(),
+            strict_claim_keys: false,
+            empty_claim_key_set: false,
+            require_expiration: false,
             footer: None,
             implicit_assertion: None,
+            #[cfg(feature = "nonce_tracking")]
+            nonce_tracker: None,
         }
     }
 
@@ -99,8 +111,9 @@ impl<'a, 'b, Version, Purpose> GenericBuilder<'a, 'b, Version, Purpose> {
     {
         let key = value.get_key().to_owned();
 
-        // Ignore empty keys
+        // Ignore empty keys, unless strict claim keys fail the build
         if key.is_empty() {
+            self.empty_claim_key_set = true;
             return self;
         }
 
@@ -127,6 +140,131 @@ impl<'a, 'b, Version, Purpose> GenericBuilder<'a, 'b, Version, Purpose> {
         self
     }
 
+    ///Fails the build with [PasetoClaimError::InvalidClaimKey] if a [claim](PasetoClaim) with an
+    ///empty key was set, rather than silently leaving the claim out of the payload
+    pub fn set_strict_claim_keys(&mut self, strict: bool) -> &mut Self {
+        self.strict_claim_keys = strict;
+        self
+    }
+
+    ///Fails the build with [GenericBuilderError::MissingExpiration] if no [expiration](ExpirationClaim)
+    ///claim was set, rather than building a token which never expires. The [prelude
+    ///builder](crate::prelude::PasetoBuilder) always sets one, so this is only needed when using the
+    ///generic builder directly.
+    pub fn require_expiration(&mut self) -> &mut Self {
+        self.require_expiration = true;
+        self
+    }
+
+    ///Adds a [claim](PasetoClaim) to the token builder when it is `Some`, and does nothing when it
+    ///is `None`, so optional claims don't break up a builder chain
+    pub fn set_claim_opt<T: 'b + PasetoClaim + erased_serde::Serialize>(&mut self, value: Option<T>) -> &mut Self
+        where
+            'b: 'a,
+    {
+        if let Some(value) = value {
+            self.set_claim(value);
+        }
+        self
+    }
+
+    ///Adds multiple [custom claims](CustomClaim) to the token builder at once
+    pub fn set_custom_claims<T, I>(&mut self, claims: I) -> &mut Self
+        where
+            T: 'b + serde::Serialize,
+            I: IntoIterator<Item = CustomClaim<T>>,
+            'b: 'a,
+    {
+        for claim in claims {
+            self.set_claim(claim);
+        }
+        self
+    }
+
+    ///Replaces all claims in the token builder with the entries of a JSON object, e.g. one
+    ///created with the `json!` macro.
+    ///
+    ///Reserved claim keys must hold values of the expected shape: [exp](ExpirationClaim),
+    ///[nbf](NotBeforeClaim) and [iat](IssuedAtClaim) must be RFC3339 date strings and the
+    ///remaining reserved claims must be strings. Keys are checked like those of a
+    ///[CustomClaim], and empty keys are handled like [set_claim](Self::set_claim) handles them.
+    ///
+    ///# Errors
+    ///
+    ///Returns [`GenericBuilderError`] if the value isn't a JSON object, a key contains a control
+    ///character or a reserved claim holds an unexpected value.  The existing claims are left
+    ///untouched on error.
+    pub fn set_payload_json(&mut self, value: Value) -> Result<&mut Self, GenericBuilderError> {
+        let claims = match value {
+            Value::Object(claims) => claims,
+            _ => return Err(PasetoClaimError::PayloadNotObject.into()),
+        };
+        validate_reserved_claims(&claims)?;
+
+        self.claims.clear();
+        self.claim_order.clear();
+        self.empty_claim_key_set = false;
+        self.insert_json_claims(claims);
+        Ok(self)
+    }
+
+    ///Adds the fields of a serializable struct as top level claims, alongside any claims already
+    ///set. The struct is serialized to a flat JSON object first, so fields marked
+    ///`#[serde(flatten)]` become top level claims rather than a nested object.
+    ///
+    ///Keys and reserved claim values are checked like those of [set_payload_json](Self::set_payload_json).
+    ///
+    ///# Errors
+    ///
+    ///Returns [`GenericBuilderError`] if the value doesn't serialize to a JSON object, a key contains
+    ///a control character or a reserved claim holds an unexpected value.  The existing claims are
+    ///left untouched on error.
+    pub fn set_claims_from<T: serde::Serialize>(&mut self, claims: &T) -> Result<&mut Self, GenericBuilderError> {
+        let claims = match serde_json::to_value(claims)? {
+            Value::Object(claims) => claims,
+            _ => return Err(PasetoClaimError::PayloadNotObject.into()),
+        };
+        validate_reserved_claims(&claims)?;
+
+        self.insert_json_claims(claims);
+        Ok(self)
+    }
+
+    ///Adds claims which are written to the payload first and in exactly the given order, for
+    ///auditors requiring a specific claim order. Any other claims set on the builder follow them,
+    ///sorted by name as usual.
+    ///
+    ///Keys and reserved claim values are checked like those of [set_payload_json](Self::set_payload_json).
+    ///
+    ///# Errors
+    ///
+    ///Returns [`GenericBuilderError`] if a key appears more than once, contains a control character
+    ///or a reserved claim holds an unexpected value.  The existing claims are left untouched on error.
+    pub fn set_ordered_claims(&mut self, claims: &[(String, Value)]) -> Result<&mut Self, GenericBuilderError> {
+        let mut unique = Map::new();
+        for (key, value) in claims {
+            if unique.insert(key.clone(), value.clone()).is_some() {
+                return Err(GenericBuilderError::DuplicateTopLevelPayloadClaim(key.clone()));
+            }
+        }
+        validate_reserved_claims(&unique)?;
+
+        self.claim_order = claims.iter().map(|(key, _)| key.clone()).collect();
+        self.insert_json_claims(unique);
+        Ok(self)
+    }
+
+    // Inserts already validated claims, treating empty keys the same way set_claim does
+    fn insert_json_claims(&mut self, claims: Map<String, Value>) {
+        for (key, value) in claims {
+            if key.is_empty() {
+                self.empty_claim_key_set = true;
+                continue;
+            }
+            self.claims.insert(key, Box::new(value));
+        }
+    }
+
     ///Adds an optional [footer](Footer) to the token builder
     pub fn set_footer(&mut self, footer: Footer<'a>) -> &mut Self {
         self.footer = Some(footer);
@@ -151,11 +289,81 @@ impl<'a, 'b, Version, Purpose> GenericBuilder<'a, 'b, Version, Purpose> {
         // Wrap the serialized claims to ensure proper nesting
         let wrapped_claims = wrap_claims(serialized_claims);
 
-        // Convert the wrapped claims to a JSON string
-        serde_json::to_string(&wrapped_claims)
+        // Convert the wrapped claims to a JSON string, taking the claim order along with the claims
+        let claim_order = std::mem::take(&mut self.claim_order);
+        if claim_order.is_empty() {
+            serde_json::to_string(&wrapped_claims)
+        } else {
+            to_ordered_string(&claim_order, wrapped_claims)
+        }
     }
 }
 
+impl<Version, Purpose> GenericBuilder<'_, '_, Version, Purpose> {
+    // Builds the payload for encryption or signing, enforcing strict claim keys and a required
+    // expiration
+    fn checked_payload(&mut self) -> Result<String, GenericBuilderError> {
+        if self.strict_claim_keys && self.empty_claim_key_set {
+            return Err(PasetoClaimError::InvalidClaimKey(String::new()).into());
+        }
+        if self.require_expiration && !self.claims.contains_key("exp") {
+            return Err(GenericBuilderError::MissingExpiration);
+        }
+        Ok(self.build_payload_from_claims()?)
+    }
+}
+
+// Checks the keys of a JSON object are valid claim keys and its reserved claims hold values of the
+// expected shape
+fn validate_reserved_claims(claims: &Map<String, Value>) -> Result<(), GenericBuilderError> {
+    for (key, value) in claims {
+        check_claim_key(key)?;
+        match (key.as_str(), value) {
+            ("exp", Value::String(date)) => {
+                ExpirationClaim::try_from(date.as_str())?;
+            }
+            ("nbf", Value::String(date)) => {
+                NotBeforeClaim::try_from(date.as_str())?;
+            }
+            ("iat", Value::String(date)) => {
+                IssuedAtClaim::try_from(date.as_str())?;
+            }
+            ("iss" | "sub" | "aud" | "jti", Value::String(_)) => {}
+            ("exp" | "nbf" | "iat" | "iss" | "sub" | "aud" | "jti", _) => {
+                return Err(PasetoClaimError::Reserved(key.to_string()).into())
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Serializes a claims object with the keys in `order` first, followed by the remaining claims in
+// their usual sorted order
+fn to_ordered_string(order: &[String], claims: Value) -> Result<String, serde_json::Error> {
+    let mut claims = match claims {
+        Value::Object(claims) => claims,
+        other => return serde_json::to_string(&other),
+    };
+    let mut entries = order
+        .iter()
+        .filter_map(|key| claims.remove(key).map(|value| (key.clone(), value)))
+        .collect::<Vec<_>>();
+    entries.extend(claims);
+
+    let mut json = String::from("{");
+    for (index, (key, value)) in entries.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str(&serde_json::to_string(key)?);
+        json.push(':');
+        json.push_str(&serde_json::to_string(value)?);
+    }
+    json.push('}');
+    Ok(json)
+}
+
 // Wrap claims in an outer JSON object to ensure proper nesting
 //
 // # Parameters
@@ -221,6 +429,19 @@ impl<Version, Purpose> Default for GenericBuilder<'_, '_, Version, Purpose> {
     }
 }
 
+///Creates a builder from a JSON object of claims, e.g. the [Value] returned by a parser when
+///proxying a token's claims into a new token. See [set_payload_json](GenericBuilder::set_payload_json)
+///for the validation applied to reserved claims.
+impl<Version, Purpose> TryFrom<Value> for GenericBuilder<'_, '_, Version, Purpose> {
+    type Error = GenericBuilderError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut builder = Self::new();
+        builder.set_payload_json(value)?;
+        Ok(builder)
+    }
+}
+
 #[cfg(feature = "v1_local")]
 impl GenericBuilder<'_, '_, V1, Local> {
     /// Given a [PasetoSymmetricKey], attempts to encrypt a (V1, Local) PASETO token from the data and
@@ -282,7 +503,7 @@ impl GenericBuilder<'_, '_, V1, Local> {
     pub fn try_encrypt(&mut self, key: &PasetoSymmetricKey<V1, Local>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V1, Local>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
         if let Some(footer) = &self.footer {
             token_builder.set_footer(*footer);
@@ -353,7 +574,7 @@ impl GenericBuilder<'_, '_, V2, Local> {
     pub fn try_encrypt(&mut self, key: &PasetoSymmetricKey<V2, Local>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V2, Local>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
         if let Some(footer) = &self.footer {
             token_builder.set_footer(*footer);
@@ -427,7 +648,7 @@ impl GenericBuilder<'_, '_, V3, Local> {
     pub fn try_encrypt(&mut self, key: &PasetoSymmetricKey<V3, Local>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V3, Local>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
         if let Some(footer) = &self.footer {
             token_builder.set_footer(*footer);
@@ -442,7 +663,15 @@ impl GenericBuilder<'_, '_, V3, Local> {
 }
 
 #[cfg(feature = "v4_local")]
-impl GenericBuilder<'_, '_, V4, Local> {
+impl<'a> GenericBuilder<'a, '_, V4, Local> {
+    /// Records every nonce generated by this builder with a [NonceTracker], failing the build with
+    /// [PasetoError::NonceReuse] if a nonce repeats. Useful when auditing batch encryption.
+    #[cfg(feature = "nonce_tracking")]
+    pub fn set_nonce_tracker(&mut self, tracker: &'a NonceTracker) -> &mut Self {
+        self.nonce_tracker = Some(tracker);
+        self
+    }
+
     /// Given a [PasetoSymmetricKey], attempts to encrypt a (V4, Local) PASETO token from the data and
     /// claims provided to the GenericBuilder.
     ///
@@ -503,9 +732,57 @@ impl GenericBuilder<'_, '_, V4, Local> {
     ///   ```
 
     pub fn try_encrypt(&mut self, key: &PasetoSymmetricKey<V4, Local>) -> Result<String, GenericBuilderError> {
+        self.try_encrypt_with_meta(key).map(|(token, _)| token)
+    }
+
+    /// Given a [PasetoSymmetricKey], attempts to encrypt a (V4, Local) PASETO token like
+    /// [try_encrypt](Self::try_encrypt), also returning the randomly generated nonce for audit
+    /// logging or debugging. The nonce is still never chosen by the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenericBuilderError`] for any errors when building the token string
+    /// for encryption or during ciphertext encryption.
+    pub fn try_encrypt_with_meta(
+        &mut self,
+        key: &PasetoSymmetricKey<V4, Local>,
+    ) -> Result<(String, [u8; 32]), GenericBuilderError> {
+        let mut token_builder = Paseto::<V4, Local>::builder();
+
+        let payload = self.checked_payload()?;
+        token_builder.set_payload(Payload::from(payload.as_str()));
+
+        if let Some(footer) = &self.footer {
+            token_builder.set_footer(*footer);
+        }
+        if let Some(implicit_assertion) = &self.implicit_assertion {
+            token_builder.set_implicit_assertion(*implicit_assertion);
+        }
+        let nonce_key = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce_key);
+        #[cfg(feature = "nonce_tracking")]
+        if let Some(tracker) = self.nonce_tracker {
+            tracker.record(&nonce)?;
+        }
+        let token = token_builder.try_encrypt(key, &nonce)?;
+        Ok((token, *nonce_key))
+    }
+
+    /// Given a [PasetoSymmetricKey], attempts to encrypt a (V4, Local) PASETO token and write it
+    /// to `w`, avoiding an intermediate `String` when the token is headed for a response buffer or file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenericBuilderError`] for any errors when building the token string, during
+    /// ciphertext encryption or when writing to `w`.
+    pub fn try_encrypt_to<W: std::io::Write>(
+        &mut self,
+        key: &PasetoSymmetricKey<V4, Local>,
+        w: &mut W,
+    ) -> Result<(), GenericBuilderError> {
         let mut token_builder = Paseto::<V4, Local>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
 
         if let Some(footer) = &self.footer {
@@ -516,7 +793,11 @@ impl GenericBuilder<'_, '_, V4, Local> {
         }
         let nonce = Key::<32>::try_new_random()?;
         let nonce = PasetoNonce::<V4, Local>::from(&nonce);
-        Ok(token_builder.try_encrypt(key, &nonce)?)
+        #[cfg(feature = "nonce_tracking")]
+        if let Some(tracker) = self.nonce_tracker {
+            tracker.record(&nonce)?;
+        }
+        Ok(token_builder.try_encrypt_to(key, &nonce, w)?)
     }
 }
 
@@ -596,7 +877,7 @@ impl GenericBuilder<'_, '_, V1, Public> {
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V1, Public>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V1, Public>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
         if let Some(footer) = &self.footer {
             token_builder.set_footer(*footer);
@@ -679,7 +960,7 @@ impl GenericBuilder<'_, '_, V2, Public> {
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V2, Public>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V2, Public>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
         if let Some(footer) = &self.footer {
             token_builder.set_footer(*footer);
@@ -770,7 +1051,7 @@ impl GenericBuilder<'_, '_, V3, Public> {
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V3, Public>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V3, Public>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
 
         if let Some(footer) = &self.footer {
@@ -864,7 +1145,7 @@ impl GenericBuilder<'_, '_, V4, Public> {
     pub fn try_sign(&mut self, key: &PasetoAsymmetricPrivateKey<V4, Public>) -> Result<String, GenericBuilderError> {
         let mut token_builder = Paseto::<V4, Public>::builder();
 
-        let payload = self.build_payload_from_claims()?;
+        let payload = self.checked_payload()?;
         token_builder.set_payload(Payload::from(payload.as_str()));
 
         if let Some(footer) = &self.footer {
@@ -1327,6 +1608,420 @@ mod tests {
         assert_eq!(json["boolean"], true);
         assert_eq!(json["null"], Value::Null);
     }
+
+    #[test]
+    fn test_set_custom_claims() {
+        let mut builder = GenericBuilder::<V4, Local>::default();
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        // Create the custom claims elsewhere and add them all at once
+        let claims = vec![
+            CustomClaim::try_from(("claim1", "value1")).unwrap(),
+            CustomClaim::try_from(("claim2", "value2")).unwrap(),
+            CustomClaim::try_from(("claim3", "value3")).unwrap(),
+        ];
+        builder.set_custom_claims(claims);
+
+        // Build the token
+        let token = builder.try_encrypt(&key).unwrap();
+
+        // Decrypt the token and verify the values
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["claim1"], "value1");
+        assert_eq!(json["claim2"], "value2");
+        assert_eq!(json["claim3"], "value3");
+    }
+
+    #[test]
+    fn test_set_payload_json() {
+        let mut builder = GenericBuilder::<V4, Local>::default();
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        // Existing claims are replaced by the json object
+        builder.set_claim(CustomClaim::try_from(("replaced", true)).unwrap());
+        builder
+            .set_payload_json(serde_json::json!({
+                "sub": "loyal subjects",
+                "exp": "2019-01-01T00:00:00+00:00",
+                "data": "this is a secret message",
+                "seats": 4
+            }))
+            .unwrap();
+
+        // Build the token
+        let token = builder.try_encrypt(&key).unwrap();
+
+        // Decrypt the token and verify the values
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["sub"], "loyal subjects");
+        assert_eq!(json["exp"], "2019-01-01T00:00:00+00:00");
+        assert_eq!(json["data"], "this is a secret message");
+        assert_eq!(json["seats"], 4);
+        assert_eq!(json["replaced"], Value::Null);
+    }
+
+    #[test]
+    fn test_set_payload_json_rejects_invalid_values() {
+        let mut builder = GenericBuilder::<V4, Local>::default();
+
+        // Non-object values
+        assert!(builder.set_payload_json(serde_json::json!(["not", "an", "object"])).is_err());
+        // Reserved claims with unexpected values
+        assert!(builder.set_payload_json(serde_json::json!({"exp": "tomorrow"})).is_err());
+        assert!(builder.set_payload_json(serde_json::json!({"sub": 4})).is_err());
+    }
+
+    #[test]
+    fn test_owned_string_claims() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let mut builder = GenericBuilder::<V4, Local>::default();
+
+        {
+            //these values only live as long as this scope, e.g. a row loaded from a database
+            let row = vec![
+                String::from("customers"),
+                String::from("loyal subjects"),
+                String::from("me"),
+                String::from("token-1"),
+            ];
+            let mut row = row.into_iter();
+            builder
+                .set_claim(AudienceClaim::from(row.next().unwrap()))
+                .set_claim(SubjectClaim::from(row.next().unwrap()))
+                .set_claim(IssuerClaim::from(row.next().unwrap()))
+                .set_claim(TokenIdentifierClaim::from(row.next().unwrap()));
+        }
+
+        let token = builder.try_encrypt(&key).unwrap();
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+
+        assert_eq!(json["aud"], "customers");
+        assert_eq!(json["sub"], "loyal subjects");
+        assert_eq!(json["iss"], "me");
+        assert_eq!(json["jti"], "token-1");
+    }
+
+    #[test]
+    fn test_try_encrypt_to_writer() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let footer = Footer::from("some footer");
+        let mut buffer = Vec::new();
+        GenericBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("written"))
+            .set_footer(footer)
+            .try_encrypt_to(&key, &mut buffer)
+            .unwrap();
+        let written = String::from_utf8(buffer).unwrap();
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("written"))
+            .set_footer(footer)
+            .try_encrypt(&key)
+            .unwrap();
+
+        //nonces differ between calls, but the framing is identical
+        assert_eq!(written.len(), token.len());
+        assert_eq!(written.rsplit_once('.').unwrap().1, token.rsplit_once('.').unwrap().1);
+        let json = GenericParser::<V4, Local>::default()
+            .set_footer(footer)
+            .parse(&written, &key)
+            .unwrap();
+        assert_eq!(json["sub"], "written");
+    }
+
+    #[test]
+    fn test_large_integer_custom_claims_keep_precision() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        //2^53 + 1 is the first integer an f64 can't represent exactly
+        let count = 9_007_199_254_740_993u64;
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(CustomClaim::try_from(("count", count)).unwrap())
+            .set_claim(CustomClaim::try_from(("max", u64::MAX)).unwrap())
+            .set_claim(CustomClaim::try_from(("min", i64::MIN)).unwrap())
+            .try_encrypt(&key)
+            .unwrap();
+
+        //the payload holds JSON integers rather than floats
+        let payload = Paseto::<V4, Local>::try_decrypt(&token, &key, None, None).unwrap();
+        assert!(payload.contains("\"count\":9007199254740993"));
+        assert!(payload.contains("\"max\":18446744073709551615"));
+
+        let json = GenericParser::<V4, Local>::default()
+            .check_claim(CustomClaim::try_from(("count", count)).unwrap())
+            .parse(&token, &key)
+            .unwrap();
+        assert_eq!(json["count"].as_u64(), Some(count));
+        assert_eq!(json["max"].as_u64(), Some(u64::MAX));
+        assert_eq!(json["min"].as_i64(), Some(i64::MIN));
+
+        //a claim one off is still told apart
+        assert!(GenericParser::<V4, Local>::default()
+            .check_claim(CustomClaim::try_from(("count", count - 1)).unwrap())
+            .parse(&token, &key)
+            .is_err());
+    }
+
+    #[test]
+    fn test_try_from_parsed_value() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("proxied"))
+            .set_claim(ExpirationClaim::try_from("2099-01-01T00:00:00+00:00").unwrap())
+            .set_claim(CustomClaim::try_from(("seats", 4)).unwrap())
+            .try_encrypt(&key)
+            .unwrap();
+        let claims = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+
+        //re-mint the same claims under a different key
+        let proxy_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from([7u8; 32]));
+        let proxied = GenericBuilder::<V4, Local>::try_from(claims.clone())
+            .unwrap()
+            .try_encrypt(&proxy_key)
+            .unwrap();
+        let proxied_claims = GenericParser::<V4, Local>::default().parse(&proxied, &proxy_key).unwrap();
+        assert_eq!(proxied_claims, claims);
+
+        //only objects with well formed reserved claims are accepted
+        assert!(GenericBuilder::<V4, Local>::try_from(Value::from("not an object")).is_err());
+        assert!(GenericBuilder::<V4, Local>::try_from(serde_json::json!({"exp": "tomorrow"})).is_err());
+    }
+
+    #[test]
+    fn test_set_claims_from_flattens_nested_structs() {
+        #[derive(Serialize)]
+        struct Metadata {
+            tenant: String,
+            roles: Vec<&'static str>,
+        }
+
+        #[derive(Serialize)]
+        struct Claims {
+            sub: &'static str,
+            #[serde(flatten)]
+            metadata: Metadata,
+        }
+
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let claims = Claims {
+            sub: "loyal subjects",
+            metadata: Metadata {
+                tenant: "acme".to_string(),
+                roles: vec!["admin", "auditor"],
+            },
+        };
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(CustomClaim::try_from(("seats", 4)).unwrap())
+            .set_claims_from(&claims)
+            .unwrap()
+            .try_encrypt(&key)
+            .unwrap();
+
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["sub"], "loyal subjects");
+        assert_eq!(json["tenant"], "acme");
+        assert_eq!(json["roles"], serde_json::json!(["admin", "auditor"]));
+        assert_eq!(json["seats"], 4);
+        assert!(json.get("metadata").is_none());
+
+        //reserved claims are still validated
+        assert!(GenericBuilder::<V4, Local>::default()
+            .set_claims_from(&serde_json::json!({"exp": "tomorrow"}))
+            .is_err());
+        assert!(GenericBuilder::<V4, Local>::default().set_claims_from(&4).is_err());
+    }
+
+    #[test]
+    fn test_set_ordered_claims_preserves_order() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let ordered = vec![
+            ("sub".to_string(), Value::from("loyal subjects")),
+            ("exp".to_string(), Value::from("2099-01-01T00:00:00+00:00")),
+            ("aud".to_string(), Value::from("customers")),
+            ("data".to_string(), serde_json::json!({"z": 1, "a": 2})),
+        ];
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(CustomClaim::try_from(("seats", 4)).unwrap())
+            .set_claim(IssuerClaim::from("me"))
+            .set_ordered_claims(&ordered)
+            .unwrap()
+            .try_encrypt(&key)
+            .unwrap();
+
+        //the raw payload lists the ordered claims first, then the remaining claims by name
+        let payload = Paseto::<V4, Local>::try_decrypt(&token, &key, None, None).unwrap();
+        let keys = ["\"sub\"", "\"exp\"", "\"aud\"", "\"data\"", "\"iss\"", "\"seats\""]
+            .map(|key| payload.find(key).unwrap());
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "unexpected order in {}", payload);
+
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["data"]["z"], 1);
+        assert_eq!(json["seats"], 4);
+
+        //a replaced or already built payload doesn't keep the earlier order
+        let mut builder = GenericBuilder::<V4, Local>::default();
+        builder.set_ordered_claims(&ordered).unwrap();
+        builder.set_payload_json(serde_json::json!({"sub": "a", "aud": "b"})).unwrap();
+        assert_eq!(builder.build_payload_from_claims().unwrap(), r#"{"aud":"b","sub":"a"}"#);
+        builder.set_ordered_claims(&ordered).unwrap();
+        builder.build_payload_from_claims().unwrap();
+        builder.set_claim(SubjectClaim::from("a")).set_claim(AudienceClaim::from("b"));
+        assert_eq!(builder.build_payload_from_claims().unwrap(), r#"{"aud":"b","sub":"a"}"#);
+
+        //duplicate keys and malformed reserved claims are rejected
+        let duplicated = vec![("sub".to_string(), Value::from("a")), ("sub".to_string(), Value::from("b"))];
+        assert!(GenericBuilder::<V4, Local>::default().set_ordered_claims(&duplicated).is_err());
+        let malformed = vec![("exp".to_string(), Value::from("tomorrow"))];
+        assert!(GenericBuilder::<V4, Local>::default().set_ordered_claims(&malformed).is_err());
+    }
+
+    #[test]
+    fn test_json_claim_setters_check_keys() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let is_nul_key = |result: Result<&mut GenericBuilder<V4, Local>, GenericBuilderError>| {
+            matches!(
+                result,
+                Err(GenericBuilderError::ClaimError {
+                    source: PasetoClaimError::InvalidClaimKey(ref key)
+                }) if key == "nul\0key"
+            )
+        };
+
+        let mut builder = GenericBuilder::<V4, Local>::default();
+        assert!(is_nul_key(builder.set_payload_json(serde_json::json!({"nul\0key": 1}))));
+        assert!(is_nul_key(builder.set_claims_from(&serde_json::json!({"nul\0key": 1}))));
+        assert!(is_nul_key(builder.set_ordered_claims(&[("nul\0key".to_string(), 1.into())])));
+
+        //empty keys are left out of the payload, unless strict claim keys fail the build
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_payload_json(serde_json::json!({"": 1, "seats": 4}))
+            .unwrap()
+            .try_encrypt(&key)
+            .unwrap();
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json, serde_json::json!({"seats": 4}));
+
+        let error = GenericBuilder::<V4, Local>::default()
+            .set_strict_claim_keys(true)
+            .set_ordered_claims(&[("".to_string(), 1.into())])
+            .unwrap()
+            .try_encrypt(&key)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            GenericBuilderError::ClaimError {
+                source: PasetoClaimError::InvalidClaimKey(ref key)
+            } if key.is_empty()
+        ));
+
+        //replacing the payload also discards an empty key set earlier
+        GenericBuilder::<V4, Local>::default()
+            .set_strict_claim_keys(true)
+            .set_claim(CustomClaim::try_from(("", 1)).unwrap())
+            .set_payload_json(serde_json::json!({"seats": 4}))
+            .unwrap()
+            .try_encrypt(&key)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_strict_claim_keys_reject_empty_keys() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        //by default a claim with an empty key is left out of the payload
+        let token = GenericBuilder::<V4, Local>::default()
+            .set_claim(CustomClaim::try_from(("", 1)).unwrap())
+            .set_claim(CustomClaim::try_from(("seats", 4)).unwrap())
+            .try_encrypt(&key)
+            .unwrap();
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json, serde_json::json!({"seats": 4}));
+
+        let error = GenericBuilder::<V4, Local>::default()
+            .set_strict_claim_keys(true)
+            .set_claim(CustomClaim::try_from(("", 1)).unwrap())
+            .try_encrypt(&key)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            GenericBuilderError::ClaimError {
+                source: PasetoClaimError::InvalidClaimKey(ref key)
+            } if key.is_empty()
+        ));
+
+        //strict keys don't affect builders without empty keys
+        GenericBuilder::<V4, Local>::default()
+            .set_strict_claim_keys(true)
+            .set_claim(CustomClaim::try_from(("seats", 4)).unwrap())
+            .try_encrypt(&key)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_require_expiration() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        let token = GenericBuilder::<V4, Local>::default()
+            .require_expiration()
+            .set_claim(ExpirationClaim::try_from("2019-01-01T00:00:00+00:00").unwrap())
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .try_encrypt(&key)
+            .unwrap();
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["exp"], "2019-01-01T00:00:00+00:00");
+
+        let error = GenericBuilder::<V4, Local>::default()
+            .require_expiration()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .try_encrypt(&key)
+            .unwrap_err();
+        assert!(matches!(error, GenericBuilderError::MissingExpiration));
+
+        //without the requirement a token may still be built without an expiration
+        GenericBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .try_encrypt(&key)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_try_encrypt_with_meta_returns_nonce() {
+        use base64::prelude::*;
+
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let (token, nonce) = GenericBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("audited"))
+            .try_encrypt_with_meta(&key)
+            .unwrap();
+
+        //the nonce is the first 32 bytes of the decoded payload
+        let payload = BASE64_URL_SAFE_NO_PAD
+            .decode(token.trim_start_matches("v4.local."))
+            .unwrap();
+        assert_eq!(payload[..32], nonce);
+        let json = GenericParser::<V4, Local>::default().parse(&token, &key).unwrap();
+        assert_eq!(json["sub"], "audited");
+    }
+
+    #[cfg(feature = "nonce_tracking")]
+    #[test]
+    fn test_nonce_tracker_batch_encryption() {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let tracker = NonceTracker::new();
+
+        for _ in 0..100_000 {
+            GenericBuilder::<V4, Local>::default()
+                .set_nonce_tracker(&tracker)
+                .set_claim(SubjectClaim::from("batch"))
+                .try_encrypt(&key)
+                .unwrap();
+        }
+        assert_eq!(tracker.len(), 100_000);
+
+        //a repeated nonce is reported
+        let nonce = Key::<32>::try_new_random().unwrap();
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        tracker.record(&nonce).unwrap();
+        assert!(matches!(tracker.record(&nonce), Err(PasetoError::NonceReuse)));
+    }
 }
 
 #[cfg(all(test, feature = "v2_local"))]
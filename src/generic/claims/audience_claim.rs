@@ -3,9 +3,11 @@ use super::PasetoClaim;
 use serde::ser::SerializeMap;
 
 ///The reserved ['aud'](https://github.com/paseto-standard/paseto-spec/blob/master/docs/02-Implementation-Guide/04-Claims.md) PASETO claim
+///
+///Can also own its value, see [owned claim values](crate::generic#owned-claim-values)
 #[derive(Clone)]
-pub struct AudienceClaim<'a>((&'a str, &'a str));
-impl<'a> PasetoClaim for AudienceClaim<'a> {
+pub struct AudienceClaim<'a, V = &'a str>((&'a str, V));
+impl<'a, V: serde::Serialize> PasetoClaim for AudienceClaim<'a, V> {
   fn get_key(&self) -> &str {
     self.0 .0
   }
@@ -24,15 +26,22 @@ impl<'a> From<&'a str> for AudienceClaim<'a> {
   }
 }
 
+//owned values, e.g. loaded from a database, don't need to outlive the claim
+impl<'a> From<String> for AudienceClaim<'a, String> {
+  fn from(s: String) -> Self {
+    Self(("aud", s))
+  }
+}
+
 //want to receive a reference as a tuple
-impl<'a> AsRef<(&'a str, &'a str)> for AudienceClaim<'a> {
-  fn as_ref(&self) -> &(&'a str, &'a str) {
+impl<'a, V> AsRef<(&'a str, V)> for AudienceClaim<'a, V> {
+  fn as_ref(&self) -> &(&'a str, V) {
     &self.0
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'a> serde::Serialize for AudienceClaim<'a> {
+impl<'a, V: serde::Serialize> serde::Serialize for AudienceClaim<'a, V> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: serde::Serializer,
@@ -162,9 +162,10 @@ pub struct CustomClaim<T>((String, T));
 impl<T> CustomClaim<T> {
   //TODO: this needs to be refactored to be configurable for eventual compressed token
   //implementations
-  pub(self) const RESERVED_CLAIMS: [&'static str; 7] = ["iss", "sub", "aud", "exp", "nbf", "iat", "jti"];
+  pub(crate) const RESERVED_CLAIMS: [&'static str; 7] = ["iss", "sub", "aud", "exp", "nbf", "iat", "jti"];
 
   fn check_if_reserved_claim_key(key: &str) -> Result<(), PasetoClaimError> {
+    check_claim_key(key)?;
     match key {
       key if Self::RESERVED_CLAIMS.contains(&key) => Err(PasetoClaimError::Reserved(key.into())),
       _ => Ok(()),
@@ -172,6 +173,15 @@ impl<T> CustomClaim<T> {
   }
 }
 
+//NUL and other control characters can confuse downstream JSON consumers, so they're rejected in
+//claim keys however the claims are set
+pub(crate) fn check_claim_key(key: &str) -> Result<(), PasetoClaimError> {
+  match key.chars().any(char::is_control) {
+    true => Err(PasetoClaimError::InvalidClaimKey(key.into())),
+    false => Ok(()),
+  }
+}
+
 #[cfg(feature = "serde")]
 impl<T: serde::Serialize> PasetoClaim for CustomClaim<T> {
   fn get_key(&self) -> &str {
@@ -206,6 +216,48 @@ impl<T> TryFrom<(&str, T)> for CustomClaim<T> {
   }
 }
 
+/// Creates a custom claim from a [time::Duration], serialized as a whole number of seconds
+///
+/// The claim's value type has to be named, as the conversion from any `(&str, T)` would otherwise
+/// also apply and keep the [time::Duration] itself.
+///
+/// ```
+/// # use rusty_paseto::generic::*;
+/// let claim = CustomClaim::<i64>::try_from(("session_ttl", time::Duration::minutes(15)))?;
+/// assert_eq!(claim.as_ref().1, 900);
+/// # Ok::<(),PasetoClaimError>(())
+/// ```
+impl TryFrom<(&str, time::Duration)> for CustomClaim<i64> {
+  type Error = PasetoClaimError;
+
+  fn try_from(val: (&str, time::Duration)) -> Result<Self, Self::Error> {
+    Self::try_from((val.0, val.1.whole_seconds()))
+  }
+}
+
+/// Creates a custom claim from a [time::OffsetDateTime], serialized as an RFC3339 string
+///
+/// The claim's value type has to be named, as the conversion from any `(&str, T)` would otherwise
+/// also apply and keep the [time::OffsetDateTime] itself.
+///
+/// ```
+/// # use rusty_paseto::generic::*;
+/// let claim = CustomClaim::<String>::try_from(("last_login", time::OffsetDateTime::UNIX_EPOCH))?;
+/// assert_eq!(claim.as_ref().1, "1970-01-01T00:00:00Z");
+/// # Ok::<(),PasetoClaimError>(())
+/// ```
+impl TryFrom<(&str, time::OffsetDateTime)> for CustomClaim<String> {
+  type Error = PasetoClaimError;
+
+  fn try_from(val: (&str, time::OffsetDateTime)) -> Result<Self, Self::Error> {
+    let value = val
+      .1
+      .format(&time::format_description::well_known::Rfc3339)
+      .map_err(|_| PasetoClaimError::RFC3339Date(val.1.to_string()))?;
+    Self::try_from((val.0, value))
+  }
+}
+
 //we want to receive a reference as a tuple
 impl<T> AsRef<(String, T)> for CustomClaim<T> {
   fn as_ref(&self) -> &(String, T) {
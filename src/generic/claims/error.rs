@@ -1,7 +1,9 @@
+use crate::core::ErrorKind;
 use thiserror::Error;
 
 /// Errors from validating claims in a parsed token
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum PasetoClaimError {
   /// Occurs during an attempt to parse an expired token
   #[error("This token is expired")]
@@ -24,10 +26,77 @@ pub enum PasetoClaimError {
   /// Occurs when a claim fails validation
   #[error("The claim '{0}' failed validation.  Expected '{1}' but received '{2}'")]
   Invalid(String, String, String),
+  /// Occurs when a claim is present but its JSON type differs from the type of the value it's checked against
+  #[error("The claim '{key}' should be a {expected} but is a {found}")]
+  TypeMismatch {
+    /// The key of the claim
+    key: String,
+    /// The JSON type of the value the claim was checked against
+    expected: &'static str,
+    /// The JSON type of the claim in the payload
+    found: &'static str,
+  },
   /// Occurs when a user attempts to create a custom claim using a reserved claim key
   #[error("The key {0} is a reserved for use within PASETO.  To set a reserved claim, use the strong type: e.g - ExpirationClaimClaim")]
   Reserved(String),
+  /// Occurs when a claim key contains control characters, or is empty and empty keys are rejected
+  #[error("The claim key {0:?} is not a valid claim key")]
+  InvalidClaimKey(String),
   /// Occurs when a user attempts to use a top level claim more than once in the payload
   #[error("The claim '{0}' appears more than once in the top level payload json")]
   DuplicateTopLevelPayloadClaim(String),
+  /// Occurs when claims are checked against a payload that is not a JSON object
+  #[error("The payload is not a JSON object so its claims cannot be validated")]
+  PayloadNotObject,
+  /// Occurs when duplicate keys are rejected and the payload contains the same top level key more than once
+  #[error("The key '{0}' appears more than once in the top level payload json")]
+  DuplicateKey(String),
+  /// Occurs when all claim errors are collected and one or more claims fail validation
+  #[error("{} claims failed validation", .0.len())]
+  Multiple(Vec<PasetoClaimError>),
+  /// Occurs when consistent time claims are required and the present claims violate `nbf <= iat <= exp`
+  #[error("The token's time claims are inconsistent, expected nbf <= iat <= exp")]
+  InconsistentTimeClaims,
+  /// Occurs when the claims don't conform to the JSON schema set on the parser
+  #[error("The claims do not conform to the schema: {0}")]
+  SchemaViolation(String),
+  /// Occurs when a maximum claim count is set on the parser and the payload has more top level claims
+  #[error("The payload has {0} top level claims, more than the maximum of {1}")]
+  TooManyClaims(usize, usize),
+  /// Occurs when a parser validates a time window and the current time falls outside of it
+  #[error("The token is only valid from {0} until {1}")]
+  OutsideWindow(String, String),
+  /// Occurs when the subject claim isn't one of the subjects a parser allows
+  #[error("The subject '{0}' is not one of the allowed subjects")]
+  SubjectNotAllowed(String),
+  /// Occurs when a JSON schema set on the parser cannot be compiled
+  #[error("The JSON schema is invalid: {0}")]
+  InvalidSchema(String),
+}
+
+impl PasetoClaimError {
+  /// The coarse [ErrorKind] of this error
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      PasetoClaimError::Reserved(_)
+      | PasetoClaimError::InvalidClaimKey(_)
+      | PasetoClaimError::DuplicateTopLevelPayloadClaim(_)
+      | PasetoClaimError::InvalidSchema(_) => ErrorKind::Configuration,
+      _ => ErrorKind::InvalidClaims,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl PasetoClaimError {
+  /// The HTTP status code a web service should respond with when a token fails with this error
+  ///
+  /// Claims which fail validation map to `401`, while errors from misconfiguring a builder or
+  /// parser map to `500`.
+  pub fn http_status(&self) -> u16 {
+    match self {
+      PasetoClaimError::Reserved(_) | PasetoClaimError::InvalidClaimKey(_) | PasetoClaimError::InvalidSchema(_) => 500,
+      _ => 401,
+    }
+  }
 }
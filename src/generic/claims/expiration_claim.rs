@@ -39,6 +39,15 @@ impl TryFrom<&str> for ExpirationClaim {
   }
 }
 
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for ExpirationClaim {
+  type Error = PasetoClaimError;
+
+  fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+    Self::try_from(value.to_rfc3339())
+  }
+}
+
 //want to receive a reference as a tuple
 impl AsRef<(String, String)> for ExpirationClaim {
   fn as_ref(&self) -> &(String, String) {
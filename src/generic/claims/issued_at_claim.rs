@@ -28,6 +28,15 @@ impl TryFrom<&str> for IssuedAtClaim {
   }
 }
 
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for IssuedAtClaim {
+  type Error = PasetoClaimError;
+
+  fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+    Self::try_from(value.to_rfc3339())
+  }
+}
+
 //want to receive a reference as a tuple
 impl AsRef<(String, String)> for IssuedAtClaim {
   fn as_ref(&self) -> &(String, String) {
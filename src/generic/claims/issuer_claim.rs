@@ -3,10 +3,12 @@ use super::PasetoClaim;
 use serde::ser::SerializeMap;
 
 ///The reserved ['iss'](https://github.com/paseto-standard/paseto-spec/blob/master/docs/02-Implementation-Guide/04-Claims.md) PASETO claim
+///
+///Can also own its value, see [owned claim values](crate::generic#owned-claim-values)
 #[derive(Clone)]
-pub struct IssuerClaim<'a>((&'a str, &'a str));
+pub struct IssuerClaim<'a, V = &'a str>((&'a str, V));
 
-impl<'a> PasetoClaim for IssuerClaim<'a> {
+impl<'a, V: serde::Serialize> PasetoClaim for IssuerClaim<'a, V> {
   fn get_key(&self) -> &str {
     self.0 .0
   }
@@ -25,21 +27,28 @@ impl<'a> From<&'a str> for IssuerClaim<'a> {
   }
 }
 
+//owned values, e.g. loaded from a database, don't need to outlive the claim
+impl<'a> From<String> for IssuerClaim<'a, String> {
+  fn from(s: String) -> Self {
+    Self(("iss", s))
+  }
+}
+
 //want to receive a reference as a tuple
-impl<'a> AsRef<(&'a str, &'a str)> for IssuerClaim<'a> {
-  fn as_ref(&self) -> &(&'a str, &'a str) {
+impl<'a, V> AsRef<(&'a str, V)> for IssuerClaim<'a, V> {
+  fn as_ref(&self) -> &(&'a str, V) {
     &self.0
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'a> serde::Serialize for IssuerClaim<'a> {
+impl<'a, V: serde::Serialize> serde::Serialize for IssuerClaim<'a, V> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: serde::Serializer,
   {
     let mut map = serializer.serialize_map(Some(2))?;
-    map.serialize_entry(self.0 .0, self.0 .1)?;
+    map.serialize_entry(self.0 .0, &self.0 .1)?;
     map.end()
   }
 }
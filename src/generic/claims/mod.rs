@@ -1,5 +1,5 @@
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 mod audience_claim;
 mod custom_claim;
@@ -14,6 +14,7 @@ mod traits;
 
 pub use audience_claim::AudienceClaim;
 pub use custom_claim::CustomClaim;
+pub(crate) use custom_claim::check_claim_key;
 pub use error::PasetoClaimError;
 pub use expiration_claim::ExpirationClaim;
 pub use issued_at_claim::IssuedAtClaim;
@@ -23,9 +24,34 @@ pub use subject_claim::SubjectClaim;
 pub use token_identifier_claim::TokenIdentifierClaim;
 pub use traits::PasetoClaim;
 ///A type for creating generic claim validation functions
-pub type ValidatorFn = dyn Fn(&str, &Value) -> Result<(), PasetoClaimError>;
+///
+///Validators must be `Send + Sync` so a configured parser can be shared between threads, e.g.
+///behind an [Arc](std::sync::Arc) used from several async worker threads.
+pub type ValidatorFn = dyn Fn(&str, &Value) -> Result<(), PasetoClaimError> + Send + Sync;
 ///A type for tracking claims in a token
 pub type ValidatorMap = HashMap<String, Box<ValidatorFn>>;
+///A type for post-processing the claims of a verified token
+///
+///Like [ValidatorFn], post-processors must be `Send + Sync`, so closures capturing an `Rc` or a
+///`RefCell` need to switch to an `Arc` and a `Mutex` or an atomic.
+pub type PostProcessorFn = dyn Fn(&mut Value) + Send + Sync;
+
+///Partitions the claims of a parsed token payload into PASETO reserved claims and custom claims,
+///returned as `(reserved, custom)`.  A payload which isn't a JSON object has no claims.
+pub fn split_claims(value: &Value) -> (BTreeMap<String, Value>, BTreeMap<String, Value>) {
+  let mut reserved = BTreeMap::new();
+  let mut custom = BTreeMap::new();
+  if let Some(claims) = value.as_object() {
+    for (key, value) in claims {
+      if CustomClaim::<Value>::RESERVED_CLAIMS.contains(&key.as_str()) {
+        reserved.insert(key.clone(), value.clone());
+      } else {
+        custom.insert(key.clone(), value.clone());
+      }
+    }
+  }
+  (reserved, custom)
+}
 
 #[cfg(test)]
 mod unit_tests {
@@ -111,6 +137,11 @@ mod unit_tests {
 
     //verify
     assert_eq!("sub", claim.get_key());
+    assert_eq!(claim.as_ref(), &("sub", "hello world"));
+
+    let owned = SubjectClaim::from(String::from("hello world"));
+    let (key, value) = owned.as_ref();
+    assert_eq!((*key, value.as_str()), ("sub", "hello world"));
   }
 
   #[test]
@@ -149,4 +180,91 @@ mod unit_tests {
     assert!(CustomClaim::try_from(("jti", 137)).is_err());
     assert!(CustomClaim::try_from(("i'm good tho", true)).is_ok());
   }
+
+  #[test]
+  fn test_control_character_custom_claim_key() {
+    assert!(matches!(
+      CustomClaim::try_from(("nul\0key", 137)),
+      Err(PasetoClaimError::InvalidClaimKey(key)) if key == "nul\0key"
+    ));
+    assert!(CustomClaim::try_from(("line\nbreak", 137)).is_err());
+    assert!(CustomClaim::try_from("\u{7f}").is_err());
+    assert!(CustomClaim::try_from((String::from("\x1b[31m"), 137)).is_err());
+    assert!(CustomClaim::<i64>::try_from(("tab\tkey", time::Duration::minutes(1))).is_err());
+    assert!(CustomClaim::try_from(("ünïcödé", 137)).is_ok());
+  }
+
+  #[test]
+  fn test_temporal_custom_claims() -> Result<()> {
+    let ttl = CustomClaim::<i64>::try_from(("session_ttl", time::Duration::minutes(15)))?;
+    assert_eq!(ttl.get_key(), "session_ttl");
+    assert_eq!(serde_json::to_value(&ttl)?, serde_json::json!({"session_ttl": 900}));
+
+    let last_login = time::OffsetDateTime::now_utc().replace_nanosecond(0)?;
+    let claim = CustomClaim::<String>::try_from(("last_login", last_login))?;
+    let json = serde_json::to_value(&claim)?;
+    let round_tripped = time::OffsetDateTime::parse(json["last_login"].as_str().unwrap(), &Rfc3339)?;
+    assert_eq!(round_tripped, last_login);
+
+    //reserved keys are still rejected
+    assert!(CustomClaim::<i64>::try_from(("exp", time::Duration::minutes(15))).is_err());
+    assert!(CustomClaim::<String>::try_from(("iat", last_login)).is_err());
+    Ok(())
+  }
+
+  #[cfg(feature = "v4_local")]
+  #[test]
+  fn test_split_claims() -> Result<()> {
+    use crate::generic::*;
+
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_claim(IssuerClaim::from("me"))
+      .set_claim(CustomClaim::try_from(("data", "this is a secret message"))?)
+      .set_claim(CustomClaim::try_from(("seats", 4))?)
+      .try_encrypt(&key)?;
+    let json = GenericParser::<V4, Local>::default().parse(&token, &key)?;
+
+    let (reserved, custom) = split_claims(&json);
+    assert_eq!(reserved.keys().collect::<Vec<_>>(), vec!["iss", "sub"]);
+    assert_eq!(reserved["sub"], "loyal subjects");
+    assert_eq!(custom.keys().collect::<Vec<_>>(), vec!["data", "seats"]);
+    assert_eq!(custom["seats"], 4);
+
+    //non-object payloads have no claims
+    let (reserved, custom) = split_claims(&Value::from(vec![1, 2, 3]));
+    assert!(reserved.is_empty() && custom.is_empty());
+    Ok(())
+  }
+
+  #[cfg(all(feature = "chrono", feature = "batteries_included", feature = "v4_local"))]
+  #[test]
+  fn test_chrono_time_claims() -> Result<()> {
+    use crate::prelude::*;
+    use chrono::{DateTime, Duration};
+
+    let now = DateTime::from_timestamp(time::OffsetDateTime::now_utc().unix_timestamp(), 0).unwrap();
+    let expiration = ExpirationClaim::try_from(now + Duration::hours(1))?;
+    let issued_at = IssuedAtClaim::try_from(now)?;
+    let not_before = NotBeforeClaim::try_from(now - Duration::minutes(1))?;
+    assert_eq!(expiration.as_ref().1, (now + Duration::hours(1)).to_rfc3339());
+
+    //the claims should be accepted by the default time claim validation
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(expiration)
+      .set_claim(issued_at)
+      .set_claim(not_before)
+      .build(&key)?;
+    let json = PasetoParser::<V4, Local>::default().parse(&token, &key)?;
+    assert_eq!(json["iat"], now.to_rfc3339());
+
+    //and an expired chrono datetime should be rejected
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(ExpirationClaim::try_from(now - Duration::hours(1))?)
+      .build(&key)?;
+    assert!(PasetoParser::<V4, Local>::default().parse(&token, &key).is_err());
+    Ok(())
+  }
 }
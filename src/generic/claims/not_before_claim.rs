@@ -39,6 +39,15 @@ impl TryFrom<&str> for NotBeforeClaim {
   }
 }
 
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for NotBeforeClaim {
+  type Error = PasetoClaimError;
+
+  fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+    Self::try_from(value.to_rfc3339())
+  }
+}
+
 //want to receive a reference as a tuple
 impl AsRef<(String, String)> for NotBeforeClaim {
   fn as_ref(&self) -> &(String, String) {
@@ -3,9 +3,11 @@ use super::PasetoClaim;
 use serde::ser::SerializeMap;
 
 ///The reserved ['jti'](https://github.com/paseto-standard/paseto-spec/blob/master/docs/02-Implementation-Guide/04-Claims.md) PASETO claim
+///
+///Can also own its value, see [owned claim values](crate::generic#owned-claim-values)
 #[derive(Clone)]
-pub struct TokenIdentifierClaim<'a>((&'a str, &'a str));
-impl<'a> PasetoClaim for TokenIdentifierClaim<'a> {
+pub struct TokenIdentifierClaim<'a, V = &'a str>((&'a str, V));
+impl<'a, V: serde::Serialize> PasetoClaim for TokenIdentifierClaim<'a, V> {
   fn get_key(&self) -> &str {
     self.0 .0
   }
@@ -24,15 +26,22 @@ impl<'a> From<&'a str> for TokenIdentifierClaim<'a> {
   }
 }
 
+//owned values, e.g. loaded from a database, don't need to outlive the claim
+impl<'a> From<String> for TokenIdentifierClaim<'a, String> {
+  fn from(s: String) -> Self {
+    Self(("jti", s))
+  }
+}
+
 //want to receive a reference as a tuple
-impl<'a> AsRef<(&'a str, &'a str)> for TokenIdentifierClaim<'a> {
-  fn as_ref(&self) -> &(&'a str, &'a str) {
+impl<'a, V> AsRef<(&'a str, V)> for TokenIdentifierClaim<'a, V> {
+  fn as_ref(&self) -> &(&'a str, V) {
     &self.0
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'a> serde::Serialize for TokenIdentifierClaim<'a> {
+impl<'a, V: serde::Serialize> serde::Serialize for TokenIdentifierClaim<'a, V> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: serde::Serializer,
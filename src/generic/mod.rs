@@ -21,6 +21,10 @@
 //! # Registered Claims
 
 //! Refer to the [PASETO specification](https://github.com/paseto-standard/paseto-spec/blob/master/docs/02-Implementation-Guide/04-Claims.md) to review reserved claims for use within PASETO.
+//!
+//! ## Owned claim values
+//!
+//! The [AudienceClaim], [IssuerClaim], [SubjectClaim] and [TokenIdentifierClaim] borrow a `&str` by default.  Created from an owned `String` instead, e.g. a value loaded from a database, they keep the `String` as their value so the claim doesn't borrow from the scope the value was loaded in.
 mod builders;
 mod claims;
 mod parsers;
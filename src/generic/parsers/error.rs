@@ -1,8 +1,11 @@
+use crate::core::ErrorKind;
 use crate::generic::claims::PasetoClaimError;
+use super::ParseReport;
 use thiserror::Error;
 
 /// Errors raised by the generic parser when validating claims or parsing a PASETO token.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum GenericParserError {
   /// An error from the existence or non-existence or validation of a claim
   #[error(transparent)]
@@ -16,6 +19,15 @@ pub enum GenericParserError {
     #[from]
     source: crate::core::PasetoError,
   },
+  /// The claims of a token parsed with `parse_with_report` failed validation, reported alongside
+  /// a [ParseReport] describing the token
+  #[error("{source}")]
+  ValidationFailed {
+    /// The claim validation error
+    source: PasetoClaimError,
+    /// The report describing the token, with a failed outcome
+    report: Box<ParseReport>,
+  },
   /// A JSON deserialization error for the token payload
   #[error("The payload was unable to be serialized into json")]
   PayloadJsonError {
@@ -23,3 +35,56 @@ pub enum GenericParserError {
     source: serde_json::Error,
   },
 }
+
+impl GenericParserError {
+  /// The coarse [ErrorKind] of this error
+  ///
+  /// # Example usage
+  /// ```
+  /// # #[cfg(feature = "default")]
+  /// # {
+  /// # use rusty_paseto::prelude::*;
+  /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+  /// let error = PasetoParser::<V4, Local>::default().parse("v4.local.", &key).unwrap_err();
+  /// match error.kind() {
+  ///   ErrorKind::MalformedToken => {}
+  ///   _ => unreachable!(),
+  /// }
+  /// # }
+  /// ```
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      GenericParserError::ClaimError { source } | GenericParserError::ValidationFailed { source, .. } => source.kind(),
+      GenericParserError::CipherError { source } => source.kind(),
+      GenericParserError::PayloadJsonError { .. } => ErrorKind::MalformedToken,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl GenericParserError {
+  /// The HTTP status code a web service should respond with when a token fails to parse
+  ///
+  /// Malformed tokens map to `400`, failed decryption, verification or claim validation to `401`
+  /// and internal errors to `500`.
+  ///
+  /// # Example usage
+  /// ```
+  /// # #[cfg(all(feature = "default", feature = "http"))]
+  /// # {
+  /// # use rusty_paseto::prelude::*;
+  /// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+  /// let error = PasetoParser::<V4, Local>::default().parse("v4.local.", &key).unwrap_err();
+  /// assert_eq!(error.http_status(), 400);
+  /// # }
+  /// ```
+  pub fn http_status(&self) -> u16 {
+    match self {
+      GenericParserError::ClaimError { source } | GenericParserError::ValidationFailed { source, .. } => {
+        source.http_status()
+      }
+      GenericParserError::CipherError { source } => source.http_status(),
+      GenericParserError::PayloadJsonError { .. } => 400,
+    }
+  }
+}
@@ -0,0 +1,107 @@
+use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError};
+use serde_json::Value;
+
+/// Reads the key id from an untrusted token's JSON footer without a key, to pick the key the token
+/// should be parsed with when issuers name the field differently.
+///
+/// Each of `candidate_keys` is looked up in order and the first string value found is returned. A
+/// candidate starting with `/` is a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) into the
+/// footer, e.g. `/header/kid`, and any other candidate names a top level field.
+///
+/// Returns `None` when the token has no footer, the footer isn't a JSON object or none of the
+/// candidates hold a string. The footer is unauthenticated until the token has been parsed, so the
+/// key id must only be used to choose among trusted keys.
+///
+/// # Errors
+///
+/// Returns [PasetoError::MalformedToken] if the token doesn't have a header and payload, or a
+/// decoding error if the footer segment isn't valid base64 or UTF-8.
+///
+/// # Example usage
+/// ```
+/// # #[cfg(feature = "default")]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+/// let token = PasetoBuilder::<V4, Local>::default()
+///   .set_footer(Footer::from(r#"{"keyid":"key-2"}"#))
+///   .build(&key)?;
+///
+/// assert_eq!(peek_footer_kid(&token, &["kid", "keyid"])?.as_deref(), Some("key-2"));
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+pub fn peek_footer_kid(token: &str, candidate_keys: &[&str]) -> Result<Option<String>, PasetoError> {
+  let mut segments = token.split('.');
+  if segments.nth(2).is_none() {
+    return Err(PasetoError::MalformedToken {
+      reason: "the token is missing its header or payload",
+    });
+  }
+  match segments.next() {
+    Some(footer) if !footer.is_empty() => kid_from_footer(&DefaultBase64Codec::decode(footer)?, candidate_keys),
+    _ => Ok(None),
+  }
+}
+
+//looks up the key id in a decoded footer, which is only searched when it's a JSON object
+pub(super) fn kid_from_footer(footer: &[u8], candidate_keys: &[&str]) -> Result<Option<String>, PasetoError> {
+  let footer = match serde_json::from_str::<Value>(std::str::from_utf8(footer)?) {
+    Ok(footer @ Value::Object(_)) => footer,
+    _ => return Ok(None),
+  };
+
+  let kid = candidate_keys.iter().find_map(|candidate| {
+    let value = match candidate.starts_with('/') {
+      true => footer.pointer(candidate),
+      false => footer.get(*candidate),
+    };
+    value.and_then(Value::as_str)
+  });
+  Ok(kid.map(str::to_string))
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+  use crate::generic::*;
+
+  fn token(footer: Option<&str>) -> String {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let mut builder = GenericBuilder::<V4, Local>::default();
+    builder.set_claim(SubjectClaim::from("loyal subjects"));
+    if let Some(footer) = footer {
+      builder.set_footer(Footer::from(footer));
+    }
+    builder.try_encrypt(&key).unwrap()
+  }
+
+  #[test]
+  fn test_peek_footer_kid() {
+    let candidates = ["kid", "keyid", "/header/kid"];
+
+    let kid = peek_footer_kid(&token(Some(r#"{"kid":"key-1"}"#)), &candidates).unwrap();
+    assert_eq!(kid.as_deref(), Some("key-1"));
+    let keyid = peek_footer_kid(&token(Some(r#"{"keyid":"key-2"}"#)), &candidates).unwrap();
+    assert_eq!(keyid.as_deref(), Some("key-2"));
+    let nested = peek_footer_kid(&token(Some(r#"{"header":{"kid":"key-3"}}"#)), &candidates).unwrap();
+    assert_eq!(nested.as_deref(), Some("key-3"));
+
+    //earlier candidates win
+    let both = token(Some(r#"{"keyid":"key-2","kid":"key-1"}"#));
+    assert_eq!(peek_footer_kid(&both, &candidates).unwrap().as_deref(), Some("key-1"));
+    assert_eq!(peek_footer_kid(&both, &["keyid", "kid"]).unwrap().as_deref(), Some("key-2"));
+  }
+
+  #[test]
+  fn test_peek_footer_kid_absent() {
+    let candidates = ["kid", "keyid"];
+    assert_eq!(peek_footer_kid(&token(None), &candidates).unwrap(), None);
+    assert_eq!(peek_footer_kid(&token(Some(r#"{"iss":"me"}"#)), &candidates).unwrap(), None);
+    assert_eq!(peek_footer_kid(&token(Some(r#"{"kid":7}"#)), &candidates).unwrap(), None);
+    assert_eq!(peek_footer_kid(&token(Some("not json")), &candidates).unwrap(), None);
+
+    assert!(peek_footer_kid("v4.local", &candidates).is_err());
+    assert!(peek_footer_kid("v4.local.payload.!!!", &candidates).is_err());
+  }
+}
@@ -2,8 +2,10 @@ use super::GenericParserError;
 use crate::generic::*;
 
 use core::marker::PhantomData;
+use serde::de::{Deserializer, IgnoredAny, MapAccess, Visitor};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 
 ///The GenericParser is created at compile time by specifying a PASETO version and purpose and
 ///providing a key of the same version and purpose. This structure allows parsing an untrusted token string
@@ -64,10 +66,28 @@ use std::collections::HashMap;
 pub struct GenericParser<'a, 'b, Version, Purpose> {
   version: PhantomData<Version>,
   purpose: PhantomData<Purpose>,
-  claims: HashMap<String, Box<dyn erased_serde::Serialize + 'b>>,
+  claims: HashMap<String, Box<dyn erased_serde::Serialize + Send + Sync + 'b>>,
   claim_validators: ValidatorMap,
+  validator_only_keys: HashSet<String>,
   footer: Footer<'a>,
   implicit_assertion: ImplicitAssertion<'a>,
+  reject_duplicate_keys: bool,
+  collect_all_errors: bool,
+  consistent_time_claims: bool,
+  reject_empty_footer: bool,
+  require_footer: bool,
+  max_claim_count: Option<usize>,
+  footer_allowlist: Option<Vec<&'a [u8]>>,
+  opaque_errors: bool,
+  time_window: Option<(String, String)>,
+  claim_aliases: HashMap<String, String>,
+  post_processor: Option<Box<PostProcessorFn>>,
+  #[cfg(feature = "v4_public")]
+  accepted_keys: Vec<&'a PasetoAsymmetricPublicKey<'a, Version, Purpose>>,
+  #[cfg(feature = "v4_local")]
+  wrapping_key: Option<&'a PasetoSymmetricKey<Version, Purpose>>,
+  #[cfg(feature = "jsonschema")]
+  schema: Option<jsonschema::Validator>,
 }
 
 impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
@@ -78,12 +98,33 @@ impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
       purpose: PhantomData::<Purpose>,
       claims: HashMap::new(),
       claim_validators: HashMap::new(),
+      validator_only_keys: HashSet::new(),
       footer: Default::default(),
       implicit_assertion: Default::default(),
+      reject_duplicate_keys: false,
+      collect_all_errors: false,
+      consistent_time_claims: false,
+      reject_empty_footer: false,
+      require_footer: false,
+      max_claim_count: None,
+      footer_allowlist: None,
+      opaque_errors: false,
+      time_window: None,
+      claim_aliases: HashMap::new(),
+      post_processor: None,
+      #[cfg(feature = "v4_public")]
+      accepted_keys: Vec::new(),
+      #[cfg(feature = "v4_local")]
+      wrapping_key: None,
+      #[cfg(feature = "jsonschema")]
+      schema: None,
     }
   }
   ///Allows adding multiple [claims](PasetoClaim) at once to be checked during parsing by passing a Hashmap of claim keys and values
-  pub fn extend_check_claims(&mut self, value: HashMap<String, Box<dyn erased_serde::Serialize + 'b>>) -> &mut Self {
+  pub fn extend_check_claims(
+    &mut self,
+    value: HashMap<String, Box<dyn erased_serde::Serialize + Send + Sync + 'b>>,
+  ) -> &mut Self {
     self.claims.extend(value);
     self
   }
@@ -95,7 +136,7 @@ impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
   }
 
   #[cfg(feature = "serde")]
-  fn set_validation_claim<T: PasetoClaim + 'b + serde::Serialize>(
+  fn set_validation_claim<T: PasetoClaim + Send + Sync + 'b + serde::Serialize>(
     &mut self,
     value: T,
     validation_closure: Option<&'static ValidatorFn>,
@@ -115,7 +156,7 @@ impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
   ///custom validation algorithms on the claim. The claim can be a reserved claim or a custom
   ///claim.
   #[cfg(feature = "serde")]
-  pub fn validate_claim<T: PasetoClaim + 'b + serde::Serialize>(
+  pub fn validate_claim<T: PasetoClaim + Send + Sync + 'b + serde::Serialize>(
     &mut self,
     value: T,
     validation_closure: &'static ValidatorFn,
@@ -125,10 +166,216 @@ impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
 
   ///Verifies a passed [PasetoClaim] exists
   #[cfg(feature = "serde")]
-  pub fn check_claim<T: PasetoClaim + 'b + serde::Serialize>(&mut self, value: T) -> &mut Self {
+  pub fn check_claim<T: PasetoClaim + Send + Sync + 'b + serde::Serialize>(&mut self, value: T) -> &mut Self {
     self.set_validation_claim(value, None)
   }
 
+  ///Verifies a passed [PasetoClaim] exists and matches its value ignoring case, for identity
+  ///providers which treat e.g. [audience](AudienceClaim) and [subject](SubjectClaim) values
+  ///case-insensitively. Claims with non-string values are compared exactly.
+  #[cfg(feature = "serde")]
+  pub fn check_claim_ci<T: PasetoClaim + Send + Sync + 'b + serde::Serialize>(&mut self, value: T) -> &mut Self {
+    let key = value.get_key().to_string();
+    let expected = serde_json::to_value(&value)
+      .map(|claim| claim[&key].clone())
+      .unwrap_or_default();
+    self.claims.insert(key.clone(), Box::new(value));
+
+    self.claim_validators.insert(
+      key,
+      Box::new(move |key: &str, value: &Value| {
+        if value.is_null() {
+          return Err(PasetoClaimError::Missing(key.to_string()));
+        }
+        verify_claim_type(key, &expected, value)?;
+        let matches = match (expected.as_str(), value.as_str()) {
+          (Some(expected), Some(received)) => expected.to_lowercase() == received.to_lowercase(),
+          _ => claim_values_equal(&expected, value),
+        };
+        if !matches {
+          return Err(PasetoClaimError::Invalid(
+            key.to_string(),
+            display_claim(&expected),
+            display_claim(value),
+          ));
+        }
+        Ok(())
+      }),
+    );
+    self
+  }
+
+  ///Pins the expected issuer of the token. This is the idiomatic way to reject tokens from an
+  ///unexpected [issuer](IssuerClaim) and is equivalent to `check_claim(IssuerClaim::from(iss))`
+  #[cfg(feature = "serde")]
+  pub fn require_issuer(&mut self, iss: &'b str) -> &mut Self {
+    self.check_claim(IssuerClaim::from(iss))
+  }
+
+  ///Verifies the [subject](SubjectClaim) exists and is exactly one of `allowed`, e.g. any of a
+  ///tenant's principals, failing with [PasetoClaimError::SubjectNotAllowed] otherwise.
+  pub fn check_subject_in(&mut self, allowed: &[&str]) -> &mut Self {
+    let allowed = allowed.iter().map(|subject| subject.to_string()).collect::<Vec<_>>();
+    //the validator replaces any earlier check of the claim and runs without a claim value to compare
+    self.claims.remove("sub");
+    self.validator_only_keys.insert("sub".to_string());
+
+    self.claim_validators.insert(
+      "sub".to_string(),
+      Box::new(move |key: &str, value: &Value| {
+        if value.is_null() {
+          return Err(PasetoClaimError::Missing(key.to_string()));
+        }
+        let subject = value.as_str().ok_or_else(|| PasetoClaimError::TypeMismatch {
+          key: key.to_string(),
+          expected: "string",
+          found: json_type(value),
+        })?;
+        if !allowed.iter().any(|allowed| allowed == subject) {
+          return Err(PasetoClaimError::SubjectNotAllowed(subject.to_string()));
+        }
+        Ok(())
+      }),
+    );
+    self
+  }
+
+  ///Verifies a numeric claim exists and is within `epsilon` of the `expected` value. Useful for
+  ///floating point claims where an exact comparison is fragile after serialization.
+  pub fn check_claim_approx(&mut self, key: &str, expected: f64, epsilon: f64) -> &mut Self {
+    //the validator replaces any earlier check of the claim and runs without a claim value to compare
+    self.claims.remove(key);
+    self.validator_only_keys.insert(key.to_string());
+
+    self.claim_validators.insert(
+      key.to_string(),
+      Box::new(move |key: &str, value: &Value| {
+        if value.is_null() {
+          return Err(PasetoClaimError::Missing(key.to_string()));
+        }
+        let received = value.as_f64().ok_or_else(|| PasetoClaimError::TypeMismatch {
+          key: key.to_string(),
+          expected: "number",
+          found: json_type(value),
+        })?;
+        if (received - expected).abs() > epsilon {
+          return Err(PasetoClaimError::Invalid(
+            key.to_string(),
+            expected.to_string(),
+            received.to_string(),
+          ));
+        }
+        Ok(())
+      }),
+    );
+    self
+  }
+
+  ///Rejects decrypted or verified payloads containing the same top level key more than once.
+  ///By default the last occurrence of a duplicate key wins, which could be used to smuggle a
+  ///claim past a validator.
+  pub fn set_reject_duplicate_keys(&mut self, reject: bool) -> &mut Self {
+    self.reject_duplicate_keys = reject;
+    self
+  }
+
+  ///Reports every failing claim check at once as a [PasetoClaimError::Multiple] rather than returning
+  ///on the first failure. Expired and not yet valid tokens are still rejected immediately.
+  pub fn set_collect_all_errors(&mut self, collect: bool) -> &mut Self {
+    self.collect_all_errors = collect;
+    self
+  }
+
+  ///Rejects tokens whose time claims contradict each other, i.e. which don't satisfy
+  ///`nbf <= iat <= exp`, regardless of the current time. Claims missing from the token are skipped.
+  pub fn require_consistent_time_claims(&mut self) -> &mut Self {
+    self.consistent_time_claims = true;
+    self
+  }
+
+  ///Rejects tokens unless the current time falls within the window between two custom RFC3339 date
+  ///claims, such as `valid_from` and `valid_to`, failing with [PasetoClaimError::OutsideWindow].
+  ///Like [not before](NotBeforeClaim) and [expiration](ExpirationClaim) the window includes its start
+  ///but not its end. Both claims must be present.
+  pub fn validate_time_window(&mut self, from_key: &str, to_key: &str) -> &mut Self {
+    self.time_window = Some((from_key.to_string(), to_key.to_string()));
+    self
+  }
+
+  ///Rejects tokens ending in an empty footer segment, e.g. `v4.local.<payload>.`, which some
+  ///producers emit. By default an empty footer segment is treated as no footer at all.
+  pub fn set_reject_empty_footer(&mut self, reject: bool) -> &mut Self {
+    self.reject_empty_footer = reject;
+    self
+  }
+
+  ///Rejects tokens without a footer with [PasetoError::MissingFooter] before any decryption or
+  ///verification. An empty footer segment counts as no footer. The footer itself is still checked
+  ///against the one set on the parser.
+  pub fn require_footer(&mut self) -> &mut Self {
+    self.require_footer = true;
+    self
+  }
+
+  ///Reports every failure to decrypt or verify a token with the same opaque
+  ///[PasetoError::VerificationFailed], so a tampered token, a wrong key and a bad tag or signature
+  ///can't be told apart by the error returned. Structural problems found before the key is used,
+  ///such as a wrong header, bad base64 or a footer mismatch, are still reported as they are.
+  ///
+  ///Only the error is made uniform, not the time taken to fail: a token rejected for its structure
+  ///still fails faster than one rejected by its tag or signature. This removes the error oracle at
+  ///the cost of diagnostics: enable it for parsers exposed to untrusted callers, and leave it off
+  ///while debugging key or footer mix-ups.
+  pub fn set_opaque_errors(&mut self, opaque: bool) -> &mut Self {
+    self.opaque_errors = opaque;
+    self
+  }
+
+  ///Rejects decrypted or verified payloads with more than `max` top level claims with
+  ///[PasetoClaimError::TooManyClaims], bounding the work spent validating an untrusted token.
+  pub fn set_max_claim_count(&mut self, max: usize) -> &mut Self {
+    self.max_claim_count = Some(max);
+    self
+  }
+
+  ///Only accepts tokens whose footer is exactly one of `allowed`, failing any other token with
+  ///[PasetoError::FooterNotAllowed]. Unlike [set_footer](Self::set_footer) this admits several
+  ///footers, but it also rejects every footer not listed. Tokens without a footer are only accepted
+  ///when the allowlist contains an empty entry. The matched footer replaces any footer set on the parser.
+  pub fn set_footer_allowlist(&mut self, allowed: &[&'a [u8]]) -> &mut Self {
+    self.footer_allowlist = Some(allowed.to_vec());
+    self
+  }
+
+  ///Looks up claims under `alias` when the token has no claim named `standard`, e.g. to validate
+  ///expiration from an `expires` claim issued by a non-conforming system. The validators and
+  ///checks registered for `standard` run against the aliased value.
+  pub fn set_claim_alias(&mut self, standard: &str, alias: &str) -> &mut Self {
+    self.claim_aliases.insert(standard.to_string(), alias.to_string());
+    self
+  }
+
+  ///Sets a function which runs on the verified claims before they're returned, after all claim
+  ///validation has passed. Useful for normalizing claims, e.g. lowercasing the issuer.
+  pub fn set_post_processor(&mut self, post_processor: Box<PostProcessorFn>) -> &mut Self {
+    self.post_processor = Some(post_processor);
+    self
+  }
+
+  ///Validates the claims against a [JSON Schema](https://json-schema.org) once the token has been
+  ///decrypted or verified and its claims have been checked. Claims which don't conform fail to
+  ///parse with [PasetoClaimError::SchemaViolation].
+  ///
+  /// # Errors
+  ///
+  /// Returns [PasetoClaimError::InvalidSchema] if the schema cannot be compiled.
+  #[cfg(feature = "jsonschema")]
+  pub fn set_schema(&mut self, schema: Value) -> Result<&mut Self, PasetoClaimError> {
+    let validator = jsonschema::validator_for(&schema).map_err(|err| PasetoClaimError::InvalidSchema(err.to_string()))?;
+    self.schema = Some(validator);
+    Ok(self)
+  }
+
   /// Gets an optional [Footer] set during parser building
   pub fn get_footer(&self) -> Footer {
     self.footer
@@ -154,47 +401,227 @@ impl<'a, 'b, Version: ImplicitAssertionCapable, Purpose> GenericParser<'a, 'b, V
 }
 
 impl<'a, 'b, Version, Purpose> GenericParser<'a, 'b, Version, Purpose> {
+  ///Runs the parser's claim checks and validators against already decrypted claims without any
+  ///cryptography, returning every violation rather than stopping at the first. This makes it
+  ///possible to test a parser's configuration against sample payloads without minting tokens.
+  ///
+  ///Violations are returned in claim key order, after any claim count, time claim ordering, time
+  ///window or schema violation.
+  pub fn dry_run(&self, claims: &Value) -> Vec<PasetoClaimError> {
+    if self.checked_keys().next().is_some() && !claims.is_object() {
+      return vec![PasetoClaimError::PayloadNotObject];
+    }
+
+    let mut errors = Vec::new();
+    if let (Some(max), Some(claims)) = (self.max_claim_count, claims.as_object()) {
+      if claims.len() > max {
+        errors.push(PasetoClaimError::TooManyClaims(claims.len(), max));
+      }
+    }
+    if self.consistent_time_claims {
+      errors.extend(verify_time_claim_order(claims).err());
+    }
+    if let Some((from_key, to_key)) = &self.time_window {
+      errors.extend(verify_time_window(claims, from_key, to_key, time::OffsetDateTime::now_utc()).err());
+    }
+    #[cfg(feature = "jsonschema")]
+    if let Some(schema) = &self.schema {
+      let violations = schema.iter_errors(claims).map(|err| err.to_string()).collect::<Vec<_>>();
+      if !violations.is_empty() {
+        errors.push(PasetoClaimError::SchemaViolation(violations.join("; ")));
+      }
+    }
+
+    let mut keys = self.checked_keys().collect::<Vec<_>>();
+    keys.sort();
+    for key in keys {
+      match self.verify_claim(key, claims) {
+        Ok(()) => {}
+        Err(GenericParserError::ClaimError { source }) => errors.push(source),
+        Err(_) => errors.push(PasetoClaimError::Unexpected(key.to_string())),
+      }
+    }
+    errors
+  }
+
+  ///Checks the token's footer segment against the parser's footer rules and returns the footer the
+  ///token must be authenticated with
+  fn expected_footer(&self, potential_token: &str) -> Result<Footer<'a>, GenericParserError> {
+    let footer_segment = potential_token.split('.').nth(3);
+    if self.reject_empty_footer && footer_segment == Some("") {
+      return Err(
+        PasetoError::MalformedToken {
+          reason: "the token has an empty footer segment",
+        }
+        .into(),
+      );
+    }
+    if self.require_footer && footer_segment.unwrap_or_default().is_empty() {
+      return Err(PasetoError::MissingFooter.into());
+    }
+    let allowlist = match &self.footer_allowlist {
+      Some(allowlist) => allowlist,
+      None => return Ok(self.footer),
+    };
+    let found = DefaultBase64Codec::decode(footer_segment.unwrap_or_default()).map_err(PasetoError::from)?;
+    let allowed = allowlist
+      .iter()
+      .find(|allowed| **allowed == found.as_slice())
+      .ok_or(PasetoError::FooterNotAllowed)?;
+    let allowed = std::str::from_utf8(allowed).map_err(PasetoError::from)?;
+    Ok(Footer::from(allowed))
+  }
+
+  //Replaces a keyed decryption or verification failure with an opaque error when opaque errors
+  //are enabled, leaving structural errors found before the key was used untouched
+  fn opaque_error(&self, error: PasetoError) -> PasetoError {
+    if !self.opaque_errors {
+      return error;
+    }
+    match error {
+      PasetoError::IncorrectSize
+      | PasetoError::MalformedToken { .. }
+      | PasetoError::WrongHeader
+      | PasetoError::VersionMismatch { .. }
+      | PasetoError::PurposeMismatch { .. }
+      | PasetoError::PayloadBase64Decode { .. }
+      | PasetoError::FooterInvalid
+      | PasetoError::FooterNotAllowed
+      | PasetoError::MissingFooter => error,
+      _ => PasetoError::VerificationFailed,
+    }
+  }
+
   fn verify_claims(&self, token: &str) -> Result<Value, GenericParserError> {
-    let json: Value = serde_json::from_str(token)?;
+    let mut json: Value = serde_json::from_str(token)?;
+
+    if self.reject_duplicate_keys && json.is_object() {
+      if let Some(key) = find_duplicate_key(token)? {
+        return Err(PasetoClaimError::DuplicateKey(key).into());
+      }
+    }
+
+    if let (Some(max), Some(claims)) = (self.max_claim_count, json.as_object()) {
+      if claims.len() > max {
+        return Err(PasetoClaimError::TooManyClaims(claims.len(), max).into());
+      }
+    }
+
+    //claims can only be looked up in an object payload
+    if self.checked_keys().next().is_some() && !json.is_object() {
+      return Err(PasetoClaimError::PayloadNotObject.into());
+    }
+
+    if self.consistent_time_claims {
+      verify_time_claim_order(&json)?;
+    }
+
+    if let Some((from_key, to_key)) = &self.time_window {
+      verify_time_window(&json, from_key, to_key, time::OffsetDateTime::now_utc())?;
+    }
 
     // here we want to traverse all of the claims to validate and verify their values
-    for (key, box_val) in &self.claims {
-      //ensure the claim exists
+    let mut errors = Vec::new();
+    for key in self.checked_keys() {
+      match self.verify_claim(key, &json) {
+        Ok(()) => {}
+        //time based checks always short circuit
+        Err(GenericParserError::ClaimError { source })
+          if self.collect_all_errors
+            && !matches!(source, PasetoClaimError::Expired | PasetoClaimError::UseBeforeAvailable(_)) =>
+        {
+          errors.push(source)
+        }
+        Err(err) => return Err(err),
+      }
+    }
+
+    if !errors.is_empty() {
+      return Err(PasetoClaimError::Multiple(errors).into());
+    }
+
+    #[cfg(feature = "jsonschema")]
+    if let Some(schema) = &self.schema {
+      let violations = schema.iter_errors(&json).map(|err| err.to_string()).collect::<Vec<_>>();
+      if !violations.is_empty() {
+        return Err(PasetoClaimError::SchemaViolation(violations.join("; ")).into());
+      }
+    }
+
+    if let Some(post_processor) = &self.post_processor {
+      post_processor(&mut json);
+    }
+
+    Ok(json)
+  }
+
+  //the keys of every claim checked or validated during verification
+  fn checked_keys(&self) -> impl Iterator<Item = &String> {
+    let validator_only = self.validator_only_keys.iter().filter(|key| !self.claims.contains_key(*key));
+    self.claims.keys().chain(validator_only)
+  }
+
+  fn verify_claim(&self, key: &str, json: &Value) -> Result<(), GenericParserError> {
+    let value = self.claim_value(key, json);
+
+    //now let's run any custom validation if there is any
+    if self.claim_validators.contains_key(key) {
+      let box_validator = &self.claim_validators[key];
+      let validator = box_validator.as_ref();
+      validator(key, value)?;
+    } else if let Some(box_val) = self.claims.get(key) {
       //get the raw value of the claim
       let raw = serde_json::to_value(box_val)?;
 
-      //now let's run any custom validation if there is any
-      if self.claim_validators.contains_key(key) {
-        let box_validator = &self.claim_validators[key];
-        let validator = box_validator.as_ref();
-        validator(key, &json[&key])?;
-      } else {
-        //otherwise, simply verify the claim exists and matches the value passed in
-        if json[&key] == Value::Null {
-          return Err(PasetoClaimError::Missing(key.to_string()).into());
-        }
+      //otherwise, simply verify the claim exists and matches the value passed in
+      if *value == Value::Null {
+        return Err(PasetoClaimError::Missing(key.to_string()).into());
+      }
 
-        if raw[&key] != json[&key] {
-          return Err(
-            PasetoClaimError::Invalid(
-              key.to_string(),
-              json[&key]
-                .as_str()
-                .ok_or_else(|| PasetoClaimError::Unexpected(key.to_string()))?
-                .into(),
-              raw[&key]
-                .as_str()
-                .ok_or_else(|| PasetoClaimError::Unexpected(key.to_string()))?
-                .into(),
-            )
-            .into(),
-          );
-        }
+      //a value of the wrong type is reported apart from a wrong value
+      verify_claim_type(key, &raw[&key], value)?;
+      if !claim_values_equal(&raw[&key], value) {
+        return Err(PasetoClaimError::Invalid(key.to_string(), display_claim(value), display_claim(&raw[&key])).into());
       }
     }
+    Ok(())
+  }
 
-    Ok(json)
+  //the claim's value in the token, falling back to its alias when the claim itself is absent
+  fn claim_value<'j>(&self, key: &str, json: &'j Value) -> &'j Value {
+    match (json.get(key), self.claim_aliases.get(key)) {
+      (None, Some(alias)) => &json[alias.as_str()],
+      _ => &json[key],
+    }
+  }
+}
+
+/// Finds the first top level key which appears more than once in a JSON object
+fn find_duplicate_key(json: &str) -> Result<Option<String>, serde_json::Error> {
+  struct DuplicateKeyVisitor;
+
+  impl<'de> Visitor<'de> for DuplicateKeyVisitor {
+    type Value = Option<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+      formatter.write_str("a json object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+      let mut keys = HashSet::new();
+      let mut duplicate = None;
+      //the whole map is consumed so the deserializer sees a complete object
+      while let Some(key) = map.next_key::<String>()? {
+        if !keys.insert(key.clone()) && duplicate.is_none() {
+          duplicate = Some(key);
+        }
+        map.next_value::<IgnoredAny>()?;
+      }
+      Ok(duplicate)
+    }
   }
+
+  serde_json::Deserializer::from_str(json).deserialize_map(DuplicateKeyVisitor)
 }
 
 #[cfg(feature = "v1_local")]
@@ -259,11 +686,68 @@ impl<'a, 'b> GenericParser<'a, 'b, V1, Local> {
     potential_token: &'a str,
     key: &'a PasetoSymmetricKey<V1, Local>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //decrypt, then validate
-    let token = Paseto::<V1, Local>::try_decrypt(potential_token, key, self.get_footer())?;
+    let token = Paseto::<V1, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V1, Local>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //decrypt, then validate
+    let token = Paseto::<V1, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V1, Local>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer set on the parser still applies.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V1, Local>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V1, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v2_local")]
@@ -324,15 +808,72 @@ impl<'a, 'b> GenericParser<'a, 'b, V2, Local> {
   ///   ```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoSymmetricKey<V2, Local>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token = Paseto::<V2, Local>::try_decrypt(potential_token, key, self.get_footer())?;
+    let token = Paseto::<V2, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V2, Local>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V2, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V2, Local>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer set on the parser still applies.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V2, Local>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V2, Local>::try_decrypt(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v3_local")]
@@ -396,16 +937,72 @@ impl<'a, 'b> GenericParser<'a, 'b, V3, Local> {
   ///   ```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoSymmetricKey<V3, Local>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token =
-      Paseto::<V3, Local>::try_decrypt(potential_token, key, self.get_footer(), self.get_implicit_assertion())?;
+    let token = Paseto::<V3, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V3, Local>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V3, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V3, Local>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer and implicit assertion set on the parser still apply.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V3, Local>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V3, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v4_local")]
@@ -469,40 +1066,133 @@ impl<'a, 'b> GenericParser<'a, 'b, V4, Local> {
   ///   ```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoSymmetricKey<V4, Local>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token =
-      Paseto::<V4, Local>::try_decrypt(potential_token, key, self.get_footer(), self.get_implicit_assertion())?;
+    let token = Paseto::<V4, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
-}
 
-#[cfg(feature = "v1_public")]
-impl<'a, 'b> GenericParser<'a, 'b, V1, Public> {
-  /// Given a [PasetoAsymmetricPublicKey], attempts to verify a (V1, Public) signed PASETO token string and then validate
-  /// claims provided to the GenericParser.
-  ///
-  /// Returns a serde_json [Value] with the verified [claims](PasetoClaim) and payload on success.
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
   ///
   /// # Errors
   ///
-  /// Returns [`GenericParserError`] for any errors when decrypting the encrypted payload or when validating claims.
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V4, Local>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V4, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
   ///
-  /// # Example
+  /// # Errors
   ///
-  ///```
-  ///# #[cfg(all(feature = "generic", feature="v1_public"))]
-  ///# {
-  ///   # use rusty_paseto::generic::*;
-
-  ///    //obtain a private key (pk)
-  ///   # let private_key = include_bytes!("../../../tests/v1_public_test_vectors_private_key.pk8");
-  ///   # let pk: &[u8] = private_key;
-  ///    let private_key = PasetoAsymmetricPrivateKey::<V1, Public>::from(pk);
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V4, Local>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer and implicit assertion set on the parser still apply.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoSymmetricKey<V4, Local>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V4, Local>::try_decrypt(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
+
+  ///Sets the master key used by [parse_with_wrapped_key](Self::parse_with_wrapped_key) to unwrap
+  ///the data key carried in a token's footer
+  pub fn set_wrapping_key(&mut self, wrapping_key: &'a PasetoSymmetricKey<V4, Local>) -> &mut Self {
+    self.wrapping_key = Some(wrapping_key);
+    self
+  }
+
+  /// Attempts to decrypt a (V4, Local) PASETO token whose footer is a `k4.local-wrap.pie.` PASERK
+  /// holding the data key the token was encrypted with. The data key is unwrapped with the
+  /// [wrapping key](Self::set_wrapping_key) and then used to decrypt the token and validate the
+  /// claims provided to the GenericParser. Any [footer](Self::set_footer) set on the parser is ignored.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if no wrapping key has been set, the footer isn't a PASERK
+  /// wrapped with the wrapping key, or for any errors when decrypting the token or validating claims.
+  pub fn parse_with_wrapped_key(&self, potential_token: &'a str) -> Result<Value, GenericParserError> {
+    self.expected_footer(potential_token)?;
+    let wrapping_key = self.wrapping_key.ok_or(PasetoError::InvalidKey)?;
+
+    //the wrapped key travels in the footer, which is still authenticated once split off
+    let (token, footer) = potential_token
+      .rsplit_once('.')
+      .filter(|_| potential_token.split('.').count() == 4)
+      .ok_or(PasetoError::InvalidPaserk {
+        expected: "k4.local-wrap.pie.",
+      })?;
+    let footer = DefaultBase64Codec::decode(footer).map_err(PasetoError::from)?;
+    let paserk = std::str::from_utf8(&footer).map_err(PasetoError::from)?;
+    let key =
+      PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(paserk, wrapping_key).map_err(|error| self.opaque_error(error))?;
+
+    let token = Paseto::<V4, Local>::try_decrypt_detached_footer(token, &key, &footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+    self.verify_claims(&token)
+  }
+}
+
+#[cfg(feature = "v1_public")]
+impl<'a, 'b> GenericParser<'a, 'b, V1, Public> {
+  /// Given a [PasetoAsymmetricPublicKey], attempts to verify a (V1, Public) signed PASETO token string and then validate
+  /// claims provided to the GenericParser.
+  ///
+  /// Returns a serde_json [Value] with the verified [claims](PasetoClaim) and payload on success.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for any errors when decrypting the encrypted payload or when validating claims.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///# #[cfg(all(feature = "generic", feature="v1_public"))]
+  ///# {
+  ///   # use rusty_paseto::generic::*;
+
+  ///    //obtain a private key (pk)
+  ///   # let private_key = include_bytes!("../../../tests/v1_public_test_vectors_private_key.pk8");
+  ///   # let pk: &[u8] = private_key;
+  ///    let private_key = PasetoAsymmetricPrivateKey::<V1, Public>::from(pk);
 
   ///     let footer = Footer::from("some footer");
 
@@ -555,15 +1245,72 @@ impl<'a, 'b> GenericParser<'a, 'b, V1, Public> {
   ///```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoAsymmetricPublicKey<V1, Public>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token = Paseto::<V1, Public>::try_verify(potential_token, key, self.get_footer())?;
+    let token = Paseto::<V1, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V1, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer set on the parser still applies.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V1, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v2_public")]
@@ -637,15 +1384,72 @@ impl<'a, 'b> GenericParser<'a, 'b, V2, Public> {
   /// # Ok::<(),anyhow::Error>(())
   ///```
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoAsymmetricPublicKey<V2, Public>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token = Paseto::<V2, Public>::try_verify(potential_token, key, self.get_footer())?;
+    let token = Paseto::<V2, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V2, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer set on the parser still applies.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V2, Public>::try_verify(potential_token, key, footer)
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v3_public")]
@@ -727,16 +1531,72 @@ impl<'a, 'b> GenericParser<'a, 'b, V3, Public> {
   ///```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoAsymmetricPublicKey<V3, Public>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token =
-      Paseto::<V3, Public>::try_verify(potential_token, key, self.get_footer(), self.get_implicit_assertion())?;
+    let token = Paseto::<V3, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V3, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer and implicit assertion set on the parser still apply.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V3, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
 }
 
 #[cfg(feature = "v4_public")]
@@ -816,16 +1676,238 @@ impl<'a, 'b> GenericParser<'a, 'b, V4, Public> {
   ///```
 
   pub fn parse(
-    &mut self,
+    &self,
     potential_token: &'a str,
     key: &'a PasetoAsymmetricPublicKey<V4, Public>,
   ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
     //first we need to verify the token
-    let token =
-      Paseto::<V4, Public>::try_verify(potential_token, key, self.get_footer(), self.get_implicit_assertion())?;
+    let token = Paseto::<V4, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
 
     self.verify_claims(&token)
   }
+
+  /// Parses the token like [parse](Self::parse) and also returns a [ParseReport] describing it,
+  /// for security event logging.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), except that a
+  /// token failing claim validation returns [GenericParserError::ValidationFailed] carrying the
+  /// report.
+  pub fn parse_with_report(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+  ) -> Result<(Value, ParseReport), GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    //first we need to verify the token
+    let token = Paseto::<V4, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    self.verify_claims_with_report(potential_token, &token)
+  }
+
+  /// Parses the token like [parse](Self::parse), returning the claims as a map sorted by claim name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] for the same reasons as [parse](Self::parse), or
+  /// [PasetoClaimError::PayloadNotObject] if the payload isn't a JSON object.
+  pub fn parse_map(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+  ) -> Result<BTreeMap<String, Value>, GenericParserError> {
+    claims_map(self.parse(potential_token, key)?)
+  }
+
+  /// Decrypts or verifies the token like [parse](Self::parse) but returns its claims without
+  /// running any claim checks or validators, e.g. to confirm an archived token is authentic after it
+  /// has expired. The footer and implicit assertion set on the parser still apply.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if the token is malformed, fails to decrypt or verify, or its
+  /// payload isn't JSON.
+  pub fn verify_only(
+    &self,
+    potential_token: &'a str,
+    key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+  ) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let token = Paseto::<V4, Public>::try_verify(potential_token, key, footer, self.get_implicit_assertion())
+      .map_err(|error| self.opaque_error(error))?;
+
+    Ok(serde_json::from_str(&token)?)
+  }
+
+  ///Sets the public keys accepted by [parse_with_accepted_keys](Self::parse_with_accepted_keys), for example
+  ///both the old and new keys while a signing key is being rotated
+  pub fn set_accepted_keys(&mut self, keys: &[&'a PasetoAsymmetricPublicKey<'a, V4, Public>]) -> &mut Self {
+    self.accepted_keys = keys.to_vec();
+    self
+  }
+
+  /// Attempts to verify a (V4, Public) signed PASETO token string against each of the
+  /// [accepted keys](Self::set_accepted_keys) in order and then validates the claims provided to the GenericParser
+  /// using the first key which verifies the signature.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`GenericParserError`] if none of the accepted keys verify the token or when validating claims.
+  /// When no accepted keys have been set the token is rejected with an invalid signature error.
+  pub fn parse_with_accepted_keys(&self, potential_token: &'a str) -> Result<Value, GenericParserError> {
+    let footer = self.expected_footer(potential_token)?;
+    let mut last_error = PasetoError::InvalidSignature;
+    for key in &self.accepted_keys {
+      match Paseto::<V4, Public>::try_verify(potential_token, key, footer, self.implicit_assertion) {
+        Ok(token) => return self.verify_claims(&token),
+        Err(err) => last_error = err,
+      }
+    }
+    Err(self.opaque_error(last_error).into())
+  }
+}
+
+//collects a verified payload's claims into a sorted map
+fn claims_map(claims: Value) -> Result<BTreeMap<String, Value>, GenericParserError> {
+  match claims {
+    Value::Object(claims) => Ok(claims.into_iter().collect()),
+    _ => Err(PasetoClaimError::PayloadNotObject.into()),
+  }
+}
+
+//the name of a JSON value's type, for reporting type mismatches
+fn json_type(value: &Value) -> &'static str {
+  match value {
+    Value::Null => "null",
+    Value::Bool(_) => "boolean",
+    Value::Number(_) => "number",
+    Value::String(_) => "string",
+    Value::Array(_) => "array",
+    Value::Object(_) => "object",
+  }
+}
+
+//fails with a type mismatch when a claim's value isn't of the same JSON type as the expected value
+fn verify_claim_type(key: &str, expected: &Value, found: &Value) -> Result<(), PasetoClaimError> {
+  let (expected, found) = (json_type(expected), json_type(found));
+  if expected != found {
+    return Err(PasetoClaimError::TypeMismatch {
+      key: key.to_string(),
+      expected,
+      found,
+    });
+  }
+  Ok(())
+}
+
+//compares claim values, treating numbers which differ only in representation, e.g. `4` and `4.0`,
+//as equal. Integers are still compared exactly so large values don't lose precision.
+fn claim_values_equal(expected: &Value, found: &Value) -> bool {
+  match (expected, found) {
+    (Value::Number(expected), Value::Number(found)) if expected.is_f64() || found.is_f64() => {
+      expected.as_f64() == found.as_f64()
+    }
+    _ => expected == found,
+  }
+}
+
+//a claim value as shown in errors, without quotes around strings
+fn display_claim(value: &Value) -> String {
+  value.as_str().map_or_else(|| value.to_string(), str::to_string)
+}
+
+//checks nbf <= iat <= exp for whichever of the three claims are present
+fn verify_time_claim_order(json: &Value) -> Result<(), PasetoClaimError> {
+  let mut previous: Option<time::OffsetDateTime> = None;
+  for key in ["nbf", "iat", "exp"] {
+    let value = match json[key].as_str() {
+      Some(value) => value,
+      None => continue,
+    };
+    let datetime = parse_time_claim(value)?;
+    if previous.is_some_and(|previous| previous > datetime) {
+      return Err(PasetoClaimError::InconsistentTimeClaims);
+    }
+    previous = Some(datetime);
+  }
+  Ok(())
+}
+
+//checks `now` falls within the window between two date claims, including its start but not its end
+fn verify_time_window(
+  json: &Value,
+  from_key: &str,
+  to_key: &str,
+  now: time::OffsetDateTime,
+) -> Result<(), PasetoClaimError> {
+  let date_claim = |key: &str| -> Result<&str, PasetoClaimError> {
+    let value = &json[key];
+    if value.is_null() {
+      return Err(PasetoClaimError::Missing(key.to_string()));
+    }
+    value.as_str().ok_or_else(|| PasetoClaimError::TypeMismatch {
+      key: key.to_string(),
+      expected: "string",
+      found: json_type(value),
+    })
+  };
+  let (from, to) = (date_claim(from_key)?, date_claim(to_key)?);
+  if !(parse_time_claim(from)?..parse_time_claim(to)?).contains(&now) {
+    return Err(PasetoClaimError::OutsideWindow(from.to_string(), to.to_string()));
+  }
+  Ok(())
+}
+
+fn parse_time_claim(value: &str) -> Result<time::OffsetDateTime, PasetoClaimError> {
+  time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339)
+    .map_err(|_| PasetoClaimError::RFC3339Date(value.to_string()))
+}
+
+impl<'a, 'b, Version: VersionTrait, Purpose: PurposeTrait> GenericParser<'a, 'b, Version, Purpose> {
+  //Validates the claims of an already decrypted or verified payload, describing the token in a
+  //report whether or not its claims pass validation
+  fn verify_claims_with_report(&self, potential_token: &str, payload: &str) -> Result<(Value, ParseReport), GenericParserError> {
+    match self.verify_claims(payload) {
+      Ok(claims) => {
+        let report = self.report(potential_token, &claims, ValidationOutcome::Passed);
+        Ok((claims, report))
+      }
+      Err(GenericParserError::ClaimError { source }) => {
+        //the payload is valid json, otherwise validation would have failed before any claim check
+        let claims = serde_json::from_str(payload).unwrap_or(Value::Null);
+        let failures = match &source {
+          PasetoClaimError::Multiple(errors) => errors.iter().map(ToString::to_string).collect(),
+          error => vec![error.to_string()],
+        };
+        let report = self.report(potential_token, &claims, ValidationOutcome::Failed(failures));
+        Err(GenericParserError::ValidationFailed {
+          source,
+          report: Box::new(report),
+        })
+      }
+      Err(error) => Err(error),
+    }
+  }
+
+  fn report(&self, potential_token: &str, claims: &Value, outcome: ValidationOutcome) -> ParseReport {
+    //the token has already been decrypted or verified, so any footer segment is valid base64
+    let footer = potential_token.split('.').nth(3).filter(|footer| !footer.is_empty());
+    ParseReport {
+      version: Version::name(),
+      purpose: Purpose::name(),
+      footer_present: footer.is_some(),
+      footer_len: footer
+        .and_then(|footer| DefaultBase64Codec::decode(footer).ok())
+        .map_or(0, |footer| footer.len()),
+      claim_count: claims.as_object().map_or(0, |claims| claims.len()),
+      claims_validated: self.checked_keys().count(),
+      outcome,
+    }
+  }
 }
 
 impl<'a, 'b, Version, Purpose> Default for GenericParser<'a, 'b, Version, Purpose> {
@@ -951,6 +2033,26 @@ mod parsers {
     Ok(())
   }
 
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn parse_through_shared_reference_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let first = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("first"))
+      .try_encrypt(&key)?;
+    let second = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("second"))
+      .try_encrypt(&key)?;
+
+    //the parser is only ever borrowed immutably
+    let parser = GenericParser::<V2, Local>::default();
+    let parser = &parser;
+
+    assert_eq!(parser.parse(&first, &key)?["sub"], "first");
+    assert_eq!(parser.parse(&second, &key)?["sub"], "second");
+    Ok(())
+  }
+
   #[cfg(feature="v2_local")]
   #[test]
   fn basic_claim_validation_test() -> Result<()> {
@@ -1113,4 +2215,879 @@ mod parsers {
     assert_eq!(expected_error_kind, actual_error_kind);
     Ok(())
   }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn approximate_claim_validation_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(CustomClaim::try_from(("ratio", 2.500126))?)
+      .try_encrypt(&key)?;
+
+    //within epsilon
+    let json = GenericParser::<V2, Local>::default()
+      .check_claim_approx("ratio", 2.5001, 0.001)
+      .parse(&token, &key)?;
+    assert_eq!(json["ratio"], 2.500126);
+
+    //outside of epsilon
+    let actual_error_kind = format!(
+      "{}",
+      GenericParser::<V2, Local>::default()
+        .check_claim_approx("ratio", 2.5, 0.0001)
+        .parse(&token, &key)
+        .unwrap_err()
+    );
+    let expected_error_kind = "The claim 'ratio' failed validation.  Expected '2.5' but received '2.500126'";
+    assert_eq!(expected_error_kind, actual_error_kind);
+
+    //the expected value is not a claim of its own
+    assert!(matches!(
+      GenericParser::<V2, Local>::default()
+        .check_claim_approx("ratio", 2.5, 0.0001)
+        .dry_run(&serde_json::json!({}))
+        .as_slice(),
+      [PasetoClaimError::Missing(ref claim)] if claim == "ratio"
+    ));
+
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn dry_run_test() -> Result<()> {
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser
+      .check_claim(AudienceClaim::from("customers"))
+      .check_claim(IssuerClaim::from("me"))
+      .validate_claim(CustomClaim::try_from("seats")?, &|key, value| match value.as_u64() {
+        Some(seats) if seats <= 4 => Ok(()),
+        _ => Err(PasetoClaimError::CustomValidation(key.to_string())),
+      })
+      .require_consistent_time_claims();
+
+    let errors = parser.dry_run(&serde_json::json!({
+      "aud": "other customers",
+      "seats": 5,
+      "nbf": "2030-01-01T00:00:00+00:00",
+      "iat": "2020-01-01T00:00:00+00:00"
+    }));
+    assert!(
+      matches!(
+        errors.as_slice(),
+        [
+          PasetoClaimError::InconsistentTimeClaims,
+          PasetoClaimError::Invalid(aud, _, _),
+          PasetoClaimError::Missing(iss),
+          PasetoClaimError::CustomValidation(seats),
+        ] if aud == "aud" && iss == "iss" && seats == "seats"
+      ),
+      "unexpected violations {:?}",
+      errors
+    );
+
+    assert!(parser
+      .dry_run(&serde_json::json!({"aud": "customers", "iss": "me", "seats": 2}))
+      .is_empty());
+    assert!(matches!(
+      parser.dry_run(&serde_json::json!("not an object")).as_slice(),
+      [PasetoClaimError::PayloadNotObject]
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn dry_run_max_claim_count_test() -> Result<()> {
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.check_claim(AudienceClaim::from("customers")).set_max_claim_count(2);
+
+    let errors = parser.dry_run(&serde_json::json!({"aud": "customers", "iss": "me", "seats": 2}));
+    assert!(
+      matches!(errors.as_slice(), [PasetoClaimError::TooManyClaims(3, 2)]),
+      "unexpected violations {:?}",
+      errors
+    );
+    assert!(parser.dry_run(&serde_json::json!({"aud": "customers", "iss": "me"})).is_empty());
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn dry_run_time_window_test() -> Result<()> {
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.validate_time_window("valid_from", "valid_to");
+
+    let errors = parser.dry_run(&serde_json::json!({
+      "valid_from": "2000-01-01T00:00:00+00:00",
+      "valid_to": "2001-01-01T00:00:00+00:00"
+    }));
+    assert!(
+      matches!(errors.as_slice(), [PasetoClaimError::OutsideWindow(_, _)]),
+      "unexpected violations {:?}",
+      errors
+    );
+    assert!(parser
+      .dry_run(&serde_json::json!({
+        "valid_from": "2000-01-01T00:00:00+00:00",
+        "valid_to": "2999-01-01T00:00:00+00:00"
+      }))
+      .is_empty());
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn case_insensitive_claim_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(AudienceClaim::from("MyApp"))
+      .set_claim(SubjectClaim::from("Loyal Subjects"))
+      .try_encrypt(&key)?;
+
+    let json = GenericParser::<V2, Local>::default()
+      .check_claim_ci(AudienceClaim::from("myapp"))
+      .check_claim_ci(SubjectClaim::from("LOYAL SUBJECTS"))
+      .parse(&token, &key)?;
+    assert_eq!(json["aud"], "MyApp");
+
+    //the exact check still rejects the differently cased value
+    assert!(GenericParser::<V2, Local>::default()
+      .check_claim(AudienceClaim::from("myapp"))
+      .parse(&token, &key)
+      .is_err());
+
+    //a different value fails either way
+    let actual_error_kind = format!(
+      "{}",
+      GenericParser::<V2, Local>::default()
+        .check_claim_ci(AudienceClaim::from("otherapp"))
+        .parse(&token, &key)
+        .unwrap_err()
+    );
+    assert_eq!(
+      actual_error_kind,
+      "The claim 'aud' failed validation.  Expected 'otherapp' but received 'MyApp'"
+    );
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn non_object_payload_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<24>::from(*b"wubbalubbadubdubwubbalub");
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+    //build a core token with an array payload
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("[\"customers\",\"loyal subjects\"]"))
+      .try_encrypt(&key, &nonce)?;
+
+    //without any checks the payload is returned as is
+    let json = GenericParser::<V2, Local>::default().parse(&token, &key)?;
+    assert_eq!(json[0], "customers");
+
+    //checking claims against a non-object payload is an error rather than a panic
+    let actual_error_kind = format!(
+      "{}",
+      GenericParser::<V2, Local>::default()
+        .check_claim(AudienceClaim::from("customers"))
+        .parse(&token, &key)
+        .unwrap_err()
+    );
+    let expected_error_kind = "The payload is not a JSON object so its claims cannot be validated";
+    assert_eq!(expected_error_kind, actual_error_kind);
+
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn require_issuer_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(IssuerClaim::from("me"))
+      .try_encrypt(&key)?;
+
+    //the right issuer passes
+    let json = GenericParser::<V2, Local>::default()
+      .require_issuer("me")
+      .parse(&token, &key)?;
+    assert_eq!(json["iss"], "me");
+
+    //the wrong issuer fails
+    let error = GenericParser::<V2, Local>::default()
+      .require_issuer("not me")
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::Invalid(ref key, _, _)
+      } if key == "iss"
+    ));
+
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn reject_duplicate_keys_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<24>::from(*b"wubbalubbadubdubwubbalub");
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+    //hand craft a payload with a duplicate subject
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("{\"sub\":\"loyal subjects\",\"sub\":\"smuggled\"}"))
+      .try_encrypt(&key, &nonce)?;
+
+    //by default the last key wins
+    let json = GenericParser::<V2, Local>::default().parse(&token, &key)?;
+    assert_eq!(json["sub"], "smuggled");
+
+    let actual_error_kind = format!(
+      "{}",
+      GenericParser::<V2, Local>::default()
+        .set_reject_duplicate_keys(true)
+        .parse(&token, &key)
+        .unwrap_err()
+    );
+    let expected_error_kind = "The key 'sub' appears more than once in the top level payload json";
+    assert_eq!(expected_error_kind, actual_error_kind);
+
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn max_claim_count_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let mut builder = GenericBuilder::<V2, Local>::default();
+    for i in 0..5 {
+      builder.set_claim(CustomClaim::try_from((format!("claim{i}"), i))?);
+    }
+    let token = builder.try_encrypt(&key)?;
+
+    let json = GenericParser::<V2, Local>::default()
+      .set_max_claim_count(5)
+      .parse(&token, &key)?;
+    assert_eq!(json["claim4"], 4);
+
+    let error = GenericParser::<V2, Local>::default()
+      .set_max_claim_count(4)
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::TooManyClaims(5, 4)
+      }
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn verify_only_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("archived"))
+      .set_claim(ExpirationClaim::try_from("2019-01-01T00:00:00+00:00")?)
+      .set_footer(Footer::from("some footer"))
+      .try_encrypt(&key)?;
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser
+      .set_footer(Footer::from("some footer"))
+      .check_claim(SubjectClaim::from("someone else"))
+      .validate_claim(ExpirationClaim::default(), &|_, _| Err(PasetoClaimError::Expired));
+
+    //the claims are returned untouched without running any checks or validators
+    let json = parser.verify_only(&token, &key)?;
+    assert_eq!(json["sub"], "archived");
+    assert_eq!(json["exp"], "2019-01-01T00:00:00+00:00");
+    assert!(matches!(
+      parser.parse(&token, &key).unwrap_err(),
+      GenericParserError::ClaimError { .. }
+    ));
+
+    //but the token must still be authentic
+    let other_key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"dubdubwubbalubbadubdubwubbalubba"));
+    assert!(parser.verify_only(&token, &other_key).is_err());
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn claim_type_mismatch_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<24>::from(*b"wubbalubbadubdubwubbalub");
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("{\"sub\":42,\"seats\":4}"))
+      .try_encrypt(&key, &nonce)?;
+
+    //a numeric claim checked against a string is a type mismatch
+    let error = GenericParser::<V2, Local>::default()
+      .check_claim(SubjectClaim::from("loyal subjects"))
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::TypeMismatch { ref key, expected: "string", found: "number" }
+      } if key == "sub"
+    ));
+    let error = GenericParser::<V2, Local>::default()
+      .check_claim_ci(SubjectClaim::from("loyal subjects"))
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::TypeMismatch { .. }
+      }
+    ));
+
+    //while a claim of the right type with the wrong value is invalid
+    let error = GenericParser::<V2, Local>::default()
+      .check_claim(CustomClaim::try_from(("seats", 5))?)
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::Invalid(ref key, ref received, ref expected)
+      } if key == "seats" && received == "4" && expected == "5"
+    ));
+    let error = GenericParser::<V2, Local>::default()
+      .check_claim(CustomClaim::try_from(("seats", "4"))?)
+      .parse(&token, &key)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "The claim 'seats' should be a string but is a number"
+    );
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn numeric_claim_representation_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let nonce = Key::<24>::from(*b"wubbalubbadubdubwubbalub");
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+
+    //an integer claim checked against a payload holding a float
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("{\"seats\":4.0}"))
+      .try_encrypt(&key, &nonce)?;
+    GenericParser::<V2, Local>::default()
+      .check_claim(CustomClaim::try_from(("seats", 4))?)
+      .parse(&token, &key)?;
+    GenericParser::<V2, Local>::default()
+      .check_claim_ci(CustomClaim::try_from(("seats", 4))?)
+      .parse(&token, &key)?;
+
+    //and a float claim checked against a payload holding an integer
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(CustomClaim::try_from(("seats", 4))?)
+      .try_encrypt(&key)?;
+    GenericParser::<V2, Local>::default()
+      .check_claim(CustomClaim::try_from(("seats", 4.0))?)
+      .parse(&token, &key)?;
+
+    //different numbers still fail
+    let error = GenericParser::<V2, Local>::default()
+      .check_claim(CustomClaim::try_from(("seats", 4.5))?)
+      .parse(&token, &key)
+      .unwrap_err();
+    assert!(matches!(
+      error,
+      GenericParserError::ClaimError {
+        source: PasetoClaimError::Invalid(..)
+      }
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn post_processor_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(IssuerClaim::from("ME"))
+      .try_encrypt(&key)?;
+
+    let json = GenericParser::<V2, Local>::default()
+      .check_claim(IssuerClaim::from("ME"))
+      .set_post_processor(Box::new(|json| {
+        //normalize the issuer and inject a derived claim
+        let issuer = json["iss"].as_str().unwrap_or_default().to_lowercase();
+        json["iss"] = issuer.clone().into();
+        json["issuer_domain"] = format!("{}.example.com", issuer).into();
+      }))
+      .parse(&token, &key)?;
+
+    //the post processor runs after validation so the original issuer was checked
+    assert_eq!(json["iss"], "me");
+    assert_eq!(json["issuer_domain"], "me.example.com");
+    Ok(())
+  }
+
+  #[cfg(feature="v4_local")]
+  #[test]
+  fn wrapped_key_footer_test() -> Result<()> {
+    let master = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let data_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+    let wrapped = data_key.try_wrap_pie(&master)?;
+
+    let token = GenericBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from("wrapped"))
+      .set_footer(Footer::from(wrapped.as_str()))
+      .try_encrypt(&data_key)?;
+
+    let json = GenericParser::<V4, Local>::default()
+      .check_claim(SubjectClaim::from("wrapped"))
+      .set_wrapping_key(&master)
+      .parse_with_wrapped_key(&token)?;
+    assert_eq!(json["sub"], "wrapped");
+
+    //another master key can't unwrap the data key
+    let other_master = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from([7u8; 32]));
+    let result = GenericParser::<V4, Local>::default()
+      .set_wrapping_key(&other_master)
+      .parse_with_wrapped_key(&token);
+    assert!(matches!(
+      result,
+      Err(GenericParserError::CipherError {
+        source: PasetoError::InvalidSignature
+      })
+    ));
+
+    //nor can a parser without one
+    assert!(GenericParser::<V4, Local>::default().parse_with_wrapped_key(&token).is_err());
+    Ok(())
+  }
+
+  #[cfg(feature="v4_public")]
+  #[test]
+  fn accepted_keys_test() -> Result<()> {
+    //the old key pair is the one from the test vectors
+    let old_private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+    let old_private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&old_private_key);
+    let old_public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+    let old_public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&old_public_key);
+
+    //the new key pair is derived from a fixed seed
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let new_public_key = Key::<32>::from(signing_key.verifying_key().to_bytes());
+    let new_public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&new_public_key);
+
+    //a token signed before the rotation
+    let token = GenericBuilder::<V4, Public>::default()
+      .set_claim(IssuerClaim::from("me"))
+      .try_sign(&old_private_key)?;
+
+    let json = GenericParser::<V4, Public>::default()
+      .check_claim(IssuerClaim::from("me"))
+      .set_accepted_keys(&[&new_public_key, &old_public_key])
+      .parse_with_accepted_keys(&token)?;
+    assert_eq!(json["iss"], "me");
+
+    //once the old key is retired the token no longer verifies
+    let result = GenericParser::<V4, Public>::default()
+      .set_accepted_keys(&[&new_public_key])
+      .parse_with_accepted_keys(&token);
+    assert!(result.is_err());
+    Ok(())
+  }
+
+  #[cfg(feature="v2_local")]
+  #[test]
+  fn collect_all_errors_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(IssuerClaim::from("me"))
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+
+    let result = GenericParser::<V2, Local>::default()
+      .set_collect_all_errors(true)
+      .check_claim(IssuerClaim::from("you"))
+      .check_claim(SubjectClaim::from("disloyal subjects"))
+      .check_claim(AudienceClaim::from("customers"))
+      .parse(&token, &key);
+
+    match result {
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::Multiple(errors),
+      }) => {
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|err| matches!(err, PasetoClaimError::Missing(key) if key == "aud")));
+        assert_eq!(
+          errors.iter().filter(|err| matches!(err, PasetoClaimError::Invalid(..))).count(),
+          2
+        );
+      }
+      other => panic!("expected every failing claim to be reported, got {:?}", other),
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn parse_with_report_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let footer = Footer::from("some footer");
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(IssuerClaim::from("me"))
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_claim(CustomClaim::try_from(("seats", 4))?)
+      .set_footer(footer)
+      .try_encrypt(&key)?;
+
+    let (json, report) = GenericParser::<V2, Local>::default()
+      .set_footer(footer)
+      .check_claim(IssuerClaim::from("me"))
+      .parse_with_report(&token, &key)?;
+
+    assert_eq!(json["seats"], 4);
+    assert_eq!(
+      report,
+      ParseReport {
+        version: "v2",
+        purpose: "local",
+        footer_present: true,
+        footer_len: "some footer".len(),
+        claim_count: 3,
+        claims_validated: 1,
+        outcome: ValidationOutcome::Passed,
+      }
+    );
+
+    //a token failing validation is still reported
+    let error = GenericParser::<V2, Local>::default()
+      .set_footer(footer)
+      .check_claim(IssuerClaim::from("someone else"))
+      .parse_with_report(&token, &key)
+      .unwrap_err();
+    match error {
+      GenericParserError::ValidationFailed { source, report } => {
+        assert!(matches!(source, PasetoClaimError::Invalid(ref key, _, _) if key == "iss"));
+        assert_eq!(report.claim_count, 3);
+        assert!(matches!(report.outcome, ValidationOutcome::Failed(ref failures) if failures.len() == 1));
+      }
+      other => panic!("expected a reported validation failure, got {:?}", other),
+    }
+    Ok(())
+  }
+
+  #[cfg(all(feature = "v2_local", feature = "jsonschema"))]
+  #[test]
+  fn schema_validation_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let schema = serde_json::json!({
+      "type": "object",
+      "properties": { "sub": { "type": "string" } },
+      "required": ["sub"]
+    });
+
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+    GenericParser::<V2, Local>::default()
+      .set_schema(schema.clone())?
+      .parse(&token, &key)?;
+
+    //a numeric subject violates the schema, so build the payload with the core builder
+    let nonce = Key::<24>::try_new_random()?;
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from(r#"{"sub":42}"#))
+      .try_encrypt(&key, &PasetoNonce::<V2, Local>::from(&nonce))?;
+    let result = GenericParser::<V2, Local>::default()
+      .set_schema(schema)?
+      .parse(&token, &key);
+    assert!(matches!(
+      result,
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::SchemaViolation(_)
+      })
+    ));
+
+    //schemas which can't be compiled are rejected up front
+    assert!(matches!(
+      GenericParser::<V2, Local>::default().set_schema(serde_json::json!({"type": 42})),
+      Err(PasetoClaimError::InvalidSchema(_))
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn parse_map_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_claim(CustomClaim::try_from(("zebra", 1))?)
+      .set_claim(CustomClaim::try_from(("apple", 2))?)
+      .set_claim(IssuerClaim::from("me"))
+      .try_encrypt(&key)?;
+
+    let claims = GenericParser::<V2, Local>::default().parse_map(&token, &key)?;
+    assert_eq!(
+      claims.keys().map(String::as_str).collect::<Vec<_>>(),
+      ["apple", "iss", "sub", "zebra"]
+    );
+    assert_eq!(claims["zebra"], 1);
+
+    //payloads which aren't objects have no claims to map
+    let nonce = Key::<24>::try_new_random()?;
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("[1, 2, 3]"))
+      .try_encrypt(&key, &PasetoNonce::<V2, Local>::from(&nonce))?;
+    assert!(matches!(
+      GenericParser::<V2, Local>::default().parse_map(&token, &key),
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::PayloadNotObject
+      })
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn trailing_empty_footer_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+    let token = format!("{}.", token);
+
+    //by default the empty footer segment is treated as no footer
+    let (json, report) = GenericParser::<V2, Local>::default().parse_with_report(&token, &key)?;
+    assert_eq!(json["sub"], "loyal subjects");
+    assert!(!report.footer_present);
+
+    let result = GenericParser::<V2, Local>::default()
+      .set_reject_empty_footer(true)
+      .parse(&token, &key);
+    assert!(matches!(
+      result,
+      Err(GenericParserError::CipherError {
+        source: PasetoError::MalformedToken { .. }
+      })
+    ));
+
+    //tokens without the trailing dot are unaffected by the toggle
+    GenericParser::<V2, Local>::default()
+      .set_reject_empty_footer(true)
+      .parse(token.trim_end_matches('.'), &key)?;
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn require_footer_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let footered = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_footer(Footer::from("route=eu-west"))
+      .try_encrypt(&key)?;
+    let footerless = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+
+    let json = GenericParser::<V2, Local>::default()
+      .set_footer(Footer::from("route=eu-west"))
+      .require_footer()
+      .parse(&footered, &key)?;
+    assert_eq!(json["sub"], "loyal subjects");
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.require_footer();
+    assert!(matches!(
+      parser.parse(&footerless, &key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::MissingFooter
+      })
+    ));
+    //an empty footer segment is no footer either
+    assert!(matches!(
+      parser.parse(&format!("{}.", footerless), &key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::MissingFooter
+      })
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn validate_time_window_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let now = time::OffsetDateTime::now_utc();
+    let token_for = |from: time::OffsetDateTime, to: time::OffsetDateTime| -> Result<String> {
+      let format = &time::format_description::well_known::Rfc3339;
+      Ok(
+        GenericBuilder::<V2, Local>::default()
+          .set_claim(CustomClaim::try_from(("valid_from", from.format(format)?))?)
+          .set_claim(CustomClaim::try_from(("valid_to", to.format(format)?))?)
+          .try_encrypt(&key)?,
+      )
+    };
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.validate_time_window("valid_from", "valid_to");
+    let in_window = token_for(now - time::Duration::hours(1), now + time::Duration::hours(1))?;
+    parser.parse(&in_window, &key)?;
+
+    let past = token_for(now - time::Duration::hours(2), now - time::Duration::hours(1))?;
+    let future = token_for(now + time::Duration::hours(1), now + time::Duration::hours(2))?;
+    for token in [past, future] {
+      assert!(matches!(
+        parser.parse(&token, &key),
+        Err(GenericParserError::ClaimError {
+          source: PasetoClaimError::OutsideWindow(_, _)
+        })
+      ));
+    }
+
+    //both ends of the window must be present
+    let missing = GenericBuilder::<V2, Local>::default()
+      .set_claim(CustomClaim::try_from(("valid_from", "2019-01-01T00:00:00+00:00"))?)
+      .try_encrypt(&key)?;
+    assert!(matches!(
+      parser.parse(&missing, &key),
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::Missing(ref key)
+      }) if key == "valid_to"
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn check_subject_in_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token_for = |subject: &'static str| {
+      GenericBuilder::<V2, Local>::default()
+        .set_claim(SubjectClaim::from(subject))
+        .try_encrypt(&key)
+    };
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.check_subject_in(&["alice@tenant-a", "bob@tenant-a"]);
+    assert_eq!(parser.parse(&token_for("bob@tenant-a")?, &key)?["sub"], "bob@tenant-a");
+    assert!(matches!(
+      parser.parse(&token_for("mallory@tenant-b")?, &key),
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::SubjectNotAllowed(ref subject)
+      }) if subject == "mallory@tenant-b"
+    ));
+
+    //a token without a subject is rejected too
+    let no_subject = GenericBuilder::<V2, Local>::default().try_encrypt(&key)?;
+    assert!(matches!(
+      parser.parse(&no_subject, &key),
+      Err(GenericParserError::ClaimError {
+        source: PasetoClaimError::Missing(_)
+      })
+    ));
+
+    //the allowed subjects never show up as a claim of their own
+    assert!(matches!(
+      parser.dry_run(&serde_json::json!({})).as_slice(),
+      [PasetoClaimError::Missing(ref claim)] if claim == "sub"
+    ));
+    assert_eq!(parser.parse_with_report(&token_for("bob@tenant-a")?, &key)?.1.claims_validated, 1);
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn opaque_errors_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let wrong_key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"dubdubwubbalubbadubdubwubbalubba"));
+    let token = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+    //flip a character in the middle of the encrypted payload
+    let mut tampered = token.clone().into_bytes();
+    let middle = tampered.len() / 2;
+    tampered[middle] = if tampered[middle] == b'A' { b'B' } else { b'A' };
+    let tampered = String::from_utf8(tampered)?;
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.set_opaque_errors(true);
+    assert_eq!(parser.parse(&token, &key)?["sub"], "loyal subjects");
+    for (token, key) in [(&tampered, &key), (&token, &wrong_key)] {
+      assert!(matches!(
+        parser.parse(token, key),
+        Err(GenericParserError::CipherError {
+          source: PasetoError::VerificationFailed
+        })
+      ));
+    }
+
+    //structural errors found before the key is used are still reported as they are
+    assert!(matches!(
+      parser.parse(&token.replacen("v2.", "v4.", 1), &key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::VersionMismatch { .. }
+      })
+    ));
+
+    //without the setting the specific error is returned
+    assert!(!matches!(
+      GenericParser::<V2, Local>::default().parse(&token, &wrong_key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::VerificationFailed
+      })
+    ));
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn footer_allowlist_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token_with_footer = |footer: &'static str| {
+      GenericBuilder::<V2, Local>::default()
+        .set_claim(SubjectClaim::from("loyal subjects"))
+        .set_footer(Footer::from(footer))
+        .try_encrypt(&key)
+    };
+    let staging = token_with_footer("staging")?;
+    let production = token_with_footer("production")?;
+    let unknown = token_with_footer("development")?;
+    let no_footer = GenericBuilder::<V2, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .try_encrypt(&key)?;
+
+    let mut parser = GenericParser::<V2, Local>::default();
+    parser.set_footer_allowlist(&[b"staging", b"production"]);
+    assert_eq!(parser.parse(&staging, &key)?["sub"], "loyal subjects");
+    assert_eq!(parser.parse(&production, &key)?["sub"], "loyal subjects");
+    assert!(matches!(
+      parser.parse(&unknown, &key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::FooterNotAllowed
+      })
+    ));
+
+    //a missing or empty footer has to be allowed explicitly
+    assert!(matches!(
+      parser.parse(&no_footer, &key),
+      Err(GenericParserError::CipherError {
+        source: PasetoError::FooterNotAllowed
+      })
+    ));
+    assert!(parser.parse(&format!("{}.", no_footer), &key).is_err());
+    parser.set_footer_allowlist(&[b"staging", b""]);
+    parser.parse(&no_footer, &key)?;
+    parser.parse(&format!("{}.", no_footer), &key)?;
+    assert!(parser.parse(&production, &key).is_err());
+    Ok(())
+  }
 }
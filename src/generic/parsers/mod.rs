@@ -1,5 +1,11 @@
 mod error;
+mod footer_kid;
 mod generic_parser;
+mod parse_report;
+mod token_inspection;
 
 pub use error::GenericParserError;
+pub use footer_kid::peek_footer_kid;
 pub use generic_parser::GenericParser;
+pub use parse_report::{ParseReport, ValidationOutcome};
+pub use token_inspection::{inspect_token, TokenInspection};
@@ -0,0 +1,32 @@
+/// Metadata describing a parsed token, intended for security event logging.
+///
+/// Returned alongside the claims by `parse_with_report` on a [GenericParser](super::GenericParser),
+/// or inside [GenericParserError::ValidationFailed](super::GenericParserError::ValidationFailed)
+/// when the token's claims fail validation. Tokens which fail to decrypt or verify have no claims
+/// to describe and return an error without a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseReport {
+  /// The token version, e.g. `"v4"`
+  pub version: &'static str,
+  /// The token purpose, e.g. `"local"`
+  pub purpose: &'static str,
+  /// Whether the token carried a footer segment
+  pub footer_present: bool,
+  /// The decoded length of the footer in bytes
+  pub footer_len: usize,
+  /// The number of top level claims in the payload
+  pub claim_count: usize,
+  /// The number of claim checks and validators configured on the parser
+  pub claims_validated: usize,
+  /// Whether the token's claims passed validation
+  pub outcome: ValidationOutcome,
+}
+
+/// The outcome of validating a token's claims, recorded in a [ParseReport]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationOutcome {
+  /// Every claim check and validator passed
+  Passed,
+  /// The messages of the claim checks and validators which failed
+  Failed(Vec<String>),
+}
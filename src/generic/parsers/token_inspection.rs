@@ -0,0 +1,155 @@
+use super::footer_kid::kid_from_footer;
+use crate::core::{Base64Codec, DefaultBase64Codec, PasetoError};
+
+/// What can be learned about an untrusted token without a key, as returned by [inspect_token].
+///
+/// Nothing here is authenticated until the token has been parsed, so it's only fit for decisions
+/// such as routing or rate limiting which don't grant access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInspection {
+  /// The token version, e.g. `"v4"`
+  pub version: &'static str,
+  /// The token purpose, e.g. `"local"`
+  pub purpose: &'static str,
+  /// Whether the token carried a non-empty footer segment
+  pub footer_present: bool,
+  /// The decoded length of the payload in bytes, including the nonce and tag or the signature
+  pub payload_len: usize,
+  /// Whether the footer is a JSON object with a top level `kid` string
+  pub kid_present: bool,
+}
+
+/// Reads the version, purpose, footer and payload length of an untrusted token in a single keyless
+/// pass, e.g. for a router or rate limiter deciding what to do with a token before parsing it.
+///
+/// Unlike [Paseto::peek_payload_len](crate::core::Paseto::peek_payload_len) the token may be of
+/// any version and purpose, and the footer's key id is read like [peek_footer_kid](super::peek_footer_kid).
+///
+/// # Errors
+///
+/// Returns [PasetoError::WrongHeader] if the token doesn't start with a known version and purpose,
+/// [PasetoError::MalformedToken] if it's missing its payload or has too many segments, or a
+/// decoding error if the payload or footer aren't valid base64 or the footer isn't UTF-8.
+///
+/// # Example usage
+/// ```
+/// # #[cfg(feature = "default")]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+/// let token = PasetoBuilder::<V4, Local>::default()
+///   .set_footer(Footer::from(r#"{"kid":"key-2"}"#))
+///   .build(&key)?;
+///
+/// let inspection = inspect_token(&token)?;
+/// assert_eq!((inspection.version, inspection.purpose), ("v4", "local"));
+/// assert!(inspection.footer_present && inspection.kid_present);
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+pub fn inspect_token(token: &str) -> Result<TokenInspection, PasetoError> {
+  let segments = token.split('.').collect::<Vec<_>>();
+  let (version, purpose) = match (segments.first(), segments.get(1)) {
+    (Some(version), Some(purpose)) => (known_version(version)?, known_purpose(purpose)?),
+    _ => return Err(PasetoError::WrongHeader),
+  };
+  let payload = match segments.len() {
+    3 | 4 if !segments[2].is_empty() => segments[2],
+    3 | 4 => return Err(PasetoError::MalformedToken { reason: "the token payload is empty" }),
+    2 => {
+      return Err(PasetoError::MalformedToken {
+        reason: "the token has a header but no payload",
+      })
+    }
+    _ => {
+      return Err(PasetoError::MalformedToken {
+        reason: "the token has too many segments",
+      })
+    }
+  };
+
+  let footer = match segments.get(3) {
+    Some(footer) if !footer.is_empty() => Some(DefaultBase64Codec::decode(footer)?),
+    _ => None,
+  };
+  let kid_present = match &footer {
+    Some(footer) => kid_from_footer(footer, &["kid"])?.is_some(),
+    None => false,
+  };
+
+  Ok(TokenInspection {
+    version,
+    purpose,
+    footer_present: footer.is_some(),
+    payload_len: DefaultBase64Codec::decode(payload)?.len(),
+    kid_present,
+  })
+}
+
+fn known_version(version: &str) -> Result<&'static str, PasetoError> {
+  match version {
+    "v1" => Ok("v1"),
+    "v2" => Ok("v2"),
+    "v3" => Ok("v3"),
+    "v4" => Ok("v4"),
+    _ => Err(PasetoError::WrongHeader),
+  }
+}
+
+fn known_purpose(purpose: &str) -> Result<&'static str, PasetoError> {
+  match purpose {
+    "local" => Ok("local"),
+    "public" => Ok("public"),
+    _ => Err(PasetoError::WrongHeader),
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+  use crate::generic::*;
+
+  #[test]
+  fn test_inspect_v4_local_token_with_footer() {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = GenericBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_footer(Footer::from(r#"{"kid":"key-1"}"#))
+      .try_encrypt(&key)
+      .unwrap();
+
+    let inspection = inspect_token(&token).unwrap();
+    assert_eq!(
+      inspection,
+      TokenInspection {
+        version: "v4",
+        purpose: "local",
+        footer_present: true,
+        //a 32 byte nonce, the encrypted claims and a 32 byte tag
+        payload_len: 32 + r#"{"sub":"loyal subjects"}"#.len() + 32,
+        kid_present: true,
+      }
+    );
+    assert_eq!(
+      inspection.payload_len,
+      Paseto::<V4, Local>::peek_payload_len(&token).unwrap()
+    );
+  }
+
+  #[test]
+  fn test_inspect_rejects_malformed_tokens() {
+    let token = "v4.local.AAAA.e30";
+    let inspection = inspect_token(token).unwrap();
+    assert!(inspection.footer_present && !inspection.kid_present);
+    assert_eq!(inspection.payload_len, 3);
+
+    assert!(matches!(inspect_token("v9.local.AAAA"), Err(PasetoError::WrongHeader)));
+    assert!(matches!(inspect_token("v4.secret.AAAA"), Err(PasetoError::WrongHeader)));
+    assert!(matches!(inspect_token("v4.local."), Err(PasetoError::MalformedToken { .. })));
+    assert!(matches!(inspect_token("v4.local"), Err(PasetoError::MalformedToken { .. })));
+    assert!(matches!(
+      inspect_token("v4.local.AAAA.e30.e30"),
+      Err(PasetoError::MalformedToken { .. })
+    ));
+  }
+}
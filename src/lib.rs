@@ -535,6 +535,26 @@
 //!
 //!
 
+//the generic and batteries_included layers are only usable with at least one version and purpose
+#[cfg(all(
+    feature = "generic",
+    not(any(
+        feature = "v1_local",
+        feature = "v1_public",
+        feature = "v2_local",
+        feature = "v2_public",
+        feature = "v3_local",
+        feature = "v3_public",
+        feature = "v4_local",
+        feature = "v4_public"
+    ))
+))]
+compile_error!(
+    "the `generic` and `batteries_included` features require at least one version and purpose feature: \
+     enable one or more of `v1_local`, `v1_public`, `v2_local`, `v2_public`, `v3_local`, `v3_public`, \
+     `v4_local` or `v4_public`"
+);
+
 //public interface
 #[cfg(feature = "core")]
 pub mod core;
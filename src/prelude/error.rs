@@ -1,11 +1,13 @@
+use crate::generic::{ErrorKind, GenericBuilderError, GenericParserError};
 use thiserror::Error;
 
 /// Errors from validating claims in a parsed token
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum GeneralPasetoError {
   ///A general, unspecified paseto error
   #[error("A general paseto error occurred")]
-  PasetoError(Box<GeneralPasetoError>),
+  PasetoError(#[source] Box<GeneralPasetoError>),
   #[error("An infallible error occurred")]
   Infallible {
     ///An infallible error
@@ -15,4 +17,117 @@ pub enum GeneralPasetoError {
   ///An error with the data format
   #[error(transparent)]
   RFC3339Date(#[from] time::error::Format),
+  ///An error raised while building a token
+  #[error(transparent)]
+  BuilderError(#[from] GenericBuilderError),
+  ///An error raised while parsing a token
+  #[error(transparent)]
+  ParserError(#[from] GenericParserError),
+}
+
+impl GeneralPasetoError {
+  /// The coarse [ErrorKind] of this error
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      GeneralPasetoError::PasetoError(source) => source.kind(),
+      GeneralPasetoError::BuilderError(source) => source.kind(),
+      GeneralPasetoError::ParserError(source) => source.kind(),
+      GeneralPasetoError::Infallible { .. } | GeneralPasetoError::RFC3339Date(_) => ErrorKind::Other,
+    }
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+  use super::*;
+  use crate::core::PasetoError;
+  use std::error::Error;
+
+  #[test]
+  fn test_builder_error_conversion_preserves_message() {
+    let builder_error = GenericBuilderError::DuplicateTopLevelPayloadClaim("sub".to_string());
+    let expected = builder_error.to_string();
+    let error: GeneralPasetoError = builder_error.into();
+
+    assert!(matches!(error, GeneralPasetoError::BuilderError(_)));
+    assert_eq!(error.to_string(), expected);
+  }
+
+  #[test]
+  fn test_parser_error_conversion_preserves_message_and_source() {
+    let parser_error: GenericParserError = PasetoError::WrongHeader.into();
+    let expected = parser_error.to_string();
+    let error: GeneralPasetoError = parser_error.into();
+
+    assert!(matches!(error, GeneralPasetoError::ParserError(_)));
+    assert_eq!(error.to_string(), expected);
+    let source = error.source().expect("the cipher error should be in the source chain");
+    assert_eq!(source.to_string(), PasetoError::WrongHeader.to_string());
+  }
+
+  #[test]
+  fn test_kind_categorizes_errors() {
+    use crate::prelude::*;
+
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let other_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"dubdubwubbalubbadubdubwubbalubba"));
+    let token = PasetoBuilder::<V4, Local>::default().build(&key).unwrap();
+
+    let malformed = PasetoParser::<V4, Local>::default().parse("v2.local.AAAA", &key).unwrap_err();
+    assert_eq!(malformed.kind(), ErrorKind::MalformedToken);
+
+    let unauthentic = PasetoParser::<V4, Local>::default().parse(&token, &other_key).unwrap_err();
+    assert_eq!(unauthentic.kind(), ErrorKind::Authentication);
+
+    let expired = PasetoBuilder::<V4, Local>::default()
+      .set_claim(ExpirationClaim::try_from("2019-01-01T00:00:00+00:00").unwrap())
+      .build(&key)
+      .unwrap();
+    let expired = PasetoParser::<V4, Local>::default().parse(&expired, &key).unwrap_err();
+    assert_eq!(expired.kind(), ErrorKind::InvalidClaims);
+
+    let reserved = CustomClaim::try_from(("exp", "soon")).unwrap_err();
+    assert_eq!(reserved.kind(), ErrorKind::Configuration);
+
+    assert_eq!(PasetoError::InvalidPaserk { expected: "k4.local." }.kind(), ErrorKind::Key);
+    assert_eq!(
+      PasetoError::PasetoCipherError(Box::new(PasetoError::InvalidSignature)).kind(),
+      ErrorKind::Authentication
+    );
+
+    //the kind survives conversion into the general prelude error
+    let general: GeneralPasetoError = unauthentic.into();
+    assert_eq!(general.kind(), ErrorKind::Authentication);
+    let general: GeneralPasetoError = GenericBuilderError::from(reserved).into();
+    assert_eq!(general.kind(), ErrorKind::Configuration);
+  }
+
+  #[test]
+  fn test_decryption_failure_chain_includes_core_cause() {
+    use crate::prelude::*;
+
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let other_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"dubdubwubbalubbadubdubwubbalubba"));
+    let token = PasetoBuilder::<V4, Local>::default().build(&key).unwrap();
+
+    let error: GeneralPasetoError = PasetoParser::<V4, Local>::default()
+      .parse(&token, &other_key)
+      .unwrap_err()
+      .into();
+    let error = anyhow::Error::from(error);
+    assert!(error.chain().any(|cause| matches!(
+      cause.downcast_ref::<PasetoError>(),
+      Some(PasetoError::InvalidSignature)
+    )));
+    assert!(format!("{:?}", error).contains(&PasetoError::InvalidSignature.to_string()));
+
+    //boxed errors continue the chain rather than ending it
+    let boxed = PasetoError::PasetoCipherError(Box::new(PasetoError::InvalidSignature));
+    assert_eq!(
+      boxed.source().map(ToString::to_string),
+      Some(PasetoError::InvalidSignature.to_string())
+    );
+    let boxed = GeneralPasetoError::PasetoError(Box::new(GenericParserError::from(boxed).into()));
+    assert_eq!(anyhow::Error::from(boxed).chain().count(), 4);
+  }
 }
@@ -0,0 +1,48 @@
+use time::Duration;
+
+/// Expiration rules applied by a [PasetoBuilder](crate::prelude::PasetoBuilder) at build time, so
+/// different kinds of tokens (e.g. short lived access tokens and long lived refresh tokens) can
+/// share one definition of their lifetime instead of setting expirations by hand.
+///
+/// When a builder with a policy has no explicit [ExpirationClaim](crate::generic::ExpirationClaim),
+/// the expiration is set to `default_ttl` from the time of the build. An explicit expiration further
+/// out than `max_ttl` is rejected, as is a non-expiring token when `require_exp` is set.
+///
+/// # Usage
+/// ```
+/// # #[cfg(feature = "default")]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+/// let access_tokens = ExpirationPolicy {
+///   default_ttl: time::Duration::minutes(15),
+///   max_ttl: time::Duration::hours(1),
+///   require_exp: true,
+/// };
+///
+/// let token = PasetoBuilder::<V4, Local>::default()
+///   .with_policy(access_tokens)
+///   .build(&key)?;
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpirationPolicy {
+  /// The lifetime given to tokens built without an explicit expiration
+  pub default_ttl: Duration,
+  /// The longest lifetime a token may be built with
+  pub max_ttl: Duration,
+  /// Whether non-expiring tokens are rejected
+  pub require_exp: bool,
+}
+
+impl Default for ExpirationPolicy {
+  /// Mirrors the builder defaults: a one hour expiration which is required
+  fn default() -> Self {
+    Self {
+      default_ttl: Duration::hours(1),
+      max_ttl: Duration::hours(1),
+      require_exp: true,
+    }
+  }
+}
@@ -25,10 +25,20 @@
 //! - "v4_public" (Sodium Modern Asymmetric Authentication)
 
 mod error;
+mod expiration_policy;
+mod parsed_token;
 mod paseto_builder;
 mod paseto_parser;
+mod shared_parser;
+#[cfg(feature = "v4_local")]
+mod token_pair;
 
 pub use crate::generic::*;
 pub use error::GeneralPasetoError;
+pub use expiration_policy::ExpirationPolicy;
+pub use parsed_token::ParsedToken;
 pub use paseto_builder::PasetoBuilder;
 pub use paseto_parser::PasetoParser;
+pub use shared_parser::SharedParser;
+#[cfg(feature = "v4_local")]
+pub use token_pair::{TokenPair, TokenPairIssuer};
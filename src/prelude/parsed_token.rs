@@ -0,0 +1,220 @@
+use serde_json::Value;
+use std::ops::Deref;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// The claims of a successfully parsed token along with typed accessors for the PASETO time
+/// claims, so decisions like caching don't require re-parsing claim strings, and for any other
+/// claim by key.
+///
+/// Dereferences to the underlying serde_json [Value] so claims can still be indexed directly.
+///
+/// # Usage
+/// ```
+/// # #[cfg(feature = "default")]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+/// let token = PasetoBuilder::<V4, Local>::default()
+///   .set_claim(SubjectClaim::from("loyal subjects"))
+///   .build(&key)?;
+///
+/// let parsed = PasetoParser::<V4, Local>::default().parse_structured(&token, &key)?;
+///
+/// assert_eq!(parsed["sub"], "loyal subjects");
+/// assert_eq!(parsed.subject(), Some("loyal subjects"));
+/// assert!(!parsed.is_expired(time::OffsetDateTime::now_utc()));
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedToken {
+  claims: Value,
+  expires_at: Option<OffsetDateTime>,
+  issued_at: Option<OffsetDateTime>,
+  not_before: Option<OffsetDateTime>,
+}
+
+impl ParsedToken {
+  /// The parsed claims of the token
+  pub fn claims(&self) -> &Value {
+    &self.claims
+  }
+
+  /// Consumes the parsed token returning its claims
+  pub fn into_claims(self) -> Value {
+    self.claims
+  }
+
+  /// The [expiration](crate::generic::ExpirationClaim) of the token, if it has one
+  pub fn expires_at(&self) -> Option<OffsetDateTime> {
+    self.expires_at
+  }
+
+  /// When the token was [issued](crate::generic::IssuedAtClaim), if it has an issued at claim
+  pub fn issued_at(&self) -> Option<OffsetDateTime> {
+    self.issued_at
+  }
+
+  /// The time [before which](crate::generic::NotBeforeClaim) the token must not be used, if it has one
+  pub fn not_before(&self) -> Option<OffsetDateTime> {
+    self.not_before
+  }
+
+  /// The claim `key` if it is a string
+  pub fn get_str(&self, key: &str) -> Option<&str> {
+    self.claims.get(key)?.as_str()
+  }
+
+  /// The claim `key` if it is an integer which fits an `i64`
+  pub fn get_i64(&self, key: &str) -> Option<i64> {
+    self.claims.get(key)?.as_i64()
+  }
+
+  /// The claim `key` if it is a non-negative integer which fits a `u64`
+  pub fn get_u64(&self, key: &str) -> Option<u64> {
+    self.claims.get(key)?.as_u64()
+  }
+
+  /// The claim `key` if it is a number, with integers converted to `f64`
+  pub fn get_f64(&self, key: &str) -> Option<f64> {
+    self.claims.get(key)?.as_f64()
+  }
+
+  /// The claim `key` if it is a boolean
+  pub fn get_bool(&self, key: &str) -> Option<bool> {
+    self.claims.get(key)?.as_bool()
+  }
+
+  /// The [issuer](crate::generic::IssuerClaim) of the token, if it has one
+  pub fn issuer(&self) -> Option<&str> {
+    self.get_str("iss")
+  }
+
+  /// The [subject](crate::generic::SubjectClaim) of the token, if it has one
+  pub fn subject(&self) -> Option<&str> {
+    self.get_str("sub")
+  }
+
+  /// The [audience](crate::generic::AudienceClaim) of the token, if it has one
+  pub fn audience(&self) -> Option<&str> {
+    self.get_str("aud")
+  }
+
+  /// The [token identifier](crate::generic::TokenIdentifierClaim) of the token, if it has one
+  pub fn token_identifier(&self) -> Option<&str> {
+    self.get_str("jti")
+  }
+
+  /// Whether the token is expired at the given time.  Tokens without an expiration never expire.
+  pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+    matches!(self.expires_at(), Some(expires_at) if expires_at <= now)
+  }
+
+  fn time_claim(claims: &Value, key: &str) -> Option<OffsetDateTime> {
+    let value = claims.get(key)?.as_str()?;
+    OffsetDateTime::parse(value, &Rfc3339).ok()
+  }
+}
+
+impl From<Value> for ParsedToken {
+  fn from(claims: Value) -> Self {
+    //the time claims are parsed once up front rather than on every access
+    Self {
+      expires_at: Self::time_claim(&claims, "exp"),
+      issued_at: Self::time_claim(&claims, "iat"),
+      not_before: Self::time_claim(&claims, "nbf"),
+      claims,
+    }
+  }
+}
+
+impl Deref for ParsedToken {
+  type Target = Value;
+
+  fn deref(&self) -> &Self::Target {
+    &self.claims
+  }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod unit_tests {
+
+  use crate::prelude::*;
+  use anyhow::Result;
+  use time::format_description::well_known::Rfc3339;
+  use time::{Duration, OffsetDateTime};
+
+  #[test]
+  fn test_expires_at_matches_builder() -> Result<()> {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let expiration = OffsetDateTime::now_utc() + Duration::minutes(10);
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(ExpirationClaim::try_from(expiration.format(&Rfc3339)?)?)
+      .build(&key)?;
+
+    let parsed = PasetoParser::<V4, Local>::default().parse_structured(&token, &key)?;
+
+    assert_eq!(parsed.expires_at(), Some(expiration));
+    assert!(!parsed.is_expired(OffsetDateTime::now_utc()));
+    assert!(parsed.is_expired(expiration));
+    Ok(())
+  }
+
+  #[test]
+  fn test_non_expiring_token() -> Result<()> {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_no_expiration_danger_acknowledged()
+      .build(&key)?;
+
+    let parsed = PasetoParser::<V4, Local>::default().parse_structured(&token, &key)?;
+
+    assert_eq!(parsed.expires_at(), None);
+    assert!(!parsed.is_expired(OffsetDateTime::now_utc() + Duration::days(365)));
+    Ok(())
+  }
+
+  #[test]
+  fn test_issued_at_and_not_before_match_builder() -> Result<()> {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let issued_at = OffsetDateTime::parse("2019-01-01T00:00:00+00:00", &Rfc3339)?;
+    let not_before = OffsetDateTime::parse("2019-06-01T12:30:00+00:00", &Rfc3339)?;
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(IssuedAtClaim::try_from(issued_at.format(&Rfc3339)?)?)
+      .set_claim(NotBeforeClaim::try_from(not_before.format(&Rfc3339)?)?)
+      .build(&key)?;
+
+    let parsed = PasetoParser::<V4, Local>::default().parse_structured(&token, &key)?;
+
+    assert_eq!(parsed.issued_at(), Some(issued_at));
+    assert_eq!(parsed.not_before(), Some(not_before));
+    Ok(())
+  }
+
+  #[test]
+  fn test_typed_claim_accessors() -> Result<()> {
+    let key = PasetoSymmetricKey::<V4, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = PasetoBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_claim(CustomClaim::try_from(("seats", 4))?)
+      .set_claim(CustomClaim::try_from(("offset", -2))?)
+      .set_claim(CustomClaim::try_from(("pi to 6 digits", 3.141526))?)
+      .set_claim(CustomClaim::try_from(("premium", true))?)
+      .build(&key)?;
+
+    let parsed = PasetoParser::<V4, Local>::default().parse_structured(&token, &key)?;
+
+    assert_eq!(parsed.subject(), Some("loyal subjects"));
+    assert_eq!(parsed.issuer(), None);
+    assert_eq!(parsed.get_u64("seats"), Some(4));
+    assert_eq!(parsed.get_i64("offset"), Some(-2));
+    assert_eq!(parsed.get_u64("offset"), None);
+    assert_eq!(parsed.get_f64("pi to 6 digits"), Some(3.141526));
+    assert_eq!(parsed.get_bool("premium"), Some(true));
+    //a claim of another type is treated as missing rather than coerced
+    assert_eq!(parsed.get_str("seats"), None);
+    assert_eq!(parsed.get_u64("missing"), None);
+    Ok(())
+  }
+}
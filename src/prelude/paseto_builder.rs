@@ -1,3 +1,4 @@
+use super::ExpirationPolicy;
 use crate::generic::*;
 use core::marker::PhantomData;
 use std::collections::HashSet;
@@ -66,6 +67,9 @@ pub struct PasetoBuilder<'a, Version, Purpose> {
   top_level_claims: HashSet<String>,
   dup_top_level_found: (bool, String),
   non_expiring_token: bool,
+  expiration_policy: Option<ExpirationPolicy>,
+  explicit_expiration: Option<String>,
+  current_time: Option<time::OffsetDateTime>,
 }
 
 impl<'a, Version, Purpose> PasetoBuilder<'a, Version, Purpose> {
@@ -77,6 +81,9 @@ impl<'a, Version, Purpose> PasetoBuilder<'a, Version, Purpose> {
       top_level_claims: HashSet::new(),
       non_expiring_token: false,
       dup_top_level_found: (false, String::default()),
+      expiration_policy: None,
+      explicit_expiration: None,
+      current_time: None,
     }
   }
 
@@ -120,11 +127,41 @@ impl<'a, Version, Purpose> PasetoBuilder<'a, Version, Purpose> {
     if !self.top_level_claims.insert(value.get_key().to_string()) {
       self.dup_top_level_found = (true, value.get_key().to_string());
     }
+    //keep the expiration around so an expiration policy can check it at build time
+    if value.get_key() == "exp" {
+      self.explicit_expiration = serde_json::to_value(&value as &dyn erased_serde::Serialize)
+        .ok()
+        .and_then(|claim| claim["exp"].as_str().map(str::to_string));
+    }
 
     self.builder.set_claim(value);
     self
   }
 
+  /// Adds the [PasetoClaim] like [set_claim](Self::set_claim) when it is `Some`, and does nothing
+  /// when it is `None`, so optional claims don't break up a builder chain.
+  ///
+  /// # Example
+  ///```
+  ///# #[cfg(all(feature = "prelude", feature="v2_local"))]
+  ///# {
+  ///   use rusty_paseto::prelude::*;
+  ///     let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  ///  let tenant: Option<&str> = None;
+  ///  let token = PasetoBuilder::<V2, Local>::default()
+  ///    .set_claim(SubjectClaim::from("loyal subjects"))
+  ///    .set_claim_opt(tenant.map(AudienceClaim::from))
+  ///    .build(&key)?;
+  /// # }
+  /// # Ok::<(),anyhow::Error>(())
+  ///   ```
+  pub fn set_claim_opt<T: PasetoClaim + erased_serde::Serialize + Sized + 'a>(&mut self, value: Option<T>) -> &mut Self {
+    if let Some(value) = value {
+      self.set_claim(value);
+    }
+    self
+  }
+
   /// Sets the token to have no expiration date.
   /// A **1 hour** ExpirationClaim is set by default because the use case for non-expiring tokens in the world of security tokens is fairly limited.
   ///  Omitting an expiration claim or forgetting to require one when processing them
@@ -185,7 +222,113 @@ impl<'a, Version, Purpose> PasetoBuilder<'a, Version, Purpose> {
     self
   }
 
+  /// Applies an [ExpirationPolicy] to the token when it is built.
+  ///
+  /// Without an explicit [ExpirationClaim], the token expires `default_ttl` after it is built.
+  ///
+  /// # Errors
+  /// The build fails with [GenericBuilderError::ExceedsMaxTtl] if the expiration is further out
+  /// than `max_ttl`, or with [GenericBuilderError::MissingExpiration] if the token is non-expiring
+  /// and the policy sets `require_exp`.
+  ///
+  /// # Example
+  ///```
+  ///# #[cfg(all(feature = "prelude", feature="v2_local"))]
+  ///# {
+  ///   use rusty_paseto::prelude::*;
+  ///     let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  ///  let refresh_tokens = ExpirationPolicy {
+  ///    default_ttl: time::Duration::days(30),
+  ///    max_ttl: time::Duration::days(30),
+  ///    require_exp: true,
+  ///  };
+  ///  let token = PasetoBuilder::<V2, Local>::default()
+  ///    .with_policy(refresh_tokens)
+  ///    .build(&key)?;
+  /// # }
+  /// # Ok::<(),anyhow::Error>(())
+  ///   ```
+  pub fn with_policy(&mut self, policy: ExpirationPolicy) -> &mut Self {
+    self.expiration_policy = Some(policy);
+    self
+  }
+
+  /// Computes the default `iat`, `nbf` and `exp` claims, and any [ExpirationPolicy] expiration,
+  /// relative to `now` instead of the wall clock, e.g. to mint deterministic tokens in tests.
+  ///
+  /// Time claims set explicitly with [set_claim](Self::set_claim) are left as they are.
+  ///
+  /// # Example
+  ///```
+  ///# #[cfg(all(feature = "prelude", feature="v2_local"))]
+  ///# {
+  ///   use rusty_paseto::prelude::*;
+  ///     let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  ///  let now = time::OffsetDateTime::from_unix_timestamp(1_546_300_800)?;
+  ///  let token = PasetoBuilder::<V2, Local>::default()
+  ///    .set_current_time(now)
+  ///    .build(&key)?;
+  ///  let json = GenericParser::<V2, Local>::default().parse(&token, &key)?;
+  ///  assert_eq!(json["iat"], "2019-01-01T00:00:00Z");
+  ///  assert_eq!(json["exp"], "2019-01-01T01:00:00Z");
+  /// # }
+  /// # Ok::<(),anyhow::Error>(())
+  ///   ```
+  pub fn set_current_time(&mut self, now: time::OffsetDateTime) -> &mut Self {
+    self.current_time = Some(now);
+    self.set_default_time_claims(now);
+    self
+  }
+
+  fn set_default_time_claims(&mut self, now: time::OffsetDateTime) {
+    //the unwraps in this function should be Infallible
+    let current_time = now.format(&Rfc3339).unwrap();
+    if !self.top_level_claims.contains("exp") {
+      let expiration_time = (now + time::Duration::hours(1)).format(&Rfc3339).unwrap();
+      self.builder.set_claim(ExpirationClaim::try_from(expiration_time).unwrap());
+    }
+    if !self.top_level_claims.contains("iat") {
+      self.builder.set_claim(IssuedAtClaim::try_from(current_time.clone()).unwrap());
+    }
+    if !self.top_level_claims.contains("nbf") {
+      self.builder.set_claim(NotBeforeClaim::try_from(current_time).unwrap());
+    }
+  }
+
+  fn apply_expiration_policy(&mut self) -> Result<(), GenericBuilderError> {
+    let policy = match self.expiration_policy {
+      Some(policy) => policy,
+      None => return Ok(()),
+    };
+    if self.non_expiring_token {
+      return match policy.require_exp {
+        true => Err(GenericBuilderError::MissingExpiration),
+        false => Ok(()),
+      };
+    }
+
+    let now = self.current_time.unwrap_or_else(time::OffsetDateTime::now_utc);
+    let expiration = match &self.explicit_expiration {
+      Some(expiration) => time::OffsetDateTime::parse(expiration, &Rfc3339)
+        .map_err(|_| PasetoClaimError::RFC3339Date(expiration.to_string()))?,
+      None => {
+        let expiration = now + policy.default_ttl;
+        let formatted = expiration
+          .format(&Rfc3339)
+          .map_err(|_| PasetoClaimError::RFC3339Date(expiration.to_string()))?;
+        self.builder.set_claim(ExpirationClaim::try_from(formatted)?);
+        expiration
+      }
+    };
+
+    if expiration - now > policy.max_ttl {
+      return Err(GenericBuilderError::ExceedsMaxTtl(policy.max_ttl));
+    }
+    Ok(())
+  }
+
   fn verify_ready_to_build(&mut self) -> Result<(), GenericBuilderError> {
+    self.apply_expiration_policy()?;
     if self.non_expiring_token {
       self.builder.remove_claim("exp");
     }
@@ -229,20 +372,9 @@ where
 
 impl<'a, Version, Purpose> Default for PasetoBuilder<'a, Version, Purpose> {
   fn default() -> Self {
-    //the unwraps in this function should be Infallible
     let mut new_builder = Self::new();
-    let now = time::OffsetDateTime::now_utc();
-    let in_one_hour = now + time::Duration::hours(1);
-
-    let expiration_time = in_one_hour.format(&Rfc3339).unwrap();
-    let current_time = now.format(&Rfc3339).unwrap();
     //set some defaults
-    new_builder
-      .builder
-      .set_claim(ExpirationClaim::try_from(expiration_time).unwrap())
-      .set_claim(IssuedAtClaim::try_from(current_time.clone()).unwrap())
-      .set_claim(NotBeforeClaim::try_from(current_time).unwrap());
-
+    new_builder.set_default_time_claims(time::OffsetDateTime::now_utc());
     new_builder
   }
 }
@@ -304,6 +436,13 @@ impl PasetoBuilder<'_, V1, Local> {
     self.verify_ready_to_build()?;
     self.builder.try_encrypt(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoSymmetricKey<V1, Local>) -> Result<PasetoToken<V1, Local>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v2_local")]
@@ -365,6 +504,13 @@ impl PasetoBuilder<'_, V2, Local> {
     self.verify_ready_to_build()?;
     self.builder.try_encrypt(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoSymmetricKey<V2, Local>) -> Result<PasetoToken<V2, Local>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v3_local")]
@@ -428,10 +574,25 @@ impl PasetoBuilder<'_, V3, Local> {
     self.verify_ready_to_build()?;
     self.builder.try_encrypt(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoSymmetricKey<V3, Local>) -> Result<PasetoToken<V3, Local>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v4_local")]
-impl PasetoBuilder<'_, V4, Local> {
+impl<'a> PasetoBuilder<'a, V4, Local> {
+  /// Records every nonce generated by this builder with a [NonceTracker], failing the build with
+  /// [PasetoError::NonceReuse] if a nonce repeats.
+  #[cfg(feature = "nonce_tracking")]
+  pub fn set_nonce_tracker(&mut self, tracker: &'a NonceTracker) -> &mut Self {
+    self.builder.set_nonce_tracker(tracker);
+    self
+  }
+
   /// Attempts to validate claims meet PASETO standard requirements and then encrypt the token.
   ///
   /// Returns Ok(String) where the string is the encrypted PASETO token.
@@ -491,6 +652,13 @@ impl PasetoBuilder<'_, V4, Local> {
     self.verify_ready_to_build()?;
     self.builder.try_encrypt(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoSymmetricKey<V4, Local>) -> Result<PasetoToken<V4, Local>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v1_public")]
@@ -568,6 +736,13 @@ impl PasetoBuilder<'_, V1, Public> {
     self.verify_ready_to_build()?;
     self.builder.try_sign(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoAsymmetricPrivateKey<V1, Public>) -> Result<PasetoToken<V1, Public>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v2_public")]
@@ -642,6 +817,13 @@ impl PasetoBuilder<'_, V2, Public> {
     self.verify_ready_to_build()?;
     self.builder.try_sign(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoAsymmetricPrivateKey<V2, Public>) -> Result<PasetoToken<V2, Public>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v3_public")]
@@ -723,6 +905,13 @@ impl PasetoBuilder<'_, V3, Public> {
     self.verify_ready_to_build()?;
     self.builder.try_sign(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoAsymmetricPrivateKey<V3, Public>) -> Result<PasetoToken<V3, Public>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(feature = "v4_public")]
@@ -802,6 +991,13 @@ impl PasetoBuilder<'_, V4, Public> {
     self.verify_ready_to_build()?;
     self.builder.try_sign(key)
   }
+
+  /// Builds the token like [build](Self::build), returning it as a [PasetoToken] typed with its
+  /// version and purpose, which only [PasetoParser::parse_token](crate::prelude::PasetoParser) of
+  /// the same version and purpose accepts.
+  pub fn build_token(&mut self, key: &PasetoAsymmetricPrivateKey<V4, Public>) -> Result<PasetoToken<V4, Public>, GenericBuilderError> {
+    self.build(key).map(PasetoToken::from_built)
+  }
 }
 
 #[cfg(all(test, feature = "v2_local"))]
@@ -1005,6 +1201,100 @@ mod paseto_builder {
     Ok(())
   }
 
+  #[test]
+  fn expiration_policy_max_ttl_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let policy = ExpirationPolicy {
+      default_ttl: time::Duration::minutes(15),
+      max_ttl: time::Duration::hours(1),
+      require_exp: true,
+    };
+
+    //within the max ttl
+    let in_30_minutes = (time::OffsetDateTime::now_utc() + time::Duration::minutes(30)).format(&Rfc3339)?;
+    PasetoBuilder::<V2, Local>::default()
+      .with_policy(policy)
+      .set_claim(ExpirationClaim::try_from(in_30_minutes)?)
+      .build(&key)?;
+
+    //beyond the max ttl
+    let in_4_days = (time::OffsetDateTime::now_utc() + time::Duration::days(4)).format(&Rfc3339)?;
+    let error = PasetoBuilder::<V2, Local>::default()
+      .with_policy(policy)
+      .set_claim(ExpirationClaim::try_from(in_4_days)?)
+      .build(&key)
+      .unwrap_err();
+    assert!(matches!(error, GenericBuilderError::ExceedsMaxTtl(ttl) if ttl == time::Duration::hours(1)));
+
+    //non-expiring tokens are rejected when an expiration is required
+    let error = PasetoBuilder::<V2, Local>::default()
+      .with_policy(policy)
+      .set_no_expiration_danger_acknowledged()
+      .build(&key)
+      .unwrap_err();
+    assert!(matches!(error, GenericBuilderError::MissingExpiration));
+
+    //without an explicit expiration the default ttl applies
+    let token = PasetoBuilder::<V2, Local>::default().with_policy(policy).build(&key)?;
+    let json = PasetoParser::<V2, Local>::default().parse(&token, &key)?;
+    let expires = time::OffsetDateTime::parse(json["exp"].as_str().unwrap(), &Rfc3339)?;
+    let ttl = expires - time::OffsetDateTime::now_utc();
+    assert!(ttl <= time::Duration::minutes(15) && ttl > time::Duration::minutes(14));
+
+    Ok(())
+  }
+
+  #[test]
+  fn set_claim_opt_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let build = |audience: Option<&str>| -> Result<serde_json::Value> {
+      let token = PasetoBuilder::<V2, Local>::default()
+        .set_claim(SubjectClaim::from("loyal subjects"))
+        .set_claim_opt(audience.map(AudienceClaim::from))
+        .set_claim_opt(None::<IssuerClaim>)
+        .build(&key)?;
+      Ok(PasetoParser::<V2, Local>::default().parse(&token, &key)?)
+    };
+
+    let with_audience = build(Some("customers"))?;
+    let without_audience = build(None)?;
+    assert_eq!(with_audience["aud"], "customers");
+    assert!(without_audience.get("aud").is_none());
+    assert!(with_audience.get("iss").is_none());
+    assert_eq!(with_audience["sub"], without_audience["sub"]);
+    Ok(())
+  }
+
+  #[test]
+  fn current_time_test() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+    let now = time::OffsetDateTime::parse("2019-01-01T00:00:00Z", &Rfc3339)?;
+
+    let token = PasetoBuilder::<V2, Local>::default().set_current_time(now).build(&key)?;
+    let json = GenericParser::<V2, Local>::default().parse(&token, &key)?;
+    assert_eq!(json["iat"], "2019-01-01T00:00:00Z");
+    assert_eq!(json["nbf"], "2019-01-01T00:00:00Z");
+    assert_eq!(json["exp"], "2019-01-01T01:00:00Z");
+
+    //explicit claims and expiration policies are relative to the injected time too
+    let policy = ExpirationPolicy {
+      default_ttl: time::Duration::minutes(15),
+      max_ttl: time::Duration::hours(1),
+      require_exp: true,
+    };
+    let token = PasetoBuilder::<V2, Local>::default()
+      .set_claim(NotBeforeClaim::try_from("2019-01-01T00:05:00Z")?)
+      .set_current_time(now)
+      .with_policy(policy)
+      .build(&key)?;
+    let json = GenericParser::<V2, Local>::default().parse(&token, &key)?;
+    assert_eq!(json["iat"], "2019-01-01T00:00:00Z");
+    assert_eq!(json["nbf"], "2019-01-01T00:05:00Z");
+    assert_eq!(json["exp"], "2019-01-01T00:15:00Z");
+
+    Ok(())
+  }
+
   #[test]
   fn full_paseto_builder_test() -> Result<()> {
     //create a key
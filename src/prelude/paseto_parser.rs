@@ -1,3 +1,4 @@
+use super::{ParsedToken, SharedParser};
 use crate::generic::*;
 use core::marker::PhantomData;
 use serde_json::Value;
@@ -149,7 +150,7 @@ impl<'a, Version, Purpose> PasetoParser<'a, Version, Purpose> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn validate_claim<T: PasetoClaim + 'a + serde::Serialize>(
+    pub fn validate_claim<T: PasetoClaim + Send + Sync + 'a + serde::Serialize>(
         &mut self,
         value: T,
         validation_closure: &'static ValidatorFn,
@@ -189,16 +190,57 @@ impl<'a, Version, Purpose> PasetoParser<'a, Version, Purpose> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn check_claim<T: PasetoClaim + 'static + serde::Serialize>(&mut self, value: T) -> &mut Self {
+    pub fn check_claim<T: PasetoClaim + Send + Sync + 'static + serde::Serialize>(&mut self, value: T) -> &mut Self {
         self.parser.check_claim(value);
         self
     }
 
+    ///Checks a claim like [check_claim](Self::check_claim) but compares string values ignoring case
+    pub fn check_claim_ci<T: PasetoClaim + Send + Sync + 'static + serde::Serialize>(&mut self, value: T) -> &mut Self {
+        self.parser.check_claim_ci(value);
+        self
+    }
+
     ///Sets an optional [Footer] to use during parsing
     pub fn set_footer(&mut self, footer: Footer<'a>) -> &mut Self {
         self.parser.set_footer(footer);
         self
     }
+
+    ///Rejects tokens whose time claims contradict each other, i.e. which don't satisfy
+    ///`nbf <= iat <= exp`, regardless of the current time. Claims missing from the token are
+    ///skipped, and a violation fails parsing with [PasetoClaimError::InconsistentTimeClaims].
+    pub fn require_consistent_time_claims(&mut self) -> &mut Self {
+        self.parser.require_consistent_time_claims();
+        self
+    }
+
+    ///Looks up claims under `alias` when the token has no claim named `standard`, so the default
+    ///`exp` and `nbf` validators can bridge issuers using non-standard names such as `expires`
+    pub fn set_claim_alias(&mut self, standard: &str, alias: &str) -> &mut Self {
+        self.parser.set_claim_alias(standard, alias);
+        self
+    }
+
+    ///Consumes the parser, returning a [SharedParser] whose configuration can no longer change and
+    ///which can parse tokens through a shared reference
+    ///
+    ///Checked claims, validators and post-processors are required to be `Send + Sync` so that the
+    ///[SharedParser] is `Sync` as well.
+    pub fn finalize(self) -> SharedParser<'a, Version, Purpose> {
+        SharedParser::new(self.parser)
+    }
+}
+
+//strips the `Bearer` scheme from an `Authorization` header value
+fn bearer_token(header_value: &str) -> Result<&str, GenericParserError> {
+    match header_value.trim().split_once(char::is_whitespace) {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => Ok(token.trim_start()),
+        _ => Err(PasetoError::MalformedToken {
+            reason: "the header value does not use the Bearer scheme",
+        }
+        .into()),
+    }
 }
 
 impl<'a, Version, Purpose> PasetoParser<'a, Version, Purpose>
@@ -216,15 +258,14 @@ impl<'a, Version, Purpose> Default for PasetoParser<'a, Version, Purpose> {
     fn default() -> Self {
         let mut me = Self::new();
         me.validate_claim(ExpirationClaim::default(), &|_, value| {
-            //let's get the expiration claim value
-            let val = value.as_str().unwrap_or_default();
-
             //check if this is a non-expiring token
-            if val.is_empty() {
+            if value.is_null() {
                 //this means the claim wasn't found, which means this is a non-expiring token
                 //and we can just skip this validation
                 return Ok(());
             }
+            //a claim which is present has to be a date, so a malformed one can't skip the check
+            let val = value.as_str().unwrap_or_default();
             //turn the value into a datetime
             let datetime =
                 time::OffsetDateTime::parse(val, &Rfc3339).map_err(|_| PasetoClaimError::RFC3339Date(val.to_string()))?;
@@ -239,12 +280,12 @@ impl<'a, Version, Purpose> Default for PasetoParser<'a, Version, Purpose> {
             }
         })
             .validate_claim(NotBeforeClaim::default(), &|_, value| {
-                //let's get the expiration claim value
-                let val = value.as_str().unwrap_or_default();
                 //if there is no value here, then the user didn't provide the claim so we just move on
-                if val.is_empty() {
+                if value.is_null() {
                     return Ok(());
                 }
+                //let's get the not before claim value
+                let val = value.as_str().unwrap_or_default();
                 //otherwise let's continue with the validation
                 //turn the value into a datetime
                 let not_before_time =
@@ -320,10 +361,38 @@ impl<'a> PasetoParser<'a, V1, Local> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn parse(&mut self, token: &'a str, key: &'a PasetoSymmetricKey<V1, Local>) -> Result<Value, GenericParserError> {
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V1, Local>) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoSymmetricKey<V1, Local>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V1, Local>,
+        key: &'a PasetoSymmetricKey<V1, Local>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoSymmetricKey<V1, Local>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v2_local")]
@@ -381,10 +450,38 @@ impl<'a> PasetoParser<'a, V2, Local> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn parse(&mut self, token: &'a str, key: &'a PasetoSymmetricKey<V2, Local>) -> Result<Value, GenericParserError> {
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V2, Local>) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoSymmetricKey<V2, Local>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V2, Local>,
+        key: &'a PasetoSymmetricKey<V2, Local>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoSymmetricKey<V2, Local>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v3_local")]
@@ -445,10 +542,38 @@ impl<'a> PasetoParser<'a, V3, Local> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn parse(&mut self, token: &'a str, key: &'a PasetoSymmetricKey<V3, Local>) -> Result<Value, GenericParserError> {
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V3, Local>) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoSymmetricKey<V3, Local>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V3, Local>,
+        key: &'a PasetoSymmetricKey<V3, Local>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoSymmetricKey<V3, Local>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v4_local")]
@@ -509,10 +634,38 @@ impl<'a> PasetoParser<'a, V4, Local> {
     /// # Ok::<(),anyhow::Error>(())
     ///   ```
 
-    pub fn parse(&mut self, token: &'a str, key: &'a PasetoSymmetricKey<V4, Local>) -> Result<Value, GenericParserError> {
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V4, Local>) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoSymmetricKey<V4, Local>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V4, Local>,
+        key: &'a PasetoSymmetricKey<V4, Local>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoSymmetricKey<V4, Local>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v1_public")]
@@ -588,13 +741,41 @@ impl<'a> PasetoParser<'a, V1, Public> {
     ///```
 
     pub fn parse(
-        &mut self,
+        &self,
         token: &'a str,
         key: &'a PasetoAsymmetricPublicKey<V1, Public>,
     ) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V1, Public>,
+        key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoAsymmetricPublicKey<V1, Public>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v2_public")]
@@ -668,13 +849,41 @@ impl<'a> PasetoParser<'a, V2, Public> {
     ///```
 
     pub fn parse(
-        &mut self,
+        &self,
         token: &'a str,
         key: &'a PasetoAsymmetricPublicKey<V2, Public>,
     ) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V2, Public>,
+        key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoAsymmetricPublicKey<V2, Public>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v3_public")]
@@ -755,13 +964,41 @@ impl<'a> PasetoParser<'a, V3, Public> {
     ///```
 
     pub fn parse(
-        &mut self,
+        &self,
         token: &'a str,
         key: &'a PasetoAsymmetricPublicKey<V3, Public>,
     ) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V3, Public>,
+        key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoAsymmetricPublicKey<V3, Public>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(feature = "v4_public")]
@@ -840,13 +1077,41 @@ impl<'a> PasetoParser<'a, V4, Public> {
     ///```
 
     pub fn parse(
-        &mut self,
+        &self,
         token: &'a str,
         key: &'a PasetoAsymmetricPublicKey<V4, Public>,
     ) -> Result<Value, GenericParserError> {
         //return the full json value to the user
         self.parser.parse(token, key)
     }
+
+    /// Parses the token exactly like [parse](Self::parse), returning a [ParsedToken] with typed
+    /// accessors for its claims.
+    pub fn parse_structured(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+    ) -> Result<ParsedToken, GenericParserError> {
+        self.parse(token, key).map(ParsedToken::from)
+    }
+
+    /// Parses a [PasetoToken] exactly like [parse](Self::parse). The token's version and purpose
+    /// are part of its type, so a token of another version or purpose is rejected at compile time.
+    pub fn parse_token(
+        &self,
+        token: &'a PasetoToken<V4, Public>,
+        key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parse(token.as_str(), key)
+    }
+
+    /// Parses the token carried in an HTTP `Authorization` header value such as `Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively and surrounding whitespace is ignored. A value
+    /// without the `Bearer` scheme fails with [PasetoError::MalformedToken].
+    pub fn parse_bearer(&self, header_value: &'a str, key: &'a PasetoAsymmetricPublicKey<V4, Public>) -> Result<Value, GenericParserError> {
+        self.parse(bearer_token(header_value)?, key)
+    }
 }
 
 #[cfg(all(test, feature = "v3_public"))]
@@ -919,6 +1184,40 @@ mod paseto_parser_unit_tests {
         Ok(())
     }
 
+    #[cfg(feature="v2_local")]
+    #[test]
+    fn claim_cleared_token_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        //a token with no exp, iat or nbf at all skips the built-in time validators
+        let token = GenericBuilder::<V2, Local>::default().try_encrypt(&key)?;
+        let json = PasetoParser::<V2, Local>::default().parse(&token, &key)?;
+        assert_eq!(json, serde_json::json!({}));
+
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_no_expiration_danger_acknowledged()
+            .set_claim(NotBeforeClaim::try_from("2019-01-01T00:00:00+00:00")?)
+            .build(&key)?;
+        PasetoParser::<V2, Local>::default().parse(&token, &key)?;
+
+        //a time claim which is present but not a date still fails rather than being skipped
+        for payload in [r#"{"exp":""}"#, r#"{"exp":4102444800}"#, r#"{"nbf":false}"#] {
+            //the builders reject these shapes, so the raw payload is encrypted with the core API
+            let nonce = Key::<24>::try_new_random()?;
+            let token = Paseto::<V2, Local>::builder()
+                .set_payload(Payload::from(payload))
+                .try_encrypt(&key, &PasetoNonce::<V2, Local>::from(&nonce))?;
+            let error = PasetoParser::<V2, Local>::default().parse(&token, &key).unwrap_err();
+            assert!(
+                matches!(error, GenericParserError::ClaimError { source: PasetoClaimError::RFC3339Date(_) }),
+                "unexpected error for {}: {:?}",
+                payload,
+                error
+            );
+        }
+        Ok(())
+    }
+
     #[cfg(feature="v2_local")]
     #[test]
     fn non_expiring_token_claim_test() -> Result<()> {
@@ -945,6 +1244,49 @@ mod paseto_parser_unit_tests {
         Ok(())
     }
 
+    #[cfg(feature="v2_local")]
+    #[test]
+    fn consistent_time_claims_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let tomorrow = (time::OffsetDateTime::now_utc() + time::Duration::days(1)).format(&Rfc3339)?;
+        let in_2_days = (time::OffsetDateTime::now_utc() + time::Duration::days(2)).format(&Rfc3339)?;
+
+        //nbf (now) <= iat (tomorrow) <= exp (in 2 days)
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(IssuedAtClaim::try_from(tomorrow.as_str())?)
+            .set_claim(ExpirationClaim::try_from(in_2_days)?)
+            .build(&key)?;
+        PasetoParser::<V2, Local>::default()
+            .require_consistent_time_claims()
+            .parse(&token, &key)?;
+
+        //issued after it expires
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(IssuedAtClaim::try_from(tomorrow.as_str())?)
+            .build(&key)?;
+        PasetoParser::<V2, Local>::default().parse(&token, &key)?;
+        let error = PasetoParser::<V2, Local>::default()
+            .require_consistent_time_claims()
+            .parse(&token, &key)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            GenericParserError::ClaimError {
+                source: PasetoClaimError::InconsistentTimeClaims
+            }
+        ));
+
+        //missing claims are skipped
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(IssuedAtClaim::try_from(tomorrow.as_str())?)
+            .set_no_expiration_danger_acknowledged()
+            .build(&key)?;
+        PasetoParser::<V2, Local>::default()
+            .require_consistent_time_claims()
+            .parse(&token, &key)?;
+        Ok(())
+    }
+
     #[cfg(feature="v2_local")]
     #[test]
     fn expired_token_claim_test() -> Result<()> {
@@ -1227,4 +1569,167 @@ mod paseto_parser_unit_tests {
         assert_eq!(json["seats"], 4);
         Ok(())
     }
+
+    #[cfg(feature="v2_local")]
+    #[test]
+    fn parse_through_shared_reference_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let first = PasetoBuilder::<V2, Local>::default()
+            .set_claim(SubjectClaim::from("first"))
+            .build(&key)?;
+        let second = PasetoBuilder::<V2, Local>::default()
+            .set_claim(SubjectClaim::from("second"))
+            .build(&key)?;
+
+        //the parser is only ever borrowed immutably
+        let parser = PasetoParser::<V2, Local>::default();
+        let parser = &parser;
+
+        assert_eq!(parser.parse(&first, &key)?["sub"], "first");
+        assert_eq!(parser.parse(&second, &key)?["sub"], "second");
+        Ok(())
+    }
+
+    #[cfg(all(feature = "v2_local", feature = "http"))]
+    #[test]
+    fn http_status_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+
+        //malformed input is the client's fault
+        let error = PasetoParser::<V2, Local>::default()
+            .parse("v2.local.not-a-token!", &key)
+            .unwrap_err();
+        assert_eq!(error.http_status(), 400);
+
+        //an expired token is well formed but unauthorized
+        let expired = (time::OffsetDateTime::now_utc() + time::Duration::minutes(-10)).format(&Rfc3339)?;
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(ExpirationClaim::try_from(expired)?)
+            .build(&key)?;
+        let error = PasetoParser::<V2, Local>::default().parse(&token, &key).unwrap_err();
+        assert_eq!(error.http_status(), 401);
+
+        //as is one encrypted with another key
+        let other_key = PasetoSymmetricKey::<V2, Local>::from(Key::from([7u8; 32]));
+        let token = PasetoBuilder::<V2, Local>::default().build(&other_key)?;
+        let error = PasetoParser::<V2, Local>::default().parse(&token, &key).unwrap_err();
+        assert_eq!(error.http_status(), 401);
+        Ok(())
+    }
+
+    #[cfg(feature = "v2_local")]
+    #[test]
+    fn claim_alias_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let expired = (time::OffsetDateTime::now_utc() + time::Duration::minutes(-10)).format(&Rfc3339)?;
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(CustomClaim::try_from(("expires", expired.as_str()))?)
+            .set_no_expiration_danger_acknowledged()
+            .build(&key)?;
+
+        //without the alias the token looks like it never expires
+        PasetoParser::<V2, Local>::default().parse(&token, &key)?;
+
+        let error = PasetoParser::<V2, Local>::default()
+            .set_claim_alias("exp", "expires")
+            .parse(&token, &key)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "This token is expired");
+
+        //the standard claim wins when both are present
+        let in_an_hour = (time::OffsetDateTime::now_utc() + time::Duration::hours(1)).format(&Rfc3339)?;
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(ExpirationClaim::try_from(in_an_hour)?)
+            .set_claim(CustomClaim::try_from(("expires", expired.as_str()))?)
+            .build(&key)?;
+        PasetoParser::<V2, Local>::default()
+            .set_claim_alias("exp", "expires")
+            .parse(&token, &key)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "v2_local")]
+    #[test]
+    fn validator_parser_shared_across_threads_test() -> Result<()> {
+        use std::sync::Arc;
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PasetoParser<'static, V2, Local>>();
+
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let allowed = PasetoBuilder::<V2, Local>::default()
+            .set_claim(CustomClaim::try_from(("seat", 1))?)
+            .build(&key)?;
+        let denied = PasetoBuilder::<V2, Local>::default()
+            .set_claim(CustomClaim::try_from(("seat", 99))?)
+            .build(&key)?;
+
+        let mut parser = PasetoParser::<V2, Local>::default();
+        parser.validate_claim(CustomClaim::try_from("seat")?, &|key, value| {
+            match value.as_u64() {
+                Some(seat) if seat <= 10 => Ok(()),
+                _ => Err(PasetoClaimError::CustomValidation(key.to_string())),
+            }
+        });
+        let parser = Arc::new(parser);
+
+        std::thread::scope(|scope| {
+            let first = {
+                let parser = Arc::clone(&parser);
+                let (token, key) = (&allowed, &key);
+                scope.spawn(move || parser.parse(token, key).is_ok())
+            };
+            let second = {
+                let parser = Arc::clone(&parser);
+                let (token, key) = (&denied, &key);
+                scope.spawn(move || parser.parse(token, key).is_ok())
+            };
+            assert!(first.join().unwrap());
+            assert!(!second.join().unwrap());
+        });
+        Ok(())
+    }
+    #[cfg(feature = "v2_local")]
+    #[test]
+    fn parse_bearer_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .build(&key)?;
+        let parser = PasetoParser::<V2, Local>::default();
+
+        let header = format!("Bearer {}", token);
+        assert_eq!(parser.parse_bearer(&header, &key)?["sub"], "loyal subjects");
+        let header = format!("  bearer   {}  ", token);
+        assert_eq!(parser.parse_bearer(&header, &key)?["sub"], "loyal subjects");
+
+        //the scheme is required
+        for header in [token.as_str(), "Basic dXNlcjpwYXNz", "Bearer", ""] {
+            let error = parser.parse_bearer(header, &key).unwrap_err();
+            assert!(matches!(
+                error,
+                GenericParserError::CipherError {
+                    source: PasetoError::MalformedToken { .. }
+                }
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn typed_token_round_trip_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V2, Local>::from(Key::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let token = PasetoBuilder::<V2, Local>::default()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .build_token(&key)?;
+        assert!(token.as_str().starts_with("v2.local."));
+
+        let json = PasetoParser::<V2, Local>::default().parse_token(&token, &key)?;
+        assert_eq!(json["sub"], "loyal subjects");
+
+        //a token string typed after the fact parses the same way
+        let received = PasetoToken::<V2, Local>::try_from(token.to_string())?;
+        assert_eq!(PasetoParser::<V2, Local>::default().parse_token(&received, &key)?, json);
+        Ok(())
+    }
 }
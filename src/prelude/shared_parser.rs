@@ -0,0 +1,151 @@
+use crate::generic::*;
+use serde_json::Value;
+
+///An immutable [PasetoParser] produced by [PasetoParser::finalize].
+///
+///Its claims, validators and footer can no longer be changed, so [parse](Self::parse) only needs a
+///shared reference and the parser can be shared between threads, for example behind an
+///[Arc](std::sync::Arc).
+///
+///# Usage
+///
+///```
+///# #[cfg(feature = "default")]
+///# {
+///   use rusty_paseto::prelude::*;
+///   # let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+///   # let token = PasetoBuilder::<V4, Local>::default()
+///   #   .set_claim(SubjectClaim::from("loyal subjects"))
+///   #   .build(&key)?;
+///
+///   let mut parser = PasetoParser::<V4, Local>::default();
+///   parser.check_claim(SubjectClaim::from("loyal subjects"));
+///   let parser = parser.finalize();
+///
+///   let json = parser.parse(&token, &key)?;
+///   assert_eq!(json["sub"], "loyal subjects");
+///# }
+///# Ok::<(),anyhow::Error>(())
+///```
+pub struct SharedParser<'a, Version, Purpose> {
+    parser: GenericParser<'a, 'a, Version, Purpose>,
+}
+
+impl<'a, Version, Purpose> SharedParser<'a, Version, Purpose> {
+    pub(crate) fn new(parser: GenericParser<'a, 'a, Version, Purpose>) -> Self {
+        Self { parser }
+    }
+}
+
+#[cfg(feature = "v1_local")]
+impl<'a> SharedParser<'a, V1, Local> {
+    /// Decrypts and validates a (V1, Local) token exactly like [PasetoParser::parse]
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V1, Local>) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v2_local")]
+impl<'a> SharedParser<'a, V2, Local> {
+    /// Decrypts and validates a (V2, Local) token exactly like [PasetoParser::parse]
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V2, Local>) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v3_local")]
+impl<'a> SharedParser<'a, V3, Local> {
+    /// Decrypts and validates a (V3, Local) token exactly like [PasetoParser::parse]
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V3, Local>) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v4_local")]
+impl<'a> SharedParser<'a, V4, Local> {
+    /// Decrypts and validates a (V4, Local) token exactly like [PasetoParser::parse]
+    pub fn parse(&self, token: &'a str, key: &'a PasetoSymmetricKey<V4, Local>) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v1_public")]
+impl<'a> SharedParser<'a, V1, Public> {
+    /// Verifies and validates a (V1, Public) token exactly like [PasetoParser::parse]
+    pub fn parse(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V1, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v2_public")]
+impl<'a> SharedParser<'a, V2, Public> {
+    /// Verifies and validates a (V2, Public) token exactly like [PasetoParser::parse]
+    pub fn parse(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V2, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v3_public")]
+impl<'a> SharedParser<'a, V3, Public> {
+    /// Verifies and validates a (V3, Public) token exactly like [PasetoParser::parse]
+    pub fn parse(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V3, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(feature = "v4_public")]
+impl<'a> SharedParser<'a, V4, Public> {
+    /// Verifies and validates a (V4, Public) token exactly like [PasetoParser::parse]
+    pub fn parse(
+        &self,
+        token: &'a str,
+        key: &'a PasetoAsymmetricPublicKey<V4, Public>,
+    ) -> Result<Value, GenericParserError> {
+        self.parser.parse(token, key)
+    }
+}
+
+#[cfg(all(test, feature = "v4_local"))]
+mod shared_parser_unit_tests {
+    use crate::prelude::*;
+    use anyhow::Result;
+    use std::thread;
+
+    #[test]
+    fn shared_parser_across_threads_test() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+        let first = PasetoBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .set_claim(CustomClaim::try_from(("seat", 1))?)
+            .build(&key)?;
+        let second = PasetoBuilder::<V4, Local>::default()
+            .set_claim(SubjectClaim::from("loyal subjects"))
+            .set_claim(CustomClaim::try_from(("seat", 2))?)
+            .build(&key)?;
+
+        let mut parser = PasetoParser::<V4, Local>::default();
+        parser.check_claim(SubjectClaim::from("loyal subjects"));
+        let parser = parser.finalize();
+
+        thread::scope(|scope| {
+            let first = scope.spawn(|| parser.parse(&first, &key).map(|json| json["seat"].clone()));
+            let second = scope.spawn(|| parser.parse(&second, &key).map(|json| json["seat"].clone()));
+
+            assert_eq!(first.join().unwrap().unwrap(), 1);
+            assert_eq!(second.join().unwrap().unwrap(), 2);
+        });
+        Ok(())
+    }
+}
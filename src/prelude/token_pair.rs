@@ -0,0 +1,191 @@
+use super::{ExpirationPolicy, GeneralPasetoError};
+use crate::generic::*;
+use core::marker::PhantomData;
+use time::Duration;
+
+///The custom claim distinguishing access tokens from refresh tokens
+const TOKEN_USE_CLAIM: &str = "token_use";
+
+/// A short lived access token and the long lived refresh token minted alongside it by a
+/// [TokenPairIssuer].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenPair {
+  /// The token presented to access protected resources
+  pub access_token: String,
+  /// The token exchanged for a new pair once the access token expires
+  pub refresh_token: String,
+}
+
+/// Mints and rotates [TokenPair]s, encoding the common access/refresh token pattern on top of the
+/// [PasetoBuilder](crate::prelude::PasetoBuilder) and [PasetoParser](crate::prelude::PasetoParser).
+///
+/// Each token gets its own random `jti` and a `token_use` claim of either `access` or `refresh`, so a
+/// refresh token can't be used for access and vice versa even when both share a key. Lifetimes come
+/// from an [ExpirationPolicy] per token type, defaulting to 15 minutes and 14 days.
+///
+/// Rotation doesn't revoke the refresh token that was exchanged; track its `jti` if a refresh token
+/// must only be usable once.
+///
+/// # Usage
+/// ```
+/// # #[cfg(feature = "default")]
+/// # {
+/// # use rusty_paseto::prelude::*;
+/// let access_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"wubbalubbadubdubwubbalubbadubdub"));
+/// let refresh_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(b"dubdubwubbalubbadubdubwubbalubba"));
+/// let issuer = TokenPairIssuer::<V4, Local>::new(&access_key, &refresh_key);
+///
+/// let pair = issuer.issue("loyal subjects")?;
+/// let rotated = issuer.rotate(&pair.refresh_token)?;
+///
+/// let claims = PasetoParser::<V4, Local>::default().parse(&rotated.access_token, &access_key)?;
+/// assert_eq!(claims["sub"], "loyal subjects");
+/// # }
+/// # Ok::<(),anyhow::Error>(())
+/// ```
+pub struct TokenPairIssuer<'a, Version, Purpose> {
+  version: PhantomData<Version>,
+  purpose: PhantomData<Purpose>,
+  access_key: &'a PasetoSymmetricKey<Version, Purpose>,
+  refresh_key: &'a PasetoSymmetricKey<Version, Purpose>,
+  access_policy: ExpirationPolicy,
+  refresh_policy: ExpirationPolicy,
+}
+
+impl<'a, Version, Purpose> TokenPairIssuer<'a, Version, Purpose> {
+  ///Creates an issuer minting access tokens with `access_key` and refresh tokens with `refresh_key`,
+  ///which may be the same key
+  pub fn new(
+    access_key: &'a PasetoSymmetricKey<Version, Purpose>,
+    refresh_key: &'a PasetoSymmetricKey<Version, Purpose>,
+  ) -> Self {
+    Self {
+      version: PhantomData,
+      purpose: PhantomData,
+      access_key,
+      refresh_key,
+      access_policy: ExpirationPolicy {
+        default_ttl: Duration::minutes(15),
+        max_ttl: Duration::minutes(15),
+        require_exp: true,
+      },
+      refresh_policy: ExpirationPolicy {
+        default_ttl: Duration::days(14),
+        max_ttl: Duration::days(14),
+        require_exp: true,
+      },
+    }
+  }
+
+  ///Sets the [ExpirationPolicy] applied to access tokens
+  pub fn set_access_policy(&mut self, policy: ExpirationPolicy) -> &mut Self {
+    self.access_policy = policy;
+    self
+  }
+
+  ///Sets the [ExpirationPolicy] applied to refresh tokens
+  pub fn set_refresh_policy(&mut self, policy: ExpirationPolicy) -> &mut Self {
+    self.refresh_policy = policy;
+    self
+  }
+}
+
+impl TokenPairIssuer<'_, V4, Local> {
+  /// Mints a new access and refresh token for `subject`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [GeneralPasetoError] if either token can't be built.
+  pub fn issue(&self, subject: &str) -> Result<TokenPair, GeneralPasetoError> {
+    Ok(TokenPair {
+      access_token: Self::build_token(subject, "access", self.access_policy, self.access_key)?,
+      refresh_token: Self::build_token(subject, "refresh", self.refresh_policy, self.refresh_key)?,
+    })
+  }
+
+  /// Validates `refresh_token` and mints a new pair for the same subject.
+  ///
+  /// # Errors
+  ///
+  /// Returns [GeneralPasetoError] if the refresh token fails to parse, has expired, is not a refresh
+  /// token or has no subject, or if the new pair can't be built.
+  pub fn rotate(&self, refresh_token: &str) -> Result<TokenPair, GeneralPasetoError> {
+    let claims = super::PasetoParser::<V4, Local>::default()
+      .check_claim(CustomClaim::try_from((TOKEN_USE_CLAIM, "refresh")).map_err(GenericParserError::from)?)
+      .parse(refresh_token, self.refresh_key)?;
+    let subject = claims["sub"]
+      .as_str()
+      .ok_or_else(|| GenericParserError::from(PasetoClaimError::Missing("sub".to_string())))?;
+    self.issue(subject)
+  }
+
+  fn build_token(
+    subject: &str,
+    token_use: &str,
+    policy: ExpirationPolicy,
+    key: &PasetoSymmetricKey<V4, Local>,
+  ) -> Result<String, GenericBuilderError> {
+    let jti = hex::encode(Key::<16>::try_new_random()?.as_slice());
+    let token = super::PasetoBuilder::<V4, Local>::default()
+      .with_policy(policy)
+      .set_claim(SubjectClaim::from(subject))
+      .set_claim(TokenIdentifierClaim::from(jti.as_str()))
+      .set_claim(CustomClaim::try_from((TOKEN_USE_CLAIM, token_use))?)
+      .build(key)?;
+    Ok(token)
+  }
+}
+
+#[cfg(test)]
+mod unit_tests {
+  use crate::prelude::*;
+  use anyhow::Result;
+
+  fn keys() -> (PasetoSymmetricKey<V4, Local>, PasetoSymmetricKey<V4, Local>) {
+    (
+      PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub")),
+      PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"dubdubwubbalubbadubdubwubbalubba")),
+    )
+  }
+
+  #[test]
+  fn test_rotation_mints_a_new_pair() -> Result<()> {
+    let (access_key, refresh_key) = keys();
+    let issuer = TokenPairIssuer::<V4, Local>::new(&access_key, &refresh_key);
+    let pair = issuer.issue("loyal subjects")?;
+
+    let access = PasetoParser::<V4, Local>::default().parse(&pair.access_token, &access_key)?;
+    let refresh = PasetoParser::<V4, Local>::default().parse(&pair.refresh_token, &refresh_key)?;
+    assert_eq!(access["token_use"], "access");
+    assert_eq!(refresh["token_use"], "refresh");
+    assert_ne!(access["jti"], refresh["jti"]);
+    assert!(access["exp"].as_str() < refresh["exp"].as_str());
+
+    let rotated = issuer.rotate(&pair.refresh_token)?;
+    assert_ne!(rotated, pair);
+    let access = PasetoParser::<V4, Local>::default().parse(&rotated.access_token, &access_key)?;
+    assert_eq!(access["sub"], "loyal subjects");
+
+    //an access token can't be exchanged for a new pair, even under a shared key
+    let shared = TokenPairIssuer::<V4, Local>::new(&access_key, &access_key);
+    let pair = shared.issue("loyal subjects")?;
+    assert!(shared.rotate(&pair.access_token).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_rotation_rejects_an_expired_refresh_token() -> Result<()> {
+    let (access_key, refresh_key) = keys();
+    let expired = PasetoBuilder::<V4, Local>::default()
+      .set_claim(SubjectClaim::from("loyal subjects"))
+      .set_claim(ExpirationClaim::try_from("2019-01-01T00:00:00+00:00")?)
+      .set_claim(CustomClaim::try_from(("token_use", "refresh"))?)
+      .build(&refresh_key)?;
+
+    let error = TokenPairIssuer::<V4, Local>::new(&access_key, &refresh_key)
+      .rotate(&expired)
+      .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidClaims);
+    Ok(())
+  }
+}
@@ -0,0 +1,12 @@
+#[path = "../benches/support/mod.rs"]
+mod support;
+
+#[test]
+fn claims_payload_matches_requested_size() {
+  for size in support::PAYLOAD_SIZES {
+    let payload = support::claims_payload(size);
+    assert_eq!(payload.len(), size);
+    let claims: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(claims["sub"], "loyal subjects");
+  }
+}
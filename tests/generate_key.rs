@@ -0,0 +1,60 @@
+#[cfg(all(test, feature = "default"))]
+#[path = "../examples/generate_key/keygen.rs"]
+mod keygen;
+
+#[cfg(all(test, feature = "default"))]
+mod generate_key {
+  use super::keygen;
+  use anyhow::Result;
+  use rusty_paseto::prelude::*;
+
+  #[test]
+  fn test_generated_paserks_round_trip() -> Result<()> {
+    let keys = keygen::generate()?;
+    assert!(keys.local.starts_with("k4.local."));
+    assert!(keys.secret.starts_with("k4.secret."));
+    assert!(keys.public.starts_with("k4.public."));
+
+    let local = PasetoSymmetricKey::<V4, Local>::try_from(keys.local.as_str())?;
+    assert_eq!(local.to_paserk(), keys.local);
+    let secret = PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk(&keys.secret)?;
+    let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(&secret);
+    assert_eq!(private_key.to_paserk(), keys.secret);
+    let public_key = PasetoAsymmetricPublicKey::<V4, Public>::try_from(keys.public.as_str())?;
+    assert_eq!(public_key.to_paserk(), keys.public);
+
+    //the decoded keys are usable, and the keypair halves belong together
+    let token = PasetoBuilder::<V4, Local>::default().build(&local)?;
+    PasetoParser::<V4, Local>::default().parse(&token, &local)?;
+    let token = PasetoBuilder::<V4, Public>::default().build(&private_key)?;
+    PasetoParser::<V4, Public>::default().parse(&token, &public_key)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_generated_keys_are_fresh() -> Result<()> {
+    let first = keygen::generate()?;
+    let second = keygen::generate()?;
+    assert_ne!(first.local, second.local);
+    assert_ne!(first.secret, second.secret);
+    Ok(())
+  }
+
+  #[test]
+  fn test_mismatched_paserks_are_rejected() -> Result<()> {
+    let keys = keygen::generate()?;
+    assert!(PasetoSymmetricKey::<V4, Local>::try_from(keys.public.as_str()).is_err());
+    assert!(PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk(&keys.local).is_err());
+    assert!(PasetoAsymmetricPublicKey::<V4, Public>::try_from(keys.secret.as_str()).is_err());
+
+    //a secret key whose public half was swapped for another key's
+    let other = keygen::generate()?;
+    let secret = PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk(&keys.secret)?;
+    let public = PasetoAsymmetricPublicKey::<V4, Public>::try_from(other.public.as_str())?;
+    let mut spliced = secret.to_vec();
+    spliced[32..].copy_from_slice(public.as_ref());
+    let spliced = PasetoAsymmetricPrivateKey::<V4, Public>::from(spliced.as_slice()).to_paserk();
+    assert!(PasetoAsymmetricPrivateKey::<V4, Public>::try_key_from_paserk(&spliced).is_err());
+    Ok(())
+  }
+}
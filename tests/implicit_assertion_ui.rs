@@ -0,0 +1,16 @@
+//the fixtures name every version, so they only build with all of the local versions enabled
+#[cfg(all(
+  test,
+  feature = "v1_local",
+  feature = "v2_local",
+  feature = "v3_local",
+  feature = "v4_local"
+))]
+mod implicit_assertion_ui {
+  #[test]
+  fn test_implicit_assertion_is_v3_and_v4_only() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/implicit_assertion_v1_v2.rs");
+    cases.pass("tests/ui/implicit_assertion_v3_v4.rs");
+  }
+}
@@ -0,0 +1,205 @@
+// Tokens from other PASETO implementations (e.g. `pasetors`) share the wire format checked by the
+// official test vectors, so these tests focus on the claim and footer shapes those libraries emit:
+// RFC 3339 timestamps with a `Z` offset and sub-second precision, and JSON footers carrying a `kid`.
+//
+// The fixed `PASETORS_*` tokens were produced by `pasetors` 0.7.8 (`version4::LocalToken::encrypt` and
+// `version4::PublicToken::sign`) with the keys below, and the fixed `RUSTY_PASETO_*` tokens were
+// produced by this crate and then decrypted and verified by `pasetors` 0.7.8 with the same footer and
+// implicit assertion, so both directions are pinned without a `pasetors` dev-dependency.
+#[cfg(all(test, feature = "batteries_included", feature = "v4_local", feature = "v4_public"))]
+mod interop {
+    use anyhow::Result;
+    use rusty_paseto::core::{Paseto, PasetoNonce};
+    use rusty_paseto::prelude::*;
+    use serde_json::json;
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
+
+    const KID_FOOTER: &str = r#"{"kid":"k4.lid.XxPub51WIAEmbVTmrs-lFoFodxTSKk8RuYEJk3gl-DYB"}"#;
+    const IMPLICIT_ASSERTION: &str = r#"{"test-vector":"interop"}"#;
+    const PRIVATE_KEY: &str = "b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+    const PUBLIC_KEY: &str = "1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+
+    const PASETORS_PAYLOAD: &str =
+        r#"{"data":"hello from pasetors","exp":"2099-01-01T00:00:00+00:00","sub":"interop"}"#;
+    const PASETORS_LOCAL: &str = "v4.local.FfrEEcIfd1m3pp8DHF3_yiXW20DTc-XzycWCsJpsJPpRS7V1h-s21T0rJhV6h7eNS5IAC8BsrTGpZ2b3gGfZz8hKMjuu8cUpZJ-k9iHP2t3IG0A0tY_qJ-1Csh5R0MZS2rlryIkS5L3VeGAGC31ruYrw827V3dnw9102T1HePiqPIWj2CcUYh23Mm79erfNS.eyJraWQiOiJrNC5saWQuWHhQdWI1MVdJQUVtYlZUbXJzLWxGb0ZvZHhUU0trOFJ1WUVKazNnbC1EWUIifQ";
+    const PASETORS_PUBLIC: &str = "v4.public.eyJkYXRhIjoiaGVsbG8gZnJvbSBwYXNldG9ycyIsImV4cCI6IjIwOTktMDEtMDFUMDA6MDA6MDArMDA6MDAiLCJzdWIiOiJpbnRlcm9wIn0yjkIOe_DYFjuUJiB-HuUv7hxxoNXbVU83_0M8L9RtVUGbmIk685AQujILdCXQu6wAFzuWKbQ2pIOTnKu1sbEN.eyJraWQiOiJrNC5saWQuWHhQdWI1MVdJQUVtYlZUbXJzLWxGb0ZvZHhUU0trOFJ1WUVKazNnbC1EWUIifQ";
+
+    const RUSTY_PASETO_PAYLOAD: &str =
+        r#"{"data":"hello from rusty_paseto","exp":"2099-01-01T00:00:00+00:00","sub":"interop"}"#;
+    const RUSTY_PASETO_NONCE: &str = "df654812bac492663825520ba2f6e67cf5ca5bdc13d4e7507a98cc4c2fcc3ad8";
+    const RUSTY_PASETO_LOCAL: &str = "v4.local.32VIErrEkmY4JVILovbmfPXKW9wT1OdQepjMTC_MOtjA4kiqw7_tcaOQ6WZbPT3kxaNbwBQ6YG03aj5f_VFcNT_nwCoXU4g7K4f6MmKOoUyUbgbqnPRXqnjX7eRcESE7TVIY5IGjjjDbxfMDxMfZ-WgUbWQJPCC9vdpujg71JDbkRBvn_uwvZgf1eIM7MtRicAAkIw.eyJraWQiOiJrNC5saWQuWHhQdWI1MVdJQUVtYlZUbXJzLWxGb0ZvZHhUU0trOFJ1WUVKazNnbC1EWUIifQ";
+    const RUSTY_PASETO_PUBLIC: &str = "v4.public.eyJkYXRhIjoiaGVsbG8gZnJvbSBydXN0eV9wYXNldG8iLCJleHAiOiIyMDk5LTAxLTAxVDAwOjAwOjAwKzAwOjAwIiwic3ViIjoiaW50ZXJvcCJ9ioUERabQzJZPvXr0xFKymZ6IvkOeBKYeSN3Ua-aXILRSLOF6P8OZdDLg7o2p9ufIyuMHXhazmuLmEUksIpjmBA.eyJraWQiOiJrNC5saWQuWHhQdWI1MVdJQUVtYlZUbXJzLWxGb0ZvZHhUU0trOFJ1WUVKazNnbC1EWUIifQ";
+
+    // mirrors the registered claims `pasetors::claims::Claims::new()` sets
+    fn pasetors_style_claims() -> String {
+        json!({
+            "iat": "2022-01-01T00:00:00.123456789Z",
+            "nbf": "2022-01-01T00:00:00.123456789Z",
+            "exp": "2099-01-01T00:00:00.123456789Z",
+            "sub": "interop",
+            "data": "hello from another implementation"
+        })
+        .to_string()
+    }
+
+    fn local_key() -> Result<PasetoSymmetricKey<V4, Local>> {
+        Ok(PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?))
+    }
+
+    fn private_key() -> Result<Key<64>> {
+        Ok(Key::<64>::try_from(PRIVATE_KEY)?)
+    }
+
+    fn public_key() -> Result<Key<32>> {
+        Ok(Key::<32>::try_from(PUBLIC_KEY)?)
+    }
+
+    #[test]
+    fn pasetors_v4_local_token_decrypts() -> Result<()> {
+        let key = local_key()?;
+        let payload = Paseto::<V4, Local>::try_decrypt(
+            PASETORS_LOCAL,
+            &key,
+            Footer::from(KID_FOOTER),
+            ImplicitAssertion::from(IMPLICIT_ASSERTION),
+        )?;
+        assert_eq!(payload, PASETORS_PAYLOAD);
+
+        let claims = PasetoParser::<V4, Local>::default()
+            .set_footer(Footer::from(KID_FOOTER))
+            .set_implicit_assertion(ImplicitAssertion::from(IMPLICIT_ASSERTION))
+            .check_claim(SubjectClaim::from("interop"))
+            .parse(PASETORS_LOCAL, &key)?;
+        assert_eq!(claims["data"], "hello from pasetors");
+
+        // the implicit assertion is authenticated, so a token can't be decrypted without it
+        assert!(Paseto::<V4, Local>::try_decrypt(PASETORS_LOCAL, &key, Footer::from(KID_FOOTER), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pasetors_v4_public_token_verifies() -> Result<()> {
+        let public_key = public_key()?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let payload = Paseto::<V4, Public>::try_verify(
+            PASETORS_PUBLIC,
+            &public_key,
+            Footer::from(KID_FOOTER),
+            ImplicitAssertion::from(IMPLICIT_ASSERTION),
+        )?;
+        assert_eq!(payload, PASETORS_PAYLOAD);
+
+        let claims = PasetoParser::<V4, Public>::default()
+            .set_footer(Footer::from(KID_FOOTER))
+            .set_implicit_assertion(ImplicitAssertion::from(IMPLICIT_ASSERTION))
+            .check_claim(SubjectClaim::from("interop"))
+            .parse(PASETORS_PUBLIC, &public_key)?;
+        assert_eq!(claims["data"], "hello from pasetors");
+
+        let footer = Footer::from(KID_FOOTER);
+        assert!(Paseto::<V4, Public>::try_verify(PASETORS_PUBLIC, &public_key, footer, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn v4_local_token_matches_pasetors_verified_vector() -> Result<()> {
+        let key = local_key()?;
+        let nonce = Key::<32>::try_from(RUSTY_PASETO_NONCE)?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(RUSTY_PASETO_PAYLOAD))
+            .set_footer(Footer::from(KID_FOOTER))
+            .set_implicit_assertion(ImplicitAssertion::from(IMPLICIT_ASSERTION))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+        assert_eq!(token, RUSTY_PASETO_LOCAL);
+        Ok(())
+    }
+
+    #[test]
+    fn v4_public_token_matches_pasetors_verified_vector() -> Result<()> {
+        let private_key = private_key()?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from(RUSTY_PASETO_PAYLOAD))
+            .set_footer(Footer::from(KID_FOOTER))
+            .set_implicit_assertion(ImplicitAssertion::from(IMPLICIT_ASSERTION))
+            .try_sign(&private_key)?;
+        assert_eq!(token, RUSTY_PASETO_PUBLIC);
+        Ok(())
+    }
+
+    #[test]
+    fn v4_local_token_with_foreign_claims_parses() -> Result<()> {
+        let key = local_key()?;
+        let nonce = Key::<32>::try_new_random()?;
+        let payload = pasetors_style_claims();
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(Footer::from(KID_FOOTER))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        let claims = PasetoParser::<V4, Local>::default()
+            .set_footer(Footer::from(KID_FOOTER))
+            .require_consistent_time_claims()
+            .check_claim(SubjectClaim::from("interop"))
+            .parse(&token, &key)?;
+        assert_eq!(claims["data"], "hello from another implementation");
+        assert_eq!(claims["exp"], "2099-01-01T00:00:00.123456789Z");
+        Ok(())
+    }
+
+    #[test]
+    fn v4_public_token_with_foreign_claims_verifies() -> Result<()> {
+        let private_key = private_key()?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let public_key = public_key()?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let payload = pasetors_style_claims();
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(Footer::from(KID_FOOTER))
+            .try_sign(&private_key)?;
+
+        let claims = PasetoParser::<V4, Public>::default()
+            .set_footer(Footer::from(KID_FOOTER))
+            .require_consistent_time_claims()
+            .parse(&token, &public_key)?;
+        assert_eq!(claims["sub"], "interop");
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_expired_token_is_rejected() -> Result<()> {
+        let key = local_key()?;
+        let nonce = Key::<32>::try_new_random()?;
+        let payload = json!({"exp": "2020-01-01T00:00:00.5Z"}).to_string();
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        assert!(PasetoParser::<V4, Local>::default().parse(&token, &key).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn issued_time_claims_are_rfc3339_for_other_implementations() -> Result<()> {
+        let key = local_key()?;
+        let token = PasetoBuilder::<V4, Local>::default()
+            .set_footer(Footer::from(KID_FOOTER))
+            .build(&key)?;
+
+        // decrypt with the core API only, as a foreign parser would, then check the timestamps with
+        // the same RFC 3339 parser `pasetors` uses
+        let payload = Paseto::<V4, Local>::try_decrypt(&token, &key, Footer::from(KID_FOOTER), None)?;
+        let claims: serde_json::Value = serde_json::from_str(&payload)?;
+        for claim in ["iat", "nbf", "exp"] {
+            let value = claims[claim].as_str().expect("time claims are strings");
+            assert!(OffsetDateTime::parse(value, &Rfc3339).is_ok(), "{claim} = {value}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(all(test, feature = "default"))]
+#[path = "../examples/key_rotation/keyring.rs"]
+mod keyring;
+
+#[cfg(all(test, feature = "default"))]
+mod key_rotation {
+  use super::keyring::Keyring;
+  use anyhow::Result;
+  use rusty_paseto::prelude::*;
+
+  fn key(bytes: &[u8; 32]) -> PasetoSymmetricKey<V4, Local> {
+    PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(bytes))
+  }
+
+  #[test]
+  fn test_tokens_parse_across_rotation() -> Result<()> {
+    let mut keyring = Keyring::new("key-1", key(b"wubbalubbadubdubwubbalubbadubdub"));
+    let old_token = keyring.mint("loyal subjects")?;
+
+    keyring.rotate("key-2", key(b"dubdubwubbalubbawubbalubbadubdub"));
+    assert_eq!(keyring.current_kid(), "key-2");
+    let new_token = keyring.mint("new subjects")?;
+
+    //old tokens still parse with the key they were minted under
+    assert_eq!(keyring.parse(&old_token)?["sub"], "loyal subjects");
+    assert_eq!(keyring.parse(&new_token)?["sub"], "new subjects");
+
+    //new tokens carry the new key id in the footer: {"kid":"key-2"}
+    assert!(new_token.ends_with(".eyJraWQiOiJrZXktMiJ9"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_unknown_key_id_is_rejected() -> Result<()> {
+    let keyring = Keyring::new("key-1", key(b"wubbalubbadubdubwubbalubbadubdub"));
+    let token = keyring.mint("loyal subjects")?;
+
+    //a keyring which doesn't hold the key the token was minted under can't parse it
+    let keyring = Keyring::new("key-2", key(b"dubdubwubbalubbawubbalubbadubdub"));
+    assert!(keyring.parse(&token).is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,10 @@
+//the fixtures mix V3 and V4 parsers, so they only build with both enabled
+#[cfg(all(test, feature = "v3_local", feature = "v4_local", feature = "v4_public"))]
+mod paseto_token_ui {
+  #[test]
+  fn test_paseto_token_matches_parser() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/paseto_token_mismatch.rs");
+    cases.pass("tests/ui/paseto_token_match.rs");
+  }
+}
@@ -0,0 +1,9 @@
+use rusty_paseto::prelude::*;
+
+fn main() {
+  PasetoBuilder::<V1, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  PasetoBuilder::<V2, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  PasetoParser::<V1, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  GenericBuilder::<V2, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  GenericParser::<V2, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+}
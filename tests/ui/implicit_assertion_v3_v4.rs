@@ -0,0 +1,9 @@
+use rusty_paseto::prelude::*;
+
+fn main() {
+  PasetoBuilder::<V3, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  PasetoBuilder::<V4, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  PasetoParser::<V3, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  GenericBuilder::<V4, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+  GenericParser::<V4, Local>::default().set_implicit_assertion(ImplicitAssertion::from("assertion"));
+}
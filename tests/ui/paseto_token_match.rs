@@ -0,0 +1,8 @@
+use rusty_paseto::prelude::*;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  let token = PasetoBuilder::<V4, Local>::default().build_token(&key)?;
+  PasetoParser::<V4, Local>::default().parse_token(&token, &key)?;
+  Ok(())
+}
@@ -0,0 +1,16 @@
+use rusty_paseto::prelude::*;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  let token = PasetoBuilder::<V4, Local>::default().build_token(&key)?;
+
+  //a v4.local token handed to a parser of another purpose
+  let public_key = Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub");
+  let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+  PasetoParser::<V4, Public>::default().parse_token(&token, &public_key)?;
+
+  //and to a parser of another version
+  let v3_key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::from(*b"wubbalubbadubdubwubbalubbadubdub"));
+  PasetoParser::<V3, Local>::default().parse_token(&token, &v3_key)?;
+  Ok(())
+}
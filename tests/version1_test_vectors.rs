@@ -1,6 +1,7 @@
 #[cfg(all(test, feature = "v1"))]
 mod v1_test_vectors {
   use anyhow::Result;
+  use base64::prelude::*;
   use rusty_paseto::core::*;
   use serde_json::json;
 
@@ -390,4 +391,129 @@ mod v1_test_vectors {
     //this test is prevented at compile time and passes by defacto
     panic!("non-compileable test")
   }
+
+  #[cfg(feature = "v1_local")]
+  #[test]
+  fn test_1_e_1_lengths() -> Result<()> {
+    let payload = json!({"data": "this is a signed message", "exp":"2019-01-01T00:00:00+00:00"}).to_string();
+    let token = "v1.local.WzhIh1MpbqVNXNt7-HbWvL-JwAym3Tomad9Pc2nl7wK87vGraUVvn2bs8BBNo7jbukCNrkVID0jCK2vr5bP18G78j1bOTbBcP9HZzqnraEdspcjd_PvrxDEhj9cS2MG5fmxtvuoHRp3M24HvxTtql9z26KTfPWxJN5bAJaAM6gos8fnfjJO8oKiqQMaiBP_Cqncmqw8";
+
+    //the body is the nonce, the ciphertext (as long as the payload) and the tag
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v1.local."))?;
+    assert_eq!(body.len(), V1::LOCAL_NONCE_LEN + payload.len() + V1::LOCAL_TAG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v1_public")]
+  #[test]
+  fn test_1_s_1_lengths() -> Result<()> {
+    let private_key = include_bytes!("v1_public_test_vectors_private_key.pk8");
+    let pk: &[u8] = private_key;
+    let private_key = PasetoAsymmetricPrivateKey::<V1, Public>::from(pk);
+    let payload = json!({"data": "this is a signed message", "exp":"2019-01-01T00:00:00+00:00"}).to_string();
+
+    let token = Paseto::<V1, Public>::builder()
+      .set_payload(Payload::from(payload.as_str()))
+      .try_sign(&private_key)?;
+
+    //the body is the payload followed by the signature
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v1.public."))?;
+    assert_eq!(body.len(), payload.len() + V1::PUBLIC_SIG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v1_local")]
+  #[test]
+  fn test_1_malformed_tokens() -> Result<()> {
+    let key = PasetoSymmetricKey::<V1, Local>::from(Key::<32>::from([0; 32]));
+    let malformed = [
+      "",
+      "v1",
+      "v1.local",
+      "v1.local.",
+      "v1.local..",
+      "v1.local.AAAA",
+      "v1.local.AAAA.Zm9vdGVy.",
+    ];
+
+    for token in malformed {
+      let result = Paseto::<V1, Local>::try_decrypt(token, &key, None);
+      assert!(
+        matches!(result, Err(PasetoError::MalformedToken { .. })),
+        "expected a malformed token error for {:?}",
+        token
+      );
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "v1_local")]
+  #[test]
+  fn test_1_decrypt_raw() -> Result<()> {
+    let key = PasetoSymmetricKey::<V1, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V1, Local>::from(&nonce);
+
+    //the payload isn't json so only the raw bytes are meaningful
+    let plaintext = "{\"unterminated\": ";
+    let token = Paseto::<V1, Local>::builder()
+      .set_payload(Payload::from(plaintext))
+      .try_encrypt(&key, &nonce)?;
+
+    let raw = Paseto::<V1, Local>::try_decrypt_raw(&token, &key, None)?;
+    assert_eq!(raw, plaintext.as_bytes());
+    Ok(())
+  }
+
+  #[cfg(feature = "v1_local")]
+  #[test]
+  fn test_1_flipped_tag_bit() -> Result<()> {
+    let key = PasetoSymmetricKey::<V1, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V1, Local>::from(&nonce);
+    let token = Paseto::<V1, Local>::builder()
+      .set_payload(Payload::from("authenticated payload"))
+      .try_encrypt(&key, &nonce)?;
+    assert!(Paseto::<V1, Local>::try_decrypt(&token, &key, None).is_ok());
+
+    //the tag is the end of the payload, so flip a bit in its last byte
+    let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v1.local."))?;
+    *decoded.last_mut().unwrap() ^= 1;
+    let tampered = format!("v1.local.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+
+    let result = Paseto::<V1, Local>::try_decrypt(&tampered, &key, None);
+    assert!(matches!(result, Err(PasetoError::InvalidSignature)), "unexpected result {:?}", result);
+    Ok(())
+  }
+
+  #[cfg(feature = "v1_public")]
+  #[test]
+  fn test_1_public_key_from_rsa_components() -> Result<()> {
+    use ring::rsa::PublicKeyComponents;
+    use ring::signature::{KeyPair, RsaKeyPair};
+
+    let private_key = include_bytes!("v1_public_test_vectors_private_key.pk8");
+    let pk: &[u8] = private_key;
+    let key_pair = RsaKeyPair::from_pkcs8(pk).map_err(|err| anyhow::anyhow!("{}", err))?;
+    let components = PublicKeyComponents::<Vec<u8>>::from(key_pair.public_key());
+
+    let public_key = PasetoAsymmetricPublicKey::<V1, Public>::from_rsa_components(&components.n, &components.e)?;
+    assert_eq!(public_key.as_ref(), include_bytes!("v1_public_test_vectors_public_key.der"));
+
+    let private_key = PasetoAsymmetricPrivateKey::<V1, Public>::from(pk);
+    let token = Paseto::<V1, Public>::builder()
+      .set_payload(Payload::from("signed with components"))
+      .try_sign(&private_key)?;
+    assert_eq!(
+      Paseto::<V1, Public>::try_verify(&token, &public_key, None)?,
+      "signed with components"
+    );
+
+    assert!(PasetoAsymmetricPublicKey::<V1, Public>::from_rsa_components(&[0, 0], &components.e).is_err());
+    Ok(())
+  }
 }
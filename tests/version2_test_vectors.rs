@@ -1,6 +1,7 @@
 #[cfg(all(test, feature = "v2"))]
 mod v2_test_vectors {
   use anyhow::Result;
+  use base64::prelude::*;
   use rusty_paseto::core::*;
   use serde_json::json;
 
@@ -397,4 +398,96 @@ mod v2_test_vectors {
     //this test is prevented at compile time and passes by defacto
     panic!("non-compileable test")
   }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn test_2_e_1_lengths() -> Result<()> {
+    let payload = json!({"data": "this is a signed message", "exp":"2019-01-01T00:00:00+00:00"}).to_string();
+    let token = "v2.local.97TTOvgwIxNGvV80XKiGZg_kD3tsXM_-qB4dZGHOeN1cTkgQ4PnW8888l802W8d9AvEGnoNBY3BnqHORy8a5cC8aKpbA0En8XELw2yDk2f1sVODyfnDbi6rEGMY3pSfCbLWMM2oHJxvlEl2XbQ";
+
+    //the body is the nonce, the ciphertext (as long as the payload) and the tag
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v2.local."))?;
+    assert_eq!(body.len(), V2::LOCAL_NONCE_LEN + payload.len() + V2::LOCAL_TAG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_public")]
+  #[test]
+  fn test_2_s_1_lengths() -> Result<()> {
+    let payload = json!({"data": "this is a signed message", "exp":"2019-01-01T00:00:00+00:00"}).to_string();
+    let token = "v2.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAxOS0wMS0wMVQwMDowMDowMCswMDowMCJ9HQr8URrGntTu7Dz9J2IF23d1M7-9lH9xiqdGyJNvzp4angPW5Esc7C5huy_M8I8_DjJK2ZXC2SUYuOFM-Q_5Cw";
+
+    //the body is the payload followed by the signature
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v2.public."))?;
+    assert_eq!(body.len(), payload.len() + V2::PUBLIC_SIG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn test_2_malformed_tokens() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::from([0; 32]));
+    let malformed = [
+      "",
+      "v2",
+      "v2.local",
+      "v2.local.",
+      "v2.local..",
+      "v2.local.AAAA",
+      "v2.local.AAAA.Zm9vdGVy.",
+    ];
+
+    for token in malformed {
+      let result = Paseto::<V2, Local>::try_decrypt(token, &key, None);
+      assert!(
+        matches!(result, Err(PasetoError::MalformedToken { .. })),
+        "expected a malformed token error for {:?}",
+        token
+      );
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn test_2_decrypt_raw() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+
+    //the payload isn't json so only the raw bytes are meaningful
+    let plaintext = "{\"unterminated\": ";
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from(plaintext))
+      .try_encrypt(&key, &nonce)?;
+
+    let raw = Paseto::<V2, Local>::try_decrypt_raw(&token, &key, None)?;
+    assert_eq!(raw, plaintext.as_bytes());
+    Ok(())
+  }
+
+  #[cfg(feature = "v2_local")]
+  #[test]
+  fn test_2_flipped_tag_bit() -> Result<()> {
+    let key = PasetoSymmetricKey::<V2, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V2, Local>::from(&nonce);
+    let token = Paseto::<V2, Local>::builder()
+      .set_payload(Payload::from("authenticated payload"))
+      .try_encrypt(&key, &nonce)?;
+    assert!(Paseto::<V2, Local>::try_decrypt(&token, &key, None).is_ok());
+
+    //the tag is the end of the payload, so flip a bit in its last byte
+    let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v2.local."))?;
+    *decoded.last_mut().unwrap() ^= 1;
+    let tampered = format!("v2.local.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+
+    let result = Paseto::<V2, Local>::try_decrypt(&tampered, &key, None);
+    assert!(matches!(result, Err(PasetoError::ChaChaCipherError)), "unexpected result {:?}", result);
+    Ok(())
+  }
 }
@@ -1,6 +1,7 @@
 #[cfg(all(test, feature = "v3"))]
 mod v3_test_vectors {
   use anyhow::Result;
+  use base64::prelude::*;
   use rusty_paseto::core::*;
   use serde_json::json;
 
@@ -570,4 +571,115 @@ mod v3_test_vectors {
     // assert_ne!(verify_attempt, "");
     Ok(())
   }
+
+  #[cfg(feature = "v3_local")]
+  #[test]
+  fn test_3_e_1_lengths() -> Result<()> {
+    let payload = json!({"data": "this is a secret message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+    let token = "v3.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADbfcIURX_0pVZVU1mAESUzrKZAsRm2EsD6yBoZYn6cpVZNzSJOhSDN-sRaWjfLU-yn9OJH1J_B8GKtOQ9gSQlb8yk9Iza7teRdkiR89ZFyvPPsVjjFiepFUVcMa-LP18zV77f_crJrVXWa5PDNRkCSeHfBBeg";
+
+    //the body is the nonce, the ciphertext (as long as the payload) and the tag
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v3.local."))?;
+    assert_eq!(body.len(), V3::LOCAL_NONCE_LEN + payload.len() + V3::LOCAL_TAG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v3_public")]
+  #[test]
+  fn test_3_s_1_lengths() -> Result<()> {
+    let payload = json!({"data":"this is a signed message","exp":"2022-01-01T00:00:00+00:00"}).to_string();
+    let token = "v3.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9qqEwwrKHKi5lJ7b9MBKc0G4MGZy0ptUiMv3lAUAaz-JY_zjoqBSIxMxhfAoeNYiSyvfUErj76KOPWm1OeNnBPkTSespeSXDGaDfxeIrl3bRrPEIy7tLwLAIsRzsXkfph";
+
+    //the body is the payload followed by the signature
+    let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v3.public."))?;
+    assert_eq!(body.len(), payload.len() + V3::PUBLIC_SIG_LEN);
+    Ok(())
+  }
+
+  #[cfg(feature = "v3_local")]
+  #[test]
+  fn test_3_malformed_tokens() -> Result<()> {
+    let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::from([0; 32]));
+    let malformed = [
+      "",
+      "v3",
+      "v3.local",
+      "v3.local.",
+      "v3.local..",
+      "v3.local.AAAA",
+      "v3.local.AAAA.Zm9vdGVy.",
+    ];
+
+    for token in malformed {
+      let result = Paseto::<V3, Local>::try_decrypt(token, &key, None, None);
+      assert!(
+        matches!(result, Err(PasetoError::MalformedToken { .. })),
+        "expected a malformed token error for {:?}",
+        token
+      );
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "v3_local")]
+  #[test]
+  fn test_3_decrypt_raw() -> Result<()> {
+    let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V3, Local>::from(&nonce);
+
+    //the payload isn't json so only the raw bytes are meaningful
+    let plaintext = "{\"unterminated\": ";
+    let token = Paseto::<V3, Local>::builder()
+      .set_payload(Payload::from(plaintext))
+      .try_encrypt(&key, &nonce)?;
+
+    let raw = Paseto::<V3, Local>::try_decrypt_raw(&token, &key, None, None)?;
+    assert_eq!(raw, plaintext.as_bytes());
+    Ok(())
+  }
+
+  #[cfg(feature = "v3_local")]
+  #[test]
+  fn test_3_flipped_tag_bit() -> Result<()> {
+    let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V3, Local>::from(&nonce);
+    let token = Paseto::<V3, Local>::builder()
+      .set_payload(Payload::from("authenticated payload"))
+      .try_encrypt(&key, &nonce)?;
+    assert!(Paseto::<V3, Local>::try_decrypt(&token, &key, None, None).is_ok());
+
+    //the tag is the end of the payload, so flip a bit in its last byte
+    let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v3.local."))?;
+    *decoded.last_mut().unwrap() ^= 1;
+    let tampered = format!("v3.local.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+
+    let result = Paseto::<V3, Local>::try_decrypt(&tampered, &key, None, None);
+    assert!(matches!(result, Err(PasetoError::InvalidSignature)), "unexpected result {:?}", result);
+    Ok(())
+  }
+
+  #[cfg(feature = "v3_local")]
+  #[test]
+  fn test_3_additional_data_channel_binding() -> Result<()> {
+    let key = PasetoSymmetricKey::<V3, Local>::from(Key::<32>::try_from(
+      "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+    )?);
+    let nonce = Key::<32>::try_new_random()?;
+    let nonce = PasetoNonce::<V3, Local>::from(&nonce);
+    let token = Paseto::<V3, Local>::builder()
+      .set_payload(Payload::from("claims"))
+      .set_additional_data(b"channel binding")
+      .try_encrypt(&key, &nonce)?;
+
+    let payload = Paseto::<V3, Local>::try_decrypt_with_additional_data(&token, &key, None, None, b"channel binding")?;
+    assert_eq!(payload, "claims");
+    assert!(Paseto::<V3, Local>::try_decrypt_with_additional_data(&token, &key, None, None, b"other binding").is_err());
+    Ok(())
+  }
 }
@@ -1,6 +1,7 @@
 #[cfg(all(test, feature = "v4"))]
 mod v4_test_vectors {
     use anyhow::{Result};
+    use base64::prelude::*;
     use serde_json::json;
 
     use rusty_paseto::core::*;
@@ -426,4 +427,716 @@ mod v4_test_vectors {
         //this test is prevented at compile time and passes by defacto
         panic!("non-compileable test")
     }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_e_1_lengths() -> Result<()> {
+        let payload = json!({"data": "this is a secret message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+        let token = "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XJ5hOb_4v9RmDkneN0S92dx0OW4pgy7omxgf3S8c3LlQg";
+
+        //the body is the nonce, the ciphertext (as long as the payload) and the tag
+        let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v4.local."))?;
+        assert_eq!(body.len(), V4::LOCAL_NONCE_LEN + payload.len() + V4::LOCAL_TAG_LEN);
+        Ok(())
+    }
+
+    #[cfg(feature = "public")]
+    #[test]
+    fn test_4_s_1_lengths() -> Result<()> {
+        let payload = json!({"data": "this is a signed message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+        let token = "v4.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9bg_XBBzds8lTZShVlwwKSgeKpLT3yukTw6JUz3W4h_ExsQV-P0V54zemZDcAxFaSeef1QlXEFtkqxT1ciiQEDA";
+
+        //the body is the payload followed by the signature
+        let body = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v4.public."))?;
+        assert_eq!(body.len(), payload.len() + V4::PUBLIC_SIG_LEN);
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_malformed_tokens() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::from([0; 32]));
+        let malformed = [
+            "",
+            "v4",
+            "v4.local",
+            "v4.local.",
+            "v4.local..",
+            "v4.local.AAAA",
+            "v4.local.AAAA.Zm9vdGVy.",
+        ];
+
+        for token in malformed {
+            let result = Paseto::<V4, Local>::try_decrypt(token, &key, None, None);
+            assert!(
+                matches!(result, Err(PasetoError::MalformedToken { .. })),
+                "expected a malformed token error for {:?}",
+                token
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_decrypt_and_match() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        let footer = Footer::from("opaque");
+
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("an opaque secret"))
+            .set_footer(footer)
+            .try_encrypt(&key, &nonce)?;
+
+        assert!(Paseto::<V4, Local>::decrypt_and_match(&token, &key, b"an opaque secret", footer, None)?);
+        assert!(!Paseto::<V4, Local>::decrypt_and_match(&token, &key, b"another secret!!", footer, None)?);
+        assert!(!Paseto::<V4, Local>::decrypt_and_match(&token, &key, b"an opaque", footer, None)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_custom_base64_codec() -> Result<()> {
+        //a codec which pads its output, so tokens it produces can't be read by the default codec
+        #[derive(Default, Clone, Copy)]
+        struct PaddedCodec;
+
+        impl Base64Codec for PaddedCodec {
+            fn encode(input: &[u8]) -> String {
+                BASE64_URL_SAFE.encode(input)
+            }
+            fn decode(input: &str) -> Result<Vec<u8>, base64::DecodeError> {
+                BASE64_URL_SAFE.decode(input)
+            }
+        }
+
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+
+        //the nonce, a three byte payload and the tag leave a remainder which needs padding
+        let token = Paseto::<V4, Local, PaddedCodec>::builder()
+            .set_payload(Payload::from("abc"))
+            .try_encrypt(&key, &nonce)?;
+        assert!(token.ends_with('='));
+
+        let payload = Paseto::<V4, Local, PaddedCodec>::try_decrypt(&token, &key, None, None)?;
+        assert_eq!(payload, "abc");
+        assert!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_key_from_seed() -> Result<()> {
+        let seed = [42u8; 64];
+        let key = PasetoSymmetricKey::<V4, Local>::from_seed(&seed);
+
+        //the derived key is stable across calls and differs between seeds
+        assert_eq!(key.as_ref(), PasetoSymmetricKey::<V4, Local>::from_seed(&seed).as_ref());
+        assert_ne!(key.as_ref(), PasetoSymmetricKey::<V4, Local>::from_seed(&[43u8; 64]).as_ref());
+        assert_eq!(key.as_ref().len(), V4::LOCAL_KEY_LEN);
+
+        let nonce = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("seeded"))
+            .try_encrypt(&key, &nonce)?;
+
+        let key = PasetoSymmetricKey::<V4, Local>::from_seed(&seed);
+        assert_eq!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None)?, "seeded");
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_decrypt_raw() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+
+        //the payload isn't json so only the raw bytes are meaningful
+        let plaintext = "{\"unterminated\": ";
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(plaintext))
+            .try_encrypt(&key, &nonce)?;
+
+        let raw = Paseto::<V4, Local>::try_decrypt_raw(&token, &key, None, None)?;
+        assert_eq!(raw, plaintext.as_bytes());
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_reencrypt_with_footer() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let payload = json!({"data": "this is a secret message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+        let old_footer = Footer::from(r#"{"kid":"old"}"#);
+        let new_footer = Footer::from(r#"{"kid":"new"}"#);
+
+        let nonce = Key::<32>::try_new_random()?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(old_footer)
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        let new_nonce = Key::<32>::try_new_random()?;
+        let token = Paseto::<V4, Local>::reencrypt_with_footer(
+            &token,
+            &key,
+            old_footer,
+            new_footer,
+            None,
+            &PasetoNonce::<V4, Local>::from(&new_nonce),
+        )?;
+
+        //the claims are unchanged and only the new footer validates
+        assert_eq!(Paseto::<V4, Local>::try_decrypt(&token, &key, new_footer, None)?, payload);
+        assert!(matches!(
+            Paseto::<V4, Local>::try_decrypt(&token, &key, old_footer, None),
+            Err(PasetoError::FooterInvalid)
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_validate_structure() -> Result<()> {
+        let payload = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XJ5hOb_4v9RmDkneN0S92dx0OW4pgy7omxgf3S8c3LlQg";
+        let footer = BASE64_URL_SAFE_NO_PAD.encode(r#"{"kid":"zVhMiPBP9fRf2snEcT7gFTioeA9COcNy9DfgL1W60haN"}"#);
+
+        //structurally valid tokens, with and without a footer
+        Paseto::<V4, Local>::validate_structure(&format!("v4.local.{}", payload))?;
+        Paseto::<V4, Local>::validate_structure(&format!("v4.local.{}.{}", payload, footer))?;
+
+        let invalid = [
+            (format!("v3.local.{}", payload), "wrong version"),
+            (format!("v4.public.{}", payload), "wrong purpose"),
+            ("v4.local.AAAA".to_string(), "too short"),
+            (format!("v4.local.{}!", payload), "payload isn't base64"),
+            (format!("v4.local.{}.{{not base64}}", payload), "footer isn't base64"),
+            (format!("v4.local.{}.{}.extra", payload, footer), "too many parts"),
+            ("v4.local".to_string(), "no payload"),
+        ];
+        for (token, description) in invalid {
+            assert!(
+                Paseto::<V4, Local>::validate_structure(&token).is_err(),
+                "expected {} to be rejected",
+                description
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "public"))]
+    #[test]
+    fn test_4_detached_footer() -> Result<()> {
+        let footer = r#"{"kid":"zVhMiPBP9fRf2snEcT7gFTioeA9COcNy9DfgL1W60haN"}"#;
+
+        //local: encrypt with a footer, then ship the token without it
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("detached local"))
+            .set_footer(Footer::from(footer))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+        let (detached, _) = token.rsplit_once('.').unwrap();
+
+        assert_eq!(
+            Paseto::<V4, Local>::try_decrypt_detached_footer(detached, &key, footer.as_bytes(), None)?,
+            "detached local"
+        );
+        assert!(Paseto::<V4, Local>::try_decrypt_detached_footer(detached, &key, b"wrong", None).is_err());
+        assert!(Paseto::<V4, Local>::try_decrypt_detached_footer(&token, &key, footer.as_bytes(), None).is_err());
+
+        //public: sign with a footer, then ship the token without it
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from("detached public"))
+            .set_footer(Footer::from(footer))
+            .try_sign(&private_key)?;
+        let (detached, _) = token.rsplit_once('.').unwrap();
+
+        assert_eq!(
+            Paseto::<V4, Public>::try_verify_detached_footer(detached, &public_key, footer.as_bytes(), None)?,
+            "detached public"
+        );
+        assert!(Paseto::<V4, Public>::try_verify_detached_footer(detached, &public_key, b"wrong", None).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_encrypt_to_writer() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_from("0000000000000000000000000000000000000000000000000000000000000000")?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        let payload = json!({"data": "this is a secret message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+        let footer = Footer::from(r#"{"kid":"zVhMiPBP9fRf2snEcT7gFTioeA9COcNy9DfgL1W60haN"}"#);
+
+        let mut buffer = Vec::new();
+        Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(footer)
+            .try_encrypt_to(&key, &nonce, &mut buffer)?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(footer)
+            .try_encrypt(&key, &nonce)?;
+
+        assert_eq!(buffer, token.as_bytes());
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_symmetric_key_from_paserk() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::try_from("k4.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8")?;
+        let expected = Key::<32>::try_from("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f")?;
+        assert_eq!(key.as_ref(), expected.as_ref());
+
+        let rejected = [
+            "k3.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8",
+            "k4.public.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8",
+            "k4.local.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yN",
+            "k4.local.not base64",
+        ];
+        for paserk in rejected {
+            assert!(matches!(
+                PasetoSymmetricKey::<V4, Local>::try_from(paserk),
+                Err(PasetoError::InvalidPaserk { expected: "k4.local." })
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_flipped_tag_bit() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let nonce = PasetoNonce::<V4, Local>::from(&nonce);
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("authenticated payload"))
+            .try_encrypt(&key, &nonce)?;
+        assert!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None).is_ok());
+
+        //the tag is the end of the payload, so flip a bit in its last byte
+        let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v4.local."))?;
+        *decoded.last_mut().unwrap() ^= 1;
+        let tampered = format!("v4.local.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+
+        let result = Paseto::<V4, Local>::try_decrypt(&tampered, &key, None, None);
+        assert!(matches!(result, Err(PasetoError::InvalidSignature)), "unexpected result {:?}", result);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "public"))]
+    #[test]
+    fn test_4_peek_payload_len() -> Result<()> {
+        let payload = json!({"data": "this is a secret message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+
+        //local payloads carry a 32 byte nonce and a 32 byte tag
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .set_footer(Footer::from("footer"))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+        assert_eq!(Paseto::<V4, Local>::peek_payload_len(&token)?, payload.len() + 32 + 32);
+
+        //public payloads carry a 64 byte signature
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .try_sign(&private_key)?;
+        assert_eq!(Paseto::<V4, Public>::peek_payload_len(&token)?, payload.len() + 64);
+
+        //the header is still checked
+        assert!(Paseto::<V4, Local>::peek_payload_len(&token).is_err());
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "public"))]
+    #[test]
+    fn test_4_debug_redacts_key_material() -> Result<()> {
+        let hex_key = "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f";
+        let raw_key = Key::<32>::try_from(hex_key)?;
+        let debug = format!("{:?}", raw_key);
+        assert_eq!(debug, "Key<32>(REDACTED)");
+
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(hex_key)?);
+        let debug = format!("{:?}", key);
+        assert_eq!(debug, "PasetoSymmetricKey<V4, Local>(REDACTED)");
+        assert!(!debug.contains("7071"));
+
+        let nonce = PasetoNonce::<V4, Local>::from(&raw_key);
+        let debug = format!("{:?}", nonce);
+        assert_eq!(debug, "PasetoNonce<V4, Local>(REDACTED)");
+
+        let hex_private_key = "b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2";
+        let private_key = Key::<64>::try_from(hex_private_key)?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let debug = format!("{:?}", private_key);
+        assert_eq!(debug, "PasetoAsymmetricPrivateKey<V4, Public>(REDACTED)");
+        assert!(!debug.contains("b4cb"));
+
+        //public keys only show a short fingerprint
+        let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let debug = format!("{:?}", public_key);
+        assert_eq!(debug, "PasetoAsymmetricPublicKey<V4, Public>(1eb9dbbbbc047c03..)");
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "public"))]
+    #[test]
+    fn test_4_additional_data_channel_binding() -> Result<()> {
+        let channel_binding = [0x5au8; 32];
+        let other_binding = [0xa5u8; 32];
+
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("claims"))
+            .set_implicit_assertion(ImplicitAssertion::from("assertion"))
+            .set_additional_data(&channel_binding)
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        let payload = Paseto::<V4, Local>::try_decrypt_with_additional_data(
+            &token,
+            &key,
+            None,
+            ImplicitAssertion::from("assertion"),
+            &channel_binding,
+        )?;
+        assert_eq!(payload, "claims");
+        assert!(Paseto::<V4, Local>::try_decrypt_with_additional_data(
+            &token,
+            &key,
+            None,
+            ImplicitAssertion::from("assertion"),
+            &other_binding
+        )
+        .is_err());
+        //the additional data is not optional once bound
+        assert!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, ImplicitAssertion::from("assertion")).is_err());
+
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from("claims"))
+            .set_additional_data(&channel_binding)
+            .try_sign(&private_key)?;
+
+        let payload =
+            Paseto::<V4, Public>::try_verify_with_additional_data(&token, &public_key, None, None, &channel_binding)?;
+        assert_eq!(payload, "claims");
+        assert!(
+            Paseto::<V4, Public>::try_verify_with_additional_data(&token, &public_key, None, None, &other_binding)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_decrypt_to_writes_nothing_for_tampered_token() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let payload = "a".repeat(4 * 1024 * 1024);
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        let mut plaintext = Vec::new();
+        Paseto::<V4, Local>::try_decrypt_to(&token, &key, None, None, &mut plaintext)?;
+        assert_eq!(plaintext, payload.as_bytes());
+
+        //flip a bit in the first ciphertext byte, right after the nonce
+        let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(token.trim_start_matches("v4.local."))?;
+        decoded[32] ^= 1;
+        let tampered = format!("v4.local.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+
+        let mut plaintext = Vec::new();
+        let result = Paseto::<V4, Local>::try_decrypt_to(&tampered, &key, None, None, &mut plaintext);
+        assert!(matches!(result, Err(PasetoError::InvalidSignature)), "unexpected result {:?}", result);
+        assert!(plaintext.is_empty());
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "public"))]
+    #[test]
+    fn test_4_purpose_mismatch() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let nonce = Key::<32>::try_new_random()?;
+        let local_token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from("claims"))
+            .try_encrypt(&key, &PasetoNonce::<V4, Local>::from(&nonce))?;
+
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let public_token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from("claims"))
+            .try_sign(&private_key)?;
+
+        //a local token presented to a public verifier
+        let result = Paseto::<V4, Public>::try_verify(&local_token, &public_key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::PurposeMismatch { expected: "public" })),
+            "unexpected result {:?}",
+            result
+        );
+
+        //and vice versa
+        let result = Paseto::<V4, Local>::try_decrypt(&public_token, &key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::PurposeMismatch { expected: "local" })),
+            "unexpected result {:?}",
+            result
+        );
+
+        //a different version is reported as such
+        let result = Paseto::<V4, Local>::try_decrypt(&local_token.replacen("v4.", "v2.", 1), &key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::VersionMismatch { expected: "v4" })),
+            "unexpected result {:?}",
+            result
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "public")]
+    #[test]
+    fn test_4_verify_parts() -> Result<()> {
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let public_key = Key::<32>::try_from("1eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let public_key = PasetoAsymmetricPublicKey::<V4, Public>::from(&public_key);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from("claims"))
+            .set_footer(Footer::from("kid-1"))
+            .set_implicit_assertion(ImplicitAssertion::from("assertion"))
+            .try_sign(&private_key)?;
+
+        let parts = token.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 4);
+        let payload = Paseto::<V4, Public>::try_verify_parts(
+            parts[2],
+            Some(parts[3]),
+            &public_key,
+            ImplicitAssertion::from("assertion"),
+        )?;
+        assert_eq!(payload, "claims");
+
+        //the footer is still covered by the signature
+        let result = Paseto::<V4, Public>::try_verify_parts(parts[2], None, &public_key, ImplicitAssertion::from("assertion"));
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_pie_wrapped_key() -> Result<()> {
+        let master = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let data_key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_new_random()?);
+        let wrapped = data_key.try_wrap_pie(&master)?;
+        assert!(wrapped.starts_with("k4.local-wrap.pie."));
+        //a 32 byte tag, 32 byte nonce and the 32 byte wrapped key
+        assert_eq!(BASE64_URL_SAFE_NO_PAD.decode(wrapped.trim_start_matches("k4.local-wrap.pie."))?.len(), 96);
+
+        let unwrapped = PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(&wrapped, &master)?;
+        assert_eq!(unwrapped.as_ref(), data_key.as_ref());
+
+        //flip a bit in the wrapped key
+        let mut decoded = BASE64_URL_SAFE_NO_PAD.decode(wrapped.trim_start_matches("k4.local-wrap.pie."))?;
+        *decoded.last_mut().unwrap() ^= 1;
+        let tampered = format!("k4.local-wrap.pie.{}", BASE64_URL_SAFE_NO_PAD.encode(decoded));
+        assert!(matches!(
+            PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(&tampered, &master),
+            Err(PasetoError::InvalidSignature)
+        ));
+        assert!(matches!(
+            PasetoSymmetricKey::<V4, Local>::try_unwrap_pie(&wrapped.replacen("k4.", "k2.", 1), &master),
+            Err(PasetoError::InvalidPaserk { .. })
+        ));
+        Ok(())
+    }
+
+    fn le64(n: u64) -> Vec<u8> {
+        n.to_le_bytes().to_vec()
+    }
+
+    #[cfg(feature = "public")]
+    #[test]
+    fn test_4_signing_input() -> Result<()> {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let mut expected = le64(4);
+        expected.extend(le64(10));
+        expected.extend(b"v4.public.");
+        expected.extend(le64(3));
+        expected.extend(b"msg");
+        expected.extend(le64(1));
+        expected.extend(b"f");
+        expected.extend(le64(0));
+        assert_eq!(Paseto::<V4, Public>::signing_input(b"msg", b"f", b""), expected);
+
+        //the signature in a real token is over exactly this message
+        let private_key = Key::<64>::try_from("b4cbfb43df4ce210727d953e4a713307fa19bb7d9f85041438d9e11b942a37741eb9dbbbbc047c03fd70604e0071f0987e16b28b757225c11f00415d0e20b1a2")?;
+        let pk: &[u8] = private_key.as_slice();
+        let private_key = PasetoAsymmetricPrivateKey::<V4, Public>::from(pk);
+        let token = Paseto::<V4, Public>::builder()
+            .set_payload(Payload::from("msg"))
+            .set_footer(Footer::from("f"))
+            .set_implicit_assertion(ImplicitAssertion::from("assertion"))
+            .try_sign(&private_key)?;
+        let signed = BASE64_URL_SAFE_NO_PAD.decode(token.split('.').nth(2).unwrap())?;
+        let (payload, signature) = signed.split_at(signed.len() - 64);
+        let verifying_key = VerifyingKey::from_bytes(&<[u8; 32]>::try_from(&pk[32..])?)?;
+        verifying_key.verify(
+            &Paseto::<V4, Public>::signing_input(payload, b"f", b"assertion"),
+            &Signature::from_slice(signature)?,
+        )?;
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_authentication_input() {
+        let mut expected = le64(5);
+        expected.extend(le64(9));
+        expected.extend(b"v4.local.");
+        expected.extend(le64(2));
+        expected.extend([0, 1]);
+        expected.extend(le64(3));
+        expected.extend(b"abc");
+        expected.extend(le64(0));
+        expected.extend(le64(1));
+        expected.extend(b"i");
+        assert_eq!(
+            Paseto::<V4, Local>::authentication_input(&[0, 1], b"abc", b"", b"i"),
+            expected
+        );
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_wrong_prefix_rejected_before_decoding() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        //a large payload which isn't even valid base64, so reaching the decoder would fail differently
+        let payload = "!".repeat(16 * 1024 * 1024);
+
+        let result = Paseto::<V4, Local>::try_decrypt(&format!("v3.local.{}", payload), &key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::VersionMismatch { expected: "v4" })),
+            "unexpected result {:?}",
+            result
+        );
+        let result = Paseto::<V4, Local>::try_decrypt(&format!("v4.public.{}", payload), &key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::PurposeMismatch { expected: "local" })),
+            "unexpected result {:?}",
+            result
+        );
+        let result = Paseto::<V4, Local>::try_decrypt(&format!("k4.local.{}", payload), &key, None, None);
+        assert!(matches!(result, Err(PasetoError::WrongHeader)), "unexpected result {:?}", result);
+
+        //the right header gets as far as decoding
+        let result = Paseto::<V4, Local>::try_decrypt(&format!("v4.local.{}", payload), &key, None, None);
+        assert!(
+            matches!(result, Err(PasetoError::PayloadBase64Decode { .. })),
+            "unexpected result {:?}",
+            result
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "local", feature = "serde"))]
+    #[test]
+    fn test_4_encrypt_claims() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        let claims = json!({"data": "this is a secret message", "exp": "2022-01-01T00:00:00+00:00", "seats": 4});
+        let claims = claims.as_object().expect("claims are an object");
+
+        let token = Paseto::<V4, Local>::encrypt_claims(
+            claims,
+            &key,
+            Footer::from("some footer"),
+            ImplicitAssertion::from("some assertion"),
+        )?;
+        let payload = Paseto::<V4, Local>::try_decrypt(
+            &token,
+            &key,
+            Footer::from("some footer"),
+            ImplicitAssertion::from("some assertion"),
+        )?;
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&payload)?, json!(claims));
+
+        //each call uses a fresh nonce
+        assert_ne!(Paseto::<V4, Local>::encrypt_claims(claims, &key, None, None)?, token);
+        Ok(())
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn test_4_e_2_nonce_from_array() -> Result<()> {
+        let key = PasetoSymmetricKey::<V4, Local>::from(Key::<32>::try_from(
+            "707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f",
+        )?);
+        //the nonce is built straight from the vector's bytes rather than through a Key
+        let nonce = PasetoNonce::<V4, Local>::from([0u8; 32]);
+        let payload = json!({"data": "this is a hidden message", "exp":"2022-01-01T00:00:00+00:00"}).to_string();
+
+        let token = Paseto::<V4, Local>::builder()
+            .set_payload(Payload::from(payload.as_str()))
+            .try_encrypt(&key, &nonce)?;
+        assert_eq!(token, "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvS2csCgglvpk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XIemu9chy3WVKvRBfg6t8wwYHK0ArLxxfZP73W_vfwt5A");
+        assert_eq!(Paseto::<V4, Local>::try_decrypt(&token, &key, None, None)?, payload);
+        Ok(())
+    }
 }